@@ -1,5 +1,5 @@
 use image::ImageReader;
-use ktx2_rw::{BasisCompressionParams, Ktx2Texture, Result, TranscodeFormat, VkFormat};
+use ktx2_rw::{BasisCompressionParams, Ktx2Texture, Result, TranscodeFormat};
 use std::env;
 use std::path::Path;
 
@@ -41,24 +41,12 @@ fn main() -> Result<()> {
 
     println!("Image data size: {} bytes", image_data.len());
 
-    // Step 2: Create KTX2 texture
+    // Step 2: Create KTX2 texture from the pixels
     println!("\n2. Creating KTX2 texture...");
-    let mut texture = Ktx2Texture::create(
-        width,
-        height,
-        1,                       // depth
-        1,                       // layers
-        1,                       // faces
-        1,                       // levels
-        VkFormat::R8G8B8A8Unorm, // vk_format (VK_FORMAT_R8G8B8A8_UNORM)
-    )?;
-
-    // Step 3: Set image data
-    println!("\n3. Setting image data...");
-    texture.set_image_data(0, 0, 0, image_data)?;
-
-    // Step 4: Add metadata
-    println!("\n4. Adding metadata...");
+    let mut texture = Ktx2Texture::from_rgba8(width, height, image_data)?;
+
+    // Step 3: Add metadata
+    println!("\n3. Adding metadata...");
     texture.set_metadata("OriginalFormat", b"PNG")?;
     texture.set_metadata("Tool", b"ktx2-rw PNG converter")?;
     texture.set_metadata("SourceFile", input_path.as_bytes())?;
@@ -67,14 +55,12 @@ fn main() -> Result<()> {
     let dims = format!("{width}x{height}");
     texture.set_metadata("Dimensions", dims.as_bytes())?;
 
-    // Step 5: Demonstrate different compression modes
-    println!("\n5. Compressing with Basis Universal...");
+    // Step 4: Demonstrate different compression modes
+    println!("\n4. Compressing with Basis Universal...");
 
     // Create two versions with different compression settings
+    let mut texture_uastc = texture.try_clone()?;
     let mut texture_etc1s = texture;
-    let mut texture_uastc =
-        Ktx2Texture::create(width, height, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm)?;
-    texture_uastc.set_image_data(0, 0, 0, image_data)?;
     texture_uastc.set_metadata("CompressionMode", b"UASTC")?;
 
     // ETC1S compression (smaller files, faster)
@@ -102,8 +88,8 @@ fn main() -> Result<()> {
 
     texture_uastc.compress_basis(&uastc_params)?;
 
-    // Step 6: Save files and compare sizes
-    println!("\n6. Saving KTX2 files...");
+    // Step 5: Save files and compare sizes
+    println!("\n5. Saving KTX2 files...");
 
     let etc1s_data = texture_etc1s.write_to_memory()?;
     let uastc_data = texture_uastc.write_to_memory()?;
@@ -151,8 +137,8 @@ fn main() -> Result<()> {
     println!("  - {etc1s_path} (ETC1S)");
     println!("  - {uastc_path} (UASTC)");
 
-    // Step 7: Verify by loading back and transcoding
-    println!("\n7. Verifying conversion by loading back...");
+    // Step 6: Verify by loading back and transcoding
+    println!("\n6. Verifying conversion by loading back...");
     let loaded_texture = Ktx2Texture::from_memory(&etc1s_data)?;
 
     println!(
@@ -178,8 +164,8 @@ fn main() -> Result<()> {
         );
     }
 
-    // Step 8: Demonstrate transcoding to different GPU formats
-    println!("\n8. Demonstrating transcoding to GPU formats...");
+    // Step 7: Demonstrate transcoding to different GPU formats
+    println!("\n7. Demonstrating transcoding to GPU formats...");
 
     let formats = vec![
         (TranscodeFormat::Bc7Rgba, "BC7 (Desktop)"),
@@ -189,8 +175,7 @@ fn main() -> Result<()> {
     ];
 
     for (format, name) in formats {
-        let mut transcoded = Ktx2Texture::from_memory(&etc1s_data)?;
-        transcoded.transcode_basis(format)?;
+        let transcoded = loaded_texture.transcode_to(format)?;
         let transcoded_data = transcoded.get_image_data(0, 0, 0)?;
         println!("  - {}: {} bytes", name, transcoded_data.len());
     }
@@ -0,0 +1,56 @@
+//! Typed access to the `KTXanimData` metadata entry.
+//!
+//! The entry marks an array texture as an animation whose layers are the
+//! frames, and stores three little-endian `u32`s: duration, timescale and loop
+//! count.
+
+use crate::error::{Error, Result};
+
+/// Animation timing stored in the `KTXanimData` metadata entry
+///
+/// Each frame is shown for `duration / timescale` seconds.
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::AnimData;
+///
+/// let anim = AnimData { duration: 1, timescale: 30, loop_count: 0 };
+/// assert_eq!(AnimData::from_bytes(&anim.to_bytes()).unwrap(), anim);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnimData {
+    /// Frame duration in `timescale` units
+    pub duration: u32,
+    /// Time units per second
+    pub timescale: u32,
+    /// Number of times to play the animation; `0` loops forever
+    pub loop_count: u32,
+}
+
+impl AnimData {
+    /// Metadata key defined by the KTX2 specification
+    pub const KEY: &'static str = "KTXanimData";
+
+    /// Parses the 12-byte value of the `KTXanimData` entry
+    ///
+    /// Any other length returns [`Error::FileDataError`].
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        let value: &[u8; 12] = value.try_into().map_err(|_| Error::FileDataError)?;
+        let word = |i: usize| u32::from_le_bytes(value[i * 4..i * 4 + 4].try_into().unwrap());
+        Ok(Self {
+            duration: word(0),
+            timescale: word(1),
+            loop_count: word(2),
+        })
+    }
+
+    /// Encodes the value of the `KTXanimData` entry
+    pub fn to_bytes(&self) -> [u8; 12] {
+        let mut value = [0; 12];
+        value[..4].copy_from_slice(&self.duration.to_le_bytes());
+        value[4..8].copy_from_slice(&self.timescale.to_le_bytes());
+        value[8..].copy_from_slice(&self.loop_count.to_le_bytes());
+        value
+    }
+}
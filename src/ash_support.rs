@@ -0,0 +1,44 @@
+//! Conversions between [`VkFormat`] and `ash::vk::Format`.
+//!
+//! Both name values in the Vulkan `VkFormat` numeric space, so converting to
+//! ash is infallible while converting back fails for formats this crate does
+//! not model.
+
+use ash::vk;
+
+use crate::error::{Error, Result};
+use crate::texture::Ktx2Texture;
+use crate::vk_format::VkFormat;
+
+impl From<VkFormat> for vk::Format {
+    fn from(format: VkFormat) -> Self {
+        vk::Format::from_raw(format.as_raw() as i32)
+    }
+}
+
+impl TryFrom<vk::Format> for VkFormat {
+    type Error = Error;
+
+    /// Returns [`Error::InvalidValue`] for formats [`VkFormat`] has no variant for
+    fn try_from(format: vk::Format) -> Result<Self> {
+        u32::try_from(format.as_raw())
+            .ok()
+            .and_then(VkFormat::from_raw)
+            .ok_or(Error::InvalidValue)
+    }
+}
+
+impl Ktx2Texture {
+    /// Returns the `vkFormat` header field as an ash format
+    ///
+    /// Unlike [`vk_format`](Self::vk_format), formats [`VkFormat`] does not
+    /// model are passed through unchanged rather than reported as
+    /// `vk::Format::UNDEFINED`.
+    pub fn vk_format_ash(&self) -> vk::Format {
+        let texture = self.as_raw();
+        if texture.is_null() {
+            return vk::Format::UNDEFINED;
+        }
+        vk::Format::from_raw(unsafe { (*texture).vkFormat } as i32)
+    }
+}
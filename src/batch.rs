@@ -0,0 +1,75 @@
+//! Batch operations over many KTX2 files.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::error::Result;
+use crate::format::TranscodeFormat;
+use crate::texture::Ktx2Texture;
+
+/// Transcodes many serialized KTX2 textures to `format` in parallel
+///
+/// Each input is parsed, transcoded if it still holds Basis Universal data, and
+/// serialized again. Work is spread over one thread per available core, and the
+/// results are returned in input order.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ktx2_rw::{transcode_many, TranscodeFormat};
+///
+/// let inputs = vec![std::fs::read("a.ktx2").unwrap(), std::fs::read("b.ktx2").unwrap()];
+/// for result in transcode_many(inputs, TranscodeFormat::Bc7Rgba) {
+///     match result {
+///         Ok(bytes) => println!("{} bytes", bytes.len()),
+///         Err(e) => println!("failed: {e}"),
+///     }
+/// }
+/// ```
+pub fn transcode_many(inputs: Vec<Vec<u8>>, format: TranscodeFormat) -> Vec<Result<Vec<u8>>> {
+    let workers = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(inputs.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<Option<Result<Vec<u8>>>> = (0..inputs.len()).map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(input) = inputs.get(index) else {
+                            break;
+                        };
+                        done.push((index, transcode_one(input, format)));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let done = handle
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            for (index, result) in done {
+                results[index] = Some(result);
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every input is processed by a worker"))
+        .collect()
+}
+
+fn transcode_one(input: &[u8], format: TranscodeFormat) -> Result<Vec<u8>> {
+    let mut texture = Ktx2Texture::from_memory(input)?;
+    texture.ensure_transcoded(format)?;
+    texture.write_to_memory()
+}
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use crate::bindings::*;
+use crate::error::ParamsError;
 
 /// Configuration parameters for Basis Universal compression
 ///
@@ -43,8 +46,12 @@ use crate::bindings::*;
 /// params.quality_level = 255;
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct BasisCompressionParams {
     pub uastc: bool,
+    pub verbose: bool,
+    pub no_sse: bool,
     pub thread_count: u32,
     pub compression_level: u32,
     pub quality_level: u32,
@@ -84,6 +91,12 @@ pub struct BasisCompressionParamsBuilder {
 }
 
 impl BasisCompressionParams {
+    /// `input_swizzle` entry for a channel that is always 0
+    pub const SWIZZLE_ZERO: u8 = 4;
+
+    /// `input_swizzle` entry for a channel that is always 1
+    pub const SWIZZLE_ONE: u8 = 5;
+
     /// Creates a new builder for `BasisCompressionParams`
     ///
     /// # Examples
@@ -101,6 +114,120 @@ impl BasisCompressionParams {
             params: Self::default(),
         }
     }
+
+    /// Preset for fast iteration: ETC1S at the lowest compression level
+    ///
+    /// Encodes several times faster than the default at the cost of larger files
+    /// and slightly lower quality. Suited to development builds.
+    pub fn preset_fast() -> BasisCompressionParamsBuilder {
+        Self::builder()
+            .uastc(false)
+            .compression_level(0)
+            .quality_level(128)
+    }
+
+    /// Preset for maximum quality: UASTC at the slowest level, without RDO
+    ///
+    /// Produces the best transcoded quality and the largest files, and is the
+    /// slowest to encode. Suited to hero assets and final builds.
+    pub fn preset_high_quality() -> BasisCompressionParamsBuilder {
+        Self::builder()
+            .uastc(true)
+            .quality_level(4)
+            .uastc_flags(ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_VERYSLOW)
+            .uastc_rdo(false)
+    }
+
+    /// Preset for tangent-space normal maps
+    ///
+    /// Follows the Basis Universal recommendations: treats the data as linear,
+    /// moves X/Y into the RGB and alpha slices (which ETC1S encodes
+    /// independently), and disables the RDO passes that smear normals.
+    /// Expects X in red and Y in green.
+    pub fn preset_normal_map() -> BasisCompressionParamsBuilder {
+        Self::builder()
+            .uastc(false)
+            .quality_level(255)
+            .normal_map(true)
+            .separate_rg_to_color_alpha(true)
+            .no_endpoint_rdo(true)
+            .no_selector_rdo(true)
+    }
+
+    /// Preset for UI, text and other sharp-edged art
+    ///
+    /// UASTC keeps hard edges and flat colors that ETC1S turns blocky, at the
+    /// default level so encode times stay reasonable. Files are larger than
+    /// ETC1S; pair with Zstandard supercompression when size matters.
+    pub fn preset_ui() -> BasisCompressionParamsBuilder {
+        Self::builder()
+            .uastc(true)
+            .quality_level(2)
+            .uastc_flags(ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_DEFAULT)
+            .uastc_rdo(false)
+    }
+
+    /// Checks that every value is within the range accepted by the encoder
+    ///
+    /// - ETC1S quality level: 1-255; UASTC quality level: 0-4
+    /// - Compression level: 0-6
+    /// - Max endpoints/selectors: 0-16128
+    /// - Endpoint/selector RDO thresholds: 0.0-1e10
+    /// - UASTC RDO quality scalar: 0.001-50.0; dictionary size: 64-65536
+    ///
+    /// ETC1S-only parameters left at a non-default value in UASTC mode are
+    /// rejected with [`ParamsError::Etc1sOnly`] rather than silently ignored.
+    /// The default quality level (128) is ETC1S-only too, so UASTC params must
+    /// set it explicitly.
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        if self.uastc {
+            check_range("quality_level", self.quality_level as f64, 0.0, 4.0)?;
+
+            let etc1s_only = [
+                ("max_endpoints", self.max_endpoints != 0),
+                ("endpoint_rdo_threshold", self.endpoint_rdo_threshold != 0.0),
+                ("max_selectors", self.max_selectors != 0),
+                ("selector_rdo_threshold", self.selector_rdo_threshold != 0.0),
+                ("no_endpoint_rdo", self.no_endpoint_rdo),
+                ("no_selector_rdo", self.no_selector_rdo),
+            ];
+            if let Some((field, _)) = etc1s_only.iter().find(|(_, set)| *set) {
+                return Err(ParamsError::Etc1sOnly { field });
+            }
+        } else {
+            check_range("quality_level", self.quality_level as f64, 1.0, 255.0)?;
+        }
+
+        check_range("compression_level", self.compression_level as f64, 0.0, 6.0)?;
+        check_range("max_endpoints", self.max_endpoints as f64, 0.0, 16128.0)?;
+        check_range("max_selectors", self.max_selectors as f64, 0.0, 16128.0)?;
+        check_range(
+            "endpoint_rdo_threshold",
+            self.endpoint_rdo_threshold as f64,
+            0.0,
+            1e10,
+        )?;
+        check_range(
+            "selector_rdo_threshold",
+            self.selector_rdo_threshold as f64,
+            0.0,
+            1e10,
+        )?;
+        check_range(
+            "uastc_rdo_quality_scalar",
+            self.uastc_rdo_quality_scalar as f64,
+            0.001,
+            50.0,
+        )?;
+        check_range(
+            "uastc_rdo_dict_size",
+            self.uastc_rdo_dict_size as f64,
+            64.0,
+            65536.0,
+        )?;
+
+        Ok(())
+    }
 }
 
 impl BasisCompressionParamsBuilder {
@@ -115,8 +242,28 @@ impl BasisCompressionParamsBuilder {
         self
     }
 
+    /// Enables verbose encoder output on stdout
+    ///
+    /// Useful for diagnosing poor compression results
+    ///
+    /// Default: `false`
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.params.verbose = verbose;
+        self
+    }
+
+    /// Disables the encoder's SSE code paths
+    ///
+    /// Default: `false`
+    pub fn no_sse(mut self, no_sse: bool) -> Self {
+        self.params.no_sse = no_sse;
+        self
+    }
+
     /// Sets the number of threads to use for compression
     ///
+    /// `0` uses [`std::thread::available_parallelism`].
+    ///
     /// Default: `1`
     pub fn thread_count(mut self, count: u32) -> Self {
         self.params.thread_count = count;
@@ -264,6 +411,10 @@ impl BasisCompressionParamsBuilder {
 
     /// Sets the input channel swizzle
     ///
+    /// Each entry selects the source of one output channel: 0-3 for R, G, B and A,
+    /// [`SWIZZLE_ZERO`](BasisCompressionParams::SWIZZLE_ZERO) or
+    /// [`SWIZZLE_ONE`](BasisCompressionParams::SWIZZLE_ONE) for a constant.
+    ///
     /// [0, 1, 2, 3] = RGBA (no swizzle)
     /// [2, 1, 0, 3] = BGRA (red-blue swap)
     ///
@@ -273,16 +424,119 @@ impl BasisCompressionParamsBuilder {
         self
     }
 
+    /// Sets the input channel swizzle from a toktx-style string
+    ///
+    /// The string must be exactly 4 characters from `r`, `g`, `b`, `a`, `0` and
+    /// `1`, e.g. `"bgra"` or `"rrr1"`.
+    pub fn input_swizzle_str(mut self, swizzle: &str) -> Result<Self, ParamsError> {
+        let invalid = || ParamsError::InvalidSwizzle(swizzle.to_string());
+
+        let mut channels = [0u8; 4];
+        let mut chars = swizzle.chars();
+        for channel in channels.iter_mut() {
+            *channel = match chars.next().ok_or_else(invalid)? {
+                'r' => 0,
+                'g' => 1,
+                'b' => 2,
+                'a' => 3,
+                '0' => BasisCompressionParams::SWIZZLE_ZERO,
+                '1' => BasisCompressionParams::SWIZZLE_ONE,
+                _ => return Err(invalid()),
+            };
+        }
+        if chars.next().is_some() {
+            return Err(invalid());
+        }
+
+        self.params.input_swizzle = channels;
+        Ok(self)
+    }
+
     /// Builds the final `BasisCompressionParams`
+    ///
+    /// Values are passed to the encoder unchecked; use [`try_build`](Self::try_build)
+    /// to validate them first.
     pub fn build(self) -> BasisCompressionParams {
         self.params
     }
+
+    /// Builds the final `BasisCompressionParams`, validating every value
+    ///
+    /// See [`BasisCompressionParams::validate`] for the rules applied.
+    pub fn try_build(self) -> Result<BasisCompressionParams, ParamsError> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+fn check_range(field: &'static str, value: f64, min: f64, max: f64) -> Result<(), ParamsError> {
+    // Written so that NaN is rejected as well
+    if value >= min && value <= max {
+        Ok(())
+    } else {
+        Err(ParamsError::OutOfRange {
+            field,
+            value,
+            min,
+            max,
+        })
+    }
+}
+
+/// Stage of a Basis Universal compression reported to a progress callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionStage {
+    /// The encoder is about to start
+    Started,
+    /// The encoder finished and the texture now holds Basis Universal data
+    Finished,
+}
+
+/// Progress report passed to [`Ktx2Texture::compress_basis_with_progress`]
+///
+/// [`Ktx2Texture::compress_basis_with_progress`]: crate::Ktx2Texture::compress_basis_with_progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressionProgress {
+    /// Current stage of the compression
+    pub stage: CompressionStage,
+    /// Number of images (levels × layers × faces) being encoded
+    pub image_count: u32,
+    /// Completed fraction in the range `0.0..=1.0`
+    pub fraction: f32,
+}
+
+/// Basis Universal encoding mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMode {
+    /// ETC1S, the smaller and lower quality mode
+    Etc1s,
+    /// UASTC, the larger and higher quality mode
+    Uastc,
+}
+
+/// Summary returned by [`Ktx2Texture::compress_basis_reported`]
+///
+/// [`Ktx2Texture::compress_basis_reported`]: crate::Ktx2Texture::compress_basis_reported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressionReport {
+    /// Image data size in bytes before compression
+    pub original_size: usize,
+    /// Image data size in bytes after compression
+    pub compressed_size: usize,
+    /// `original_size / compressed_size`
+    pub ratio: f64,
+    /// Encoding mode that ran
+    pub mode: CompressionMode,
+    /// Wall-clock time spent in the encoder
+    pub duration: Duration,
 }
 
 impl Default for BasisCompressionParams {
     fn default() -> Self {
         Self {
             uastc: false,
+            verbose: false,
+            no_sse: false,
             thread_count: 1,
             compression_level: unsafe { KTX_ETC1S_DEFAULT_COMPRESSION_LEVEL },
             quality_level: 128,
@@ -304,14 +558,22 @@ impl Default for BasisCompressionParams {
     }
 }
 
+/// Replaces the `0` sentinel with the number of available cores
+fn resolve_thread_count(count: u32) -> u32 {
+    match count {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get() as u32),
+        n => n,
+    }
+}
+
 impl From<&BasisCompressionParams> for ktxBasisParams {
     fn from(params: &BasisCompressionParams) -> Self {
         let mut ktx_params = ktxBasisParams {
             structSize: std::mem::size_of::<ktxBasisParams>() as u32,
             uastc: params.uastc,
-            verbose: false,
-            noSSE: false,
-            threadCount: params.thread_count,
+            verbose: params.verbose,
+            noSSE: params.no_sse,
+            threadCount: resolve_thread_count(params.thread_count),
             compressionLevel: params.compression_level,
             qualityLevel: params.quality_level,
             maxEndpoints: params.max_endpoints,
@@ -334,10 +596,319 @@ impl From<&BasisCompressionParams> for ktxBasisParams {
             uastcRDONoMultithreading: false,
         };
 
-        for i in 0..4 {
-            ktx_params.inputSwizzle[i] = params.input_swizzle[i] as std::os::raw::c_char;
-        }
+        ktx_params.inputSwizzle = encode_swizzle(params.input_swizzle);
 
         ktx_params
     }
 }
+
+/// Converts an `input_swizzle` into the character form libktx expects
+///
+/// libktx treats a leading NUL as "no swizzle", so the identity swizzle is sent
+/// as all zeros.
+fn encode_swizzle(swizzle: [u8; 4]) -> [std::os::raw::c_char; 4] {
+    if swizzle == [0, 1, 2, 3] {
+        return [0; 4];
+    }
+
+    swizzle.map(|channel| {
+        let channel = match channel {
+            0 => b'r',
+            1 => b'g',
+            2 => b'b',
+            3 => b'a',
+            BasisCompressionParams::SWIZZLE_ZERO => b'0',
+            BasisCompressionParams::SWIZZLE_ONE => b'1',
+            // Passed through so libktx rejects it
+            other => other,
+        };
+        channel as std::os::raw::c_char
+    })
+}
+
+/// ASTC block footprint used by [`AstcParams`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AstcBlockDimension {
+    D4x4,
+    D5x4,
+    D5x5,
+    D6x5,
+    D6x6,
+    D8x5,
+    D8x6,
+    D10x5,
+    D10x6,
+    D8x8,
+    D10x8,
+    D10x10,
+    D12x10,
+    D12x12,
+}
+
+impl AstcBlockDimension {
+    /// Returns the block width and height in texels
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            AstcBlockDimension::D4x4 => (4, 4),
+            AstcBlockDimension::D5x4 => (5, 4),
+            AstcBlockDimension::D5x5 => (5, 5),
+            AstcBlockDimension::D6x5 => (6, 5),
+            AstcBlockDimension::D6x6 => (6, 6),
+            AstcBlockDimension::D8x5 => (8, 5),
+            AstcBlockDimension::D8x6 => (8, 6),
+            AstcBlockDimension::D10x5 => (10, 5),
+            AstcBlockDimension::D10x6 => (10, 6),
+            AstcBlockDimension::D8x8 => (8, 8),
+            AstcBlockDimension::D10x8 => (10, 8),
+            AstcBlockDimension::D10x10 => (10, 10),
+            AstcBlockDimension::D12x10 => (12, 10),
+            AstcBlockDimension::D12x12 => (12, 12),
+        }
+    }
+}
+
+impl From<AstcBlockDimension> for ktx_pack_astc_block_dimension_e {
+    fn from(dimension: AstcBlockDimension) -> Self {
+        match dimension {
+            AstcBlockDimension::D4x4 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4
+            }
+            AstcBlockDimension::D5x4 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4
+            }
+            AstcBlockDimension::D5x5 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5
+            }
+            AstcBlockDimension::D6x5 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5
+            }
+            AstcBlockDimension::D6x6 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6
+            }
+            AstcBlockDimension::D8x5 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x5
+            }
+            AstcBlockDimension::D8x6 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x6
+            }
+            AstcBlockDimension::D10x5 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x5
+            }
+            AstcBlockDimension::D10x6 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x6
+            }
+            AstcBlockDimension::D8x8 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x8
+            }
+            AstcBlockDimension::D10x8 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x8
+            }
+            AstcBlockDimension::D10x10 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x10
+            }
+            AstcBlockDimension::D12x10 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x10
+            }
+            AstcBlockDimension::D12x12 => {
+                ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x12
+            }
+        }
+    }
+}
+
+/// ASTC encoder profile used by [`AstcParams`]
+///
+/// Whether the output is sRGB or linear is not chosen here: it follows the
+/// transfer function of the texture being compressed, so an `R8G8B8A8Srgb`
+/// texture becomes an ASTC sRGB block format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AstcMode {
+    /// Low dynamic range (8-bit) input
+    Ldr,
+    /// High dynamic range (floating point) input
+    Hdr,
+}
+
+impl From<AstcMode> for ktx_pack_astc_encoder_mode_e {
+    fn from(mode: AstcMode) -> Self {
+        match mode {
+            AstcMode::Ldr => ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_LDR,
+            AstcMode::Hdr => ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_HDR,
+        }
+    }
+}
+
+/// Configuration parameters for native ASTC compression
+///
+/// Used with [`Ktx2Texture::compress_astc`] to encode straight to an ASTC block
+/// format, skipping Basis Universal transcoding on ASTC-only targets.
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{AstcBlockDimension, AstcParams};
+///
+/// let params = AstcParams::builder()
+///     .block_dimension(AstcBlockDimension::D6x6)
+///     .quality_level(AstcParams::QUALITY_THOROUGH)
+///     .thread_count(8)
+///     .build();
+/// ```
+///
+/// [`Ktx2Texture::compress_astc`]: crate::Ktx2Texture::compress_astc
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AstcParams {
+    pub verbose: bool,
+    pub thread_count: u32,
+    pub block_dimension: AstcBlockDimension,
+    pub mode: AstcMode,
+    pub quality_level: u32,
+    pub normal_map: bool,
+    pub perceptual: bool,
+    pub input_swizzle: [u8; 4],
+}
+
+/// Builder for [`AstcParams`]
+pub struct AstcParamsBuilder {
+    params: AstcParams,
+}
+
+impl AstcParams {
+    /// Fastest encoder preset
+    pub const QUALITY_FASTEST: u32 =
+        ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FASTEST;
+    /// Fast encoder preset
+    pub const QUALITY_FAST: u32 = ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FAST;
+    /// Medium encoder preset, recommended by libktx
+    pub const QUALITY_MEDIUM: u32 =
+        ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_MEDIUM;
+    /// Thorough encoder preset
+    pub const QUALITY_THOROUGH: u32 =
+        ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_THOROUGH;
+    /// Exhaustive encoder preset
+    pub const QUALITY_EXHAUSTIVE: u32 =
+        ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_EXHAUSTIVE;
+
+    /// Creates a new builder for `AstcParams`
+    pub fn builder() -> AstcParamsBuilder {
+        AstcParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+impl AstcParamsBuilder {
+    /// Enables verbose encoder output on stdout
+    ///
+    /// Default: `false`
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.params.verbose = verbose;
+        self
+    }
+
+    /// Sets the number of threads to use for compression
+    ///
+    /// `0` uses [`std::thread::available_parallelism`].
+    ///
+    /// Default: `1`
+    pub fn thread_count(mut self, count: u32) -> Self {
+        self.params.thread_count = count;
+        self
+    }
+
+    /// Sets the block footprint
+    ///
+    /// Larger blocks give lower bit rates and lower quality
+    ///
+    /// Default: `D4x4`
+    pub fn block_dimension(mut self, dimension: AstcBlockDimension) -> Self {
+        self.params.block_dimension = dimension;
+        self
+    }
+
+    /// Sets the encoder profile
+    ///
+    /// Default: `Ldr`
+    pub fn mode(mut self, mode: AstcMode) -> Self {
+        self.params.mode = mode;
+        self
+    }
+
+    /// Sets the quality level
+    ///
+    /// 0-100; see the `AstcParams::QUALITY_*` presets
+    ///
+    /// Default: `QUALITY_MEDIUM`
+    pub fn quality_level(mut self, quality: u32) -> Self {
+        self.params.quality_level = quality;
+        self
+    }
+
+    /// Configures for normal map compression
+    ///
+    /// Only X and Y are kept; shaders must reconstruct Z
+    ///
+    /// Default: `false`
+    pub fn normal_map(mut self, is_normal_map: bool) -> Self {
+        self.params.normal_map = is_normal_map;
+        self
+    }
+
+    /// Optimizes for perceptual rather than RMS error
+    ///
+    /// Default: `false`
+    pub fn perceptual(mut self, perceptual: bool) -> Self {
+        self.params.perceptual = perceptual;
+        self
+    }
+
+    /// Sets the input channel swizzle
+    ///
+    /// Uses the same encoding as [`BasisCompressionParamsBuilder::input_swizzle`]
+    ///
+    /// Default: `[0, 1, 2, 3]`
+    pub fn input_swizzle(mut self, swizzle: [u8; 4]) -> Self {
+        self.params.input_swizzle = swizzle;
+        self
+    }
+
+    /// Builds the final `AstcParams`
+    pub fn build(self) -> AstcParams {
+        self.params
+    }
+}
+
+impl Default for AstcParams {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            thread_count: 1,
+            block_dimension: AstcBlockDimension::D4x4,
+            mode: AstcMode::Ldr,
+            quality_level: Self::QUALITY_MEDIUM,
+            normal_map: false,
+            perceptual: false,
+            input_swizzle: [0, 1, 2, 3],
+        }
+    }
+}
+
+impl From<&AstcParams> for ktxAstcParams {
+    fn from(params: &AstcParams) -> Self {
+        ktxAstcParams {
+            structSize: std::mem::size_of::<ktxAstcParams>() as u32,
+            verbose: params.verbose,
+            threadCount: resolve_thread_count(params.thread_count),
+            blockDimension: params.block_dimension.into(),
+            mode: params.mode.into(),
+            qualityLevel: params.quality_level,
+            normalMap: params.normal_map,
+            perceptual: params.perceptual,
+            inputSwizzle: encode_swizzle(params.input_swizzle),
+        }
+    }
+}
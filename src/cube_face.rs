@@ -0,0 +1,43 @@
+//! Named cubemap faces.
+//!
+//! KTX2 stores the faces of a cubemap in the Vulkan order +X, -X, +Y, -Y, +Z,
+//! -Z, so face index 0 is +X and index 5 is -Z.
+
+/// One face of a cubemap, in KTX2 storage order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CubeFace {
+    /// Positive X (index 0)
+    PosX = 0,
+    /// Negative X (index 1)
+    NegX = 1,
+    /// Positive Y (index 2)
+    PosY = 2,
+    /// Negative Y (index 3)
+    NegY = 3,
+    /// Positive Z (index 4)
+    PosZ = 4,
+    /// Negative Z (index 5)
+    NegZ = 5,
+}
+
+impl CubeFace {
+    /// Every face in storage order
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PosX,
+        CubeFace::NegX,
+        CubeFace::PosY,
+        CubeFace::NegY,
+        CubeFace::PosZ,
+        CubeFace::NegZ,
+    ];
+
+    /// Returns the face index used by the index-based image data methods
+    pub fn index(&self) -> u32 {
+        *self as u32
+    }
+
+    /// Returns the face stored at `index`, if it is in `0..6`
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+}
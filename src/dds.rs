@@ -0,0 +1,219 @@
+//! Import of DirectDraw Surface (DDS) files.
+//!
+//! Covers the layouts content tools commonly produce: 2D textures, cubemaps
+//! and arrays with optional mip chains, described either by a DX10 extended
+//! header or by a legacy pixel format (DXT1, DXT5, ATI1/BC4, ATI2/BC5 and
+//! 32-bit RGBA/BGRA).
+
+use crate::error::{Error, Result};
+use crate::texture::Ktx2Texture;
+use crate::vk_format::VkFormat;
+
+const MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: usize = 124;
+const PIXEL_FORMAT_SIZE: u32 = 32;
+const DX10_HEADER_SIZE: usize = 20;
+
+/// `DDS_HEADER::dwFlags`: `dwMipMapCount` is valid
+const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+/// `DDS_PIXELFORMAT::dwFlags`: the alpha mask is valid
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+/// `DDS_PIXELFORMAT::dwFlags`: `dwFourCC` is valid
+const DDPF_FOURCC: u32 = 0x4;
+/// `DDS_PIXELFORMAT::dwFlags`: the data is uncompressed RGB
+const DDPF_RGB: u32 = 0x40;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x20_0000;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const D3D10_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Maps a `DXGI_FORMAT` value to the matching Vulkan format
+fn vk_format_from_dxgi(format: u32) -> Option<VkFormat> {
+    let format = match format {
+        2 => VkFormat::R32G32B32A32Sfloat,
+        10 => VkFormat::R16G16B16A16Sfloat,
+        16 => VkFormat::R32G32Sfloat,
+        28 => VkFormat::R8G8B8A8Unorm,
+        29 => VkFormat::R8G8B8A8Srgb,
+        34 => VkFormat::R16G16Sfloat,
+        41 => VkFormat::R32Sfloat,
+        49 => VkFormat::R8G8Unorm,
+        54 => VkFormat::R16Sfloat,
+        61 => VkFormat::R8Unorm,
+        71 => VkFormat::Bc1RgbaUnormBlock,
+        72 => VkFormat::Bc1RgbaSrgbBlock,
+        77 => VkFormat::Bc3UnormBlock,
+        78 => VkFormat::Bc3SrgbBlock,
+        80 => VkFormat::Bc4UnormBlock,
+        81 => VkFormat::Bc4SnormBlock,
+        83 => VkFormat::Bc5UnormBlock,
+        84 => VkFormat::Bc5SnormBlock,
+        87 => VkFormat::B8G8R8A8Unorm,
+        91 => VkFormat::B8G8R8A8Srgb,
+        95 => VkFormat::Bc6hUfloatBlock,
+        96 => VkFormat::Bc6hSfloatBlock,
+        98 => VkFormat::Bc7UnormBlock,
+        99 => VkFormat::Bc7SrgbBlock,
+        _ => return None,
+    };
+    Some(format)
+}
+
+/// Maps the legacy `DDS_PIXELFORMAT` stored at offset 72 of the header
+fn vk_format_from_legacy(header: &[u8]) -> Option<VkFormat> {
+    let flags = read_u32(header, 76);
+
+    if flags & DDPF_FOURCC != 0 {
+        return match &read_u32(header, 80).to_le_bytes() {
+            b"DXT1" => Some(VkFormat::Bc1RgbaUnormBlock),
+            b"DXT5" => Some(VkFormat::Bc3UnormBlock),
+            b"ATI1" | b"BC4U" => Some(VkFormat::Bc4UnormBlock),
+            b"BC4S" => Some(VkFormat::Bc4SnormBlock),
+            b"ATI2" | b"BC5U" => Some(VkFormat::Bc5UnormBlock),
+            b"BC5S" => Some(VkFormat::Bc5SnormBlock),
+            _ => None,
+        };
+    }
+
+    // Only 32-bit layouts with a real alpha channel map onto a VkFormat
+    if flags & DDPF_RGB == 0 || flags & DDPF_ALPHAPIXELS == 0 || read_u32(header, 84) != 32 {
+        return None;
+    }
+    let masks = [88, 92, 96, 100].map(|offset| read_u32(header, offset));
+    match masks {
+        [0xFF, 0xFF00, 0xFF_0000, 0xFF00_0000] => Some(VkFormat::R8G8B8A8Unorm),
+        [0xFF_0000, 0xFF00, 0xFF, 0xFF00_0000] => Some(VkFormat::B8G8R8A8Unorm),
+        _ => None,
+    }
+}
+
+impl Ktx2Texture {
+    /// Creates a texture from the contents of a DDS file
+    ///
+    /// 2D textures, cubemaps and arrays (including cubemap arrays) with any
+    /// number of mip levels are supported. Formats come from the DX10 header's
+    /// `DXGI_FORMAT` (BC1 to BC7, 8-bit RGBA/BGRA and 16/32-bit float) or from
+    /// a legacy pixel format: DXT1, DXT5, ATI1/BC4, ATI2/BC5 and 32-bit RGBA or
+    /// BGRA with alpha.
+    ///
+    /// Returns [`Error::UnknownFileFormat`] if `data` is not a DDS file,
+    /// [`Error::FileUnexpectedEof`] if it is shorter than its headers declare
+    /// (checked before any image storage is allocated) and
+    /// [`Error::FileDataError`] for inconsistent headers.
+    /// [`Error::UnsupportedFeature`] is returned for:
+    ///
+    /// - volume textures (`DDSCAPS2_VOLUME`)
+    /// - DX10 resources other than `TEXTURE2D`, such as 1D textures
+    /// - `DXGI_FORMAT` values without a mapping above
+    /// - legacy cubemaps that do not store all six faces
+    /// - legacy pixel formats without a mapping, such as DXT3 or packed YUV
+    pub fn from_dds_memory(data: &[u8]) -> Result<Self> {
+        if data.get(..4) != Some(MAGIC.as_slice()) {
+            return Err(Error::UnknownFileFormat);
+        }
+        let header = data
+            .get(4..4 + HEADER_SIZE)
+            .ok_or(Error::FileUnexpectedEof)?;
+        if read_u32(header, 0) as usize != HEADER_SIZE || read_u32(header, 72) != PIXEL_FORMAT_SIZE
+        {
+            return Err(Error::FileDataError);
+        }
+
+        let flags = read_u32(header, 4);
+        let (height, width) = (read_u32(header, 8), read_u32(header, 12));
+        let levels = if flags & DDSD_MIPMAPCOUNT != 0 {
+            read_u32(header, 24).max(1)
+        } else {
+            1
+        };
+        let caps2 = read_u32(header, 108);
+        if caps2 & DDSCAPS2_VOLUME != 0 {
+            return Err(Error::UnsupportedFeature);
+        }
+
+        let mut offset = 4 + HEADER_SIZE;
+        let is_dx10 = read_u32(header, 76) & DDPF_FOURCC != 0 && &header[80..84] == b"DX10";
+        let (vk_format, layers, faces) = if is_dx10 {
+            let dx10 = data
+                .get(offset..offset + DX10_HEADER_SIZE)
+                .ok_or(Error::FileUnexpectedEof)?;
+            offset += DX10_HEADER_SIZE;
+
+            if read_u32(dx10, 4) != D3D10_RESOURCE_DIMENSION_TEXTURE2D {
+                return Err(Error::UnsupportedFeature);
+            }
+            let vk_format =
+                vk_format_from_dxgi(read_u32(dx10, 0)).ok_or(Error::UnsupportedFeature)?;
+            let faces = if read_u32(dx10, 8) & D3D10_RESOURCE_MISC_TEXTURECUBE != 0 {
+                6
+            } else {
+                1
+            };
+            (vk_format, read_u32(dx10, 12).max(1), faces)
+        } else {
+            let faces = match caps2 & (DDSCAPS2_CUBEMAP | DDSCAPS2_CUBEMAP_ALLFACES) {
+                0 => 1,
+                bits if bits == DDSCAPS2_CUBEMAP | DDSCAPS2_CUBEMAP_ALLFACES => 6,
+                _ => return Err(Error::UnsupportedFeature),
+            };
+            let vk_format = vk_format_from_legacy(header).ok_or(Error::UnsupportedFeature)?;
+            (vk_format, 1, faces)
+        };
+
+        if width == 0 || height == 0 || (faces == 6 && width != height) {
+            return Err(Error::FileDataError);
+        }
+        if levels > Self::full_mip_levels(width, height, 1) {
+            return Err(Error::FileDataError);
+        }
+
+        // Images are stored per array element (each cubemap face counting as
+        // one), each followed by its mip chain. DDS packs rows to the block
+        // pitch, which is also the KTX2 layout, so every image copies as is.
+        let (block_width, block_height) = vk_format.block_size();
+        let bytes_per_block = vk_format.bytes_per_block() as u64;
+        let level_size = |level: u32| {
+            let (level_width, level_height) = ((width >> level).max(1), (height >> level).max(1));
+            level_width.div_ceil(block_width) as u64
+                * level_height.div_ceil(block_height) as u64
+                * bytes_per_block
+        };
+
+        // The header is untrusted, so check the payload is there before
+        // allocating storage for it
+        let chain_size: u64 = (0..levels).map(level_size).sum();
+        let payload = (layers as u64 * faces as u64)
+            .checked_mul(chain_size)
+            .ok_or(Error::FileUnexpectedEof)?;
+        if ((data.len() - offset) as u64) < payload {
+            return Err(Error::FileUnexpectedEof);
+        }
+
+        let mut texture = Self::builder()
+            .dimensions(width, height)
+            .num_dimensions(2)
+            .layers(layers)
+            .faces(faces)
+            .levels(levels)
+            .format(vk_format)
+            .build()?;
+
+        for image in 0..layers * faces {
+            for level in 0..levels {
+                let size = level_size(level) as usize;
+                let pixels = data
+                    .get(offset..offset + size)
+                    .ok_or(Error::FileUnexpectedEof)?;
+                texture.set_image_data(level, image / faces, image % faces, pixels)?;
+                offset += size;
+            }
+        }
+
+        Ok(texture)
+    }
+}
@@ -0,0 +1,207 @@
+//! The Khronos data format descriptor (DFD) stored in every KTX2 file.
+//!
+//! A DFD describes the layout and meaning of each texel independently of the
+//! `VkFormat`. Only the basic descriptor block is modelled; see the Khronos Data
+//! Format Specification for the meaning of the raw model, primaries and
+//! transfer values.
+
+use crate::error::{Error, Result};
+
+/// Words before the first sample: the total size, the block header and the
+/// fixed part of the basic block
+const HEADER_WORDS: usize = 7;
+/// Words per sample
+const SAMPLE_WORDS: usize = 4;
+/// Size in bytes of the basic block without samples
+const BASIC_BLOCK_SIZE: usize = (HEADER_WORDS - 1) * 4;
+
+/// One sample of a basic data format descriptor block
+///
+/// A sample describes a contiguous run of bits belonging to a single channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DfdSample {
+    /// Offset of the first bit within the texel block
+    pub bit_offset: u16,
+    /// Number of bits, from 1 to 256
+    pub bit_length: u16,
+    /// Channel identifier for the color model, from 0 to 15
+    pub channel_id: u8,
+    /// The channel is stored linearly regardless of the transfer function
+    pub linear: bool,
+    /// `lower` and `upper` hold an exponent rather than a value range
+    pub exponent: bool,
+    /// The sample holds signed values
+    pub signed: bool,
+    /// The sample holds floating-point values
+    pub float: bool,
+    /// Position of the sample within the texel block, in 1/256ths of a texel
+    /// per dimension
+    pub sample_positions: [u8; 4],
+    /// Value that maps to the bottom of the channel's range
+    pub lower: u32,
+    /// Value that maps to the top of the channel's range
+    pub upper: u32,
+}
+
+/// Basic data format descriptor block
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{Ktx2Texture, VkFormat};
+/// # fn main() -> ktx2_rw::Result<()> {
+///
+/// let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8Unorm)?;
+/// let dfd = texture.dfd()?;
+/// assert_eq!(dfd.samples.len(), 2);
+/// assert_eq!(dfd.bytes_planes[0], 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DataFormatDescriptor {
+    /// Version of the Data Format Specification; KTX2 uses `2` (version 1.3)
+    pub version: u16,
+    /// Color model, such as `1` for RGBSDA
+    pub color_model: u8,
+    /// Color primaries, such as `1` for BT.709
+    pub color_primaries: u8,
+    /// Transfer function, such as `1` for linear and `2` for sRGB
+    pub transfer_function: u8,
+    /// Flags; bit 0 marks premultiplied alpha
+    pub flags: u8,
+    /// Width, height, depth and fourth dimension of a texel block, from 1 to 256
+    pub texel_block_dimensions: [u16; 4],
+    /// Bytes per texel block in each plane
+    pub bytes_planes: [u8; 8],
+    pub samples: Vec<DfdSample>,
+}
+
+impl DataFormatDescriptor {
+    /// Parses the words libktx stores in `ktxTexture2::pDfd`
+    ///
+    /// The first word is the total size in bytes. Descriptor blocks after the
+    /// basic block are ignored. Malformed input returns [`Error::InvalidValue`]
+    /// and a first block other than the Khronos basic block returns
+    /// [`Error::UnsupportedFeature`].
+    pub fn from_words(words: &[u32]) -> Result<Self> {
+        if words.len() < HEADER_WORDS {
+            return Err(Error::InvalidValue);
+        }
+        let total_size = words[0] as usize;
+        if !total_size.is_multiple_of(4) || total_size / 4 > words.len() {
+            return Err(Error::InvalidValue);
+        }
+
+        let (vendor_id, descriptor_type) = (words[1] & 0x1_FFFF, words[1] >> 17);
+        if vendor_id != 0 || descriptor_type != 0 {
+            return Err(Error::UnsupportedFeature);
+        }
+
+        let block_size = (words[2] >> 16) as usize;
+        if block_size < BASIC_BLOCK_SIZE
+            || !(block_size - BASIC_BLOCK_SIZE).is_multiple_of(SAMPLE_WORDS * 4)
+            || block_size + 4 > total_size
+        {
+            return Err(Error::InvalidValue);
+        }
+
+        let [color_model, color_primaries, transfer_function, flags] = words[3].to_le_bytes();
+        let texel_block_dimensions = words[4].to_le_bytes().map(|d| u16::from(d) + 1);
+        let mut bytes_planes = [0; 8];
+        bytes_planes[..4].copy_from_slice(&words[5].to_le_bytes());
+        bytes_planes[4..].copy_from_slice(&words[6].to_le_bytes());
+
+        let sample_count = (block_size - BASIC_BLOCK_SIZE) / (SAMPLE_WORDS * 4);
+        let samples = words[HEADER_WORDS..HEADER_WORDS + sample_count * SAMPLE_WORDS]
+            .chunks_exact(SAMPLE_WORDS)
+            .map(|sample| {
+                let [_, _, bit_length, channel_type] = sample[0].to_le_bytes();
+                DfdSample {
+                    bit_offset: sample[0] as u16,
+                    bit_length: u16::from(bit_length) + 1,
+                    channel_id: channel_type & 0x0F,
+                    linear: channel_type & 0x10 != 0,
+                    exponent: channel_type & 0x20 != 0,
+                    signed: channel_type & 0x40 != 0,
+                    float: channel_type & 0x80 != 0,
+                    sample_positions: sample[1].to_le_bytes(),
+                    lower: sample[2],
+                    upper: sample[3],
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            version: words[2] as u16,
+            color_model,
+            color_primaries,
+            transfer_function,
+            flags,
+            texel_block_dimensions,
+            bytes_planes,
+            samples,
+        })
+    }
+
+    /// Serializes the descriptor into the word layout of `ktxTexture2::pDfd`
+    ///
+    /// Returns [`Error::InvalidValue`] if a block dimension, bit length or
+    /// channel identifier is out of range.
+    pub fn to_words(&self) -> Result<Vec<u32>> {
+        let block_size = BASIC_BLOCK_SIZE + self.samples.len() * SAMPLE_WORDS * 4;
+        if block_size > usize::from(u16::MAX) {
+            return Err(Error::InvalidValue);
+        }
+
+        // Block dimensions and bit lengths are stored minus one
+        let minus_one = |value: u16| match value {
+            1..=256 => Ok((value - 1) as u8),
+            _ => Err(Error::InvalidValue),
+        };
+
+        let mut dimensions = [0; 4];
+        for (stored, &value) in dimensions.iter_mut().zip(&self.texel_block_dimensions) {
+            *stored = minus_one(value)?;
+        }
+
+        let mut words = Vec::with_capacity(1 + block_size / 4);
+        words.push((block_size + 4) as u32);
+        words.push(0); // Khronos vendor, basic descriptor type
+        words.push(u32::from(self.version) | (block_size as u32) << 16);
+        words.push(u32::from_le_bytes([
+            self.color_model,
+            self.color_primaries,
+            self.transfer_function,
+            self.flags,
+        ]));
+        words.push(u32::from_le_bytes(dimensions));
+        words.push(u32::from_le_bytes(
+            self.bytes_planes[..4].try_into().unwrap(),
+        ));
+        words.push(u32::from_le_bytes(
+            self.bytes_planes[4..].try_into().unwrap(),
+        ));
+
+        for sample in &self.samples {
+            if sample.channel_id > 0x0F {
+                return Err(Error::InvalidValue);
+            }
+            let channel_type = sample.channel_id
+                | u8::from(sample.linear) << 4
+                | u8::from(sample.exponent) << 5
+                | u8::from(sample.signed) << 6
+                | u8::from(sample.float) << 7;
+            words.push(
+                u32::from(sample.bit_offset)
+                    | u32::from(minus_one(sample.bit_length)?) << 16
+                    | u32::from(channel_type) << 24,
+            );
+            words.push(u32::from_le_bytes(sample.sample_positions));
+            words.push(sample.lower);
+            words.push(sample.upper);
+        }
+
+        Ok(words)
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt;
+use std::io;
 
 use crate::bindings::*;
+use crate::format::TranscodeFormat;
+use crate::vk_format::VkFormat;
 
 /// Specialized Result type for KTX2 operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -28,6 +31,22 @@ pub enum Error {
     LibraryNotLinked,
     DecompressLengthError,
     DecompressChecksumError,
+    UnsupportedFormat(VkFormat),
+    UnsupportedTranscodeTarget(TranscodeFormat),
+    /// Image data passed to the library does not match the size of the image
+    ImageSizeMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// A metadata key is empty or contains a NUL byte
+    InvalidMetadataKey(String),
+    /// An image file could not be decoded; holds the decoder's message
+    ImageDecode(String),
+    /// A Rust reader or writer passed to the library failed
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
     Other(u32),
 }
 
@@ -69,6 +88,22 @@ impl fmt::Display for Error {
                 write!(f, "Decompressed byte count does not match expected size")
             }
             Error::DecompressChecksumError => write!(f, "Checksum mismatch when decompressing"),
+            Error::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "Texture format {format:?} is not supported by this operation"
+                )
+            }
+            Error::UnsupportedTranscodeTarget(format) => {
+                write!(f, "This texture cannot be transcoded to {format:?}")
+            }
+            Error::ImageSizeMismatch { expected, actual } => write!(
+                f,
+                "Image data is {actual} bytes but the image needs {expected} bytes"
+            ),
+            Error::InvalidMetadataKey(key) => write!(f, "Invalid metadata key {key:?}"),
+            Error::ImageDecode(message) => write!(f, "Failed to decode image: {message}"),
+            Error::Io { message, .. } => write!(f, "I/O error: {message}"),
             Error::Other(code) => write!(f, "Unknown error code: {code}"),
         }
     }
@@ -76,13 +111,140 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Converts a libktx result code, returning `None` for `KTX_SUCCESS`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ktx2_rw::Error;
+    ///
+    /// assert_eq!(Error::try_from_code(0), None);
+    /// assert_eq!(Error::try_from_code(13), Some(Error::OutOfMemory));
+    /// ```
+    pub fn try_from_code(code: u32) -> Option<Self> {
+        if code == ktx_error_code_e_KTX_SUCCESS {
+            None
+        } else {
+            Some(code.into())
+        }
+    }
+
+    /// Returns the `ktx_error_code_e` value this error corresponds to
+    ///
+    /// The inverse of the conversion from libktx codes, so
+    /// `Error::from(code).code() == code` for every error code. Errors raised
+    /// by this crate rather than libktx report the closest libktx code:
+    /// `KTX_INVALID_VALUE` for [`Error::ImageSizeMismatch`] and
+    /// [`Error::InvalidMetadataKey`], `KTX_UNSUPPORTED_FEATURE` for
+    /// [`Error::UnsupportedFormat`] and [`Error::UnsupportedTranscodeTarget`],
+    /// `KTX_FILE_DATA_ERROR` for [`Error::ImageDecode`], and
+    /// `KTX_FILE_UNEXPECTED_EOF` or `KTX_FILE_READ_ERROR` for [`Error::Io`]
+    /// depending on its kind.
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::FileDataError => ktx_error_code_e_KTX_FILE_DATA_ERROR,
+            Error::FilePipe => ktx_error_code_e_KTX_FILE_ISPIPE,
+            Error::FileOpenFailed => ktx_error_code_e_KTX_FILE_OPEN_FAILED,
+            Error::FileOverflow => ktx_error_code_e_KTX_FILE_OVERFLOW,
+            Error::FileReadError => ktx_error_code_e_KTX_FILE_READ_ERROR,
+            Error::FileSeekError => ktx_error_code_e_KTX_FILE_SEEK_ERROR,
+            Error::FileUnexpectedEof => ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF,
+            Error::FileWriteError => ktx_error_code_e_KTX_FILE_WRITE_ERROR,
+            Error::GlError => ktx_error_code_e_KTX_GL_ERROR,
+            Error::InvalidOperation => ktx_error_code_e_KTX_INVALID_OPERATION,
+            Error::InvalidValue => ktx_error_code_e_KTX_INVALID_VALUE,
+            Error::NotFound => ktx_error_code_e_KTX_NOT_FOUND,
+            Error::OutOfMemory => ktx_error_code_e_KTX_OUT_OF_MEMORY,
+            Error::TranscodeFailed => ktx_error_code_e_KTX_TRANSCODE_FAILED,
+            Error::UnknownFileFormat => ktx_error_code_e_KTX_UNKNOWN_FILE_FORMAT,
+            Error::UnsupportedTextureType => ktx_error_code_e_KTX_UNSUPPORTED_TEXTURE_TYPE,
+            Error::UnsupportedFeature => ktx_error_code_e_KTX_UNSUPPORTED_FEATURE,
+            Error::LibraryNotLinked => ktx_error_code_e_KTX_LIBRARY_NOT_LINKED,
+            Error::DecompressLengthError => ktx_error_code_e_KTX_DECOMPRESS_LENGTH_ERROR,
+            Error::DecompressChecksumError => ktx_error_code_e_KTX_DECOMPRESS_CHECKSUM_ERROR,
+            Error::UnsupportedFormat(_) | Error::UnsupportedTranscodeTarget(_) => {
+                ktx_error_code_e_KTX_UNSUPPORTED_FEATURE
+            }
+            Error::ImageSizeMismatch { .. } | Error::InvalidMetadataKey(_) => {
+                ktx_error_code_e_KTX_INVALID_VALUE
+            }
+            Error::ImageDecode(_) => ktx_error_code_e_KTX_FILE_DATA_ERROR,
+            Error::Io {
+                kind: io::ErrorKind::UnexpectedEof,
+                ..
+            } => ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF,
+            Error::Io { .. } => ktx_error_code_e_KTX_FILE_READ_ERROR,
+            Error::Other(code) => *code,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Validation errors for [`BasisCompressionParams`]
+///
+/// Returned by [`BasisCompressionParamsBuilder::try_build`].
+///
+/// [`BasisCompressionParams`]: crate::BasisCompressionParams
+/// [`BasisCompressionParamsBuilder::try_build`]: crate::BasisCompressionParamsBuilder::try_build
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamsError {
+    /// A parameter is outside the range accepted by the encoder
+    OutOfRange {
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    /// An ETC1S-only parameter was set while UASTC mode is enabled
+    Etc1sOnly { field: &'static str },
+    /// A swizzle string is not 4 characters from `rgba01`
+    InvalidSwizzle(String),
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "`{field}` is {value}, outside the accepted range {min}..={max}"
+            ),
+            ParamsError::Etc1sOnly { field } => {
+                write!(
+                    f,
+                    "`{field}` only applies to ETC1S and cannot be used with UASTC"
+                )
+            }
+            ParamsError::InvalidSwizzle(swizzle) => write!(
+                f,
+                "Invalid swizzle \"{swizzle}\": expected 4 characters from r, g, b, a, 0, 1"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
 #[allow(non_upper_case_globals)]
 impl From<ktx_error_code_e> for Error {
     fn from(code: ktx_error_code_e) -> Self {
         match code {
-            ktx_error_code_e_KTX_SUCCESS => {
-                unreachable!("Success should not be converted to error")
-            }
+            // Not an error, but kept distinct rather than panicking; use
+            // `Error::try_from_code` where success is possible
+            ktx_error_code_e_KTX_SUCCESS => Error::Other(code),
             ktx_error_code_e_KTX_FILE_DATA_ERROR => Error::FileDataError,
             ktx_error_code_e_KTX_FILE_ISPIPE => Error::FilePipe,
             ktx_error_code_e_KTX_FILE_OPEN_FAILED => Error::FileOpenFailed,
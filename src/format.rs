@@ -1,8 +1,10 @@
 use crate::bindings::*;
+use crate::vk_format::VkFormat;
 
 /// GPU texture formats supported for transcoding
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TranscodeFormat {
     /// ETC1 RGB format (mobile)
     Etc1Rgb,
@@ -54,3 +56,231 @@ impl From<TranscodeFormat> for ktx_transcode_fmt_e {
         }
     }
 }
+
+impl TranscodeFormat {
+    /// Every transcode target
+    pub const ALL: [TranscodeFormat; 14] = [
+        TranscodeFormat::Etc1Rgb,
+        TranscodeFormat::Etc2Rgba,
+        TranscodeFormat::Bc1Rgb,
+        TranscodeFormat::Bc3Rgba,
+        TranscodeFormat::Bc4R,
+        TranscodeFormat::Bc5Rg,
+        TranscodeFormat::Bc7Rgba,
+        TranscodeFormat::Pvrtc1_4_Rgb,
+        TranscodeFormat::Pvrtc1_4_Rgba,
+        TranscodeFormat::Astc_4x4_Rgba,
+        TranscodeFormat::Rgba32,
+        TranscodeFormat::Rgb565,
+        TranscodeFormat::Bgr565,
+        TranscodeFormat::Rgba4444,
+    ];
+
+    /// Returns the number of channels the format stores
+    pub fn num_components(&self) -> u32 {
+        match self {
+            TranscodeFormat::Bc4R => 1,
+            TranscodeFormat::Bc5Rg => 2,
+            TranscodeFormat::Etc1Rgb
+            | TranscodeFormat::Bc1Rgb
+            | TranscodeFormat::Pvrtc1_4_Rgb
+            | TranscodeFormat::Rgb565
+            | TranscodeFormat::Bgr565 => 3,
+            TranscodeFormat::Etc2Rgba
+            | TranscodeFormat::Bc3Rgba
+            | TranscodeFormat::Bc7Rgba
+            | TranscodeFormat::Pvrtc1_4_Rgba
+            | TranscodeFormat::Astc_4x4_Rgba
+            | TranscodeFormat::Rgba32
+            | TranscodeFormat::Rgba4444 => 4,
+        }
+    }
+
+    /// Returns the transcode target that produces `format`, if Basis Universal
+    /// can transcode to it
+    ///
+    /// sRGB and UNORM variants share a target; which one libktx writes is decided
+    /// by the transfer function of the source texture. PVRTC1 maps to the RGBA
+    /// target, which produces the same blocks as the RGB one.
+    pub fn from_vk_format(format: VkFormat) -> Option<Self> {
+        match format {
+            VkFormat::Bc1RgbUnormBlock | VkFormat::Bc1RgbSrgbBlock => Some(TranscodeFormat::Bc1Rgb),
+            VkFormat::Bc3UnormBlock | VkFormat::Bc3SrgbBlock => Some(TranscodeFormat::Bc3Rgba),
+            VkFormat::Bc4UnormBlock => Some(TranscodeFormat::Bc4R),
+            VkFormat::Bc5UnormBlock => Some(TranscodeFormat::Bc5Rg),
+            VkFormat::Bc7UnormBlock | VkFormat::Bc7SrgbBlock => Some(TranscodeFormat::Bc7Rgba),
+            VkFormat::Etc2R8G8B8UnormBlock | VkFormat::Etc2R8G8B8SrgbBlock => {
+                Some(TranscodeFormat::Etc1Rgb)
+            }
+            VkFormat::Etc2R8G8B8A8UnormBlock | VkFormat::Etc2R8G8B8A8SrgbBlock => {
+                Some(TranscodeFormat::Etc2Rgba)
+            }
+            VkFormat::Astc4x4UnormBlock | VkFormat::Astc4x4SrgbBlock => {
+                Some(TranscodeFormat::Astc_4x4_Rgba)
+            }
+            VkFormat::Pvrtc1_4bppUnormBlockImg | VkFormat::Pvrtc1_4bppSrgbBlockImg => {
+                Some(TranscodeFormat::Pvrtc1_4_Rgba)
+            }
+            VkFormat::R8G8B8A8Unorm | VkFormat::R8G8B8A8Srgb => Some(TranscodeFormat::Rgba32),
+            VkFormat::R5G6B5UnormPack16 => Some(TranscodeFormat::Rgb565),
+            VkFormat::B5G6R5UnormPack16 => Some(TranscodeFormat::Bgr565),
+            VkFormat::R4G4B4A4UnormPack16 => Some(TranscodeFormat::Rgba4444),
+            _ => None,
+        }
+    }
+
+    /// Returns the `VkFormat` of data transcoded to this target
+    ///
+    /// `srgb` picks the sRGB variant and should follow the transfer function of
+    /// the source texture. BC4, BC5 and the packed 16-bit targets have no sRGB
+    /// variant and ignore it.
+    pub fn to_vk_format(&self, srgb: bool) -> Option<VkFormat> {
+        let (unorm, srgb_format) = match self {
+            TranscodeFormat::Etc1Rgb => (
+                VkFormat::Etc2R8G8B8UnormBlock,
+                Some(VkFormat::Etc2R8G8B8SrgbBlock),
+            ),
+            TranscodeFormat::Etc2Rgba => (
+                VkFormat::Etc2R8G8B8A8UnormBlock,
+                Some(VkFormat::Etc2R8G8B8A8SrgbBlock),
+            ),
+            TranscodeFormat::Bc1Rgb => {
+                (VkFormat::Bc1RgbUnormBlock, Some(VkFormat::Bc1RgbSrgbBlock))
+            }
+            TranscodeFormat::Bc3Rgba => (VkFormat::Bc3UnormBlock, Some(VkFormat::Bc3SrgbBlock)),
+            TranscodeFormat::Bc4R => return Some(VkFormat::Bc4UnormBlock),
+            TranscodeFormat::Bc5Rg => return Some(VkFormat::Bc5UnormBlock),
+            TranscodeFormat::Bc7Rgba => (VkFormat::Bc7UnormBlock, Some(VkFormat::Bc7SrgbBlock)),
+            TranscodeFormat::Astc_4x4_Rgba => (
+                VkFormat::Astc4x4UnormBlock,
+                Some(VkFormat::Astc4x4SrgbBlock),
+            ),
+            TranscodeFormat::Pvrtc1_4_Rgb | TranscodeFormat::Pvrtc1_4_Rgba => (
+                VkFormat::Pvrtc1_4bppUnormBlockImg,
+                Some(VkFormat::Pvrtc1_4bppSrgbBlockImg),
+            ),
+            TranscodeFormat::Rgba32 => (VkFormat::R8G8B8A8Unorm, Some(VkFormat::R8G8B8A8Srgb)),
+            TranscodeFormat::Rgb565 => return Some(VkFormat::R5G6B5UnormPack16),
+            TranscodeFormat::Bgr565 => return Some(VkFormat::B5G6R5UnormPack16),
+            TranscodeFormat::Rgba4444 => return Some(VkFormat::R4G4B4A4UnormPack16),
+        };
+
+        if srgb {
+            srgb_format
+        } else {
+            Some(unorm)
+        }
+    }
+
+    /// Returns `true` if a `width` x `height` texture can be transcoded to this
+    /// format
+    ///
+    /// PVRTC1 needs square, power-of-two dimensions. The other targets accept
+    /// any non-zero size.
+    pub fn is_compatible_with(&self, width: u32, height: u32) -> bool {
+        if width == 0 || height == 0 {
+            return false;
+        }
+
+        match self {
+            TranscodeFormat::Pvrtc1_4_Rgb | TranscodeFormat::Pvrtc1_4_Rgba => {
+                width == height && width.is_power_of_two()
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns the block width and height in texels
+    ///
+    /// Uncompressed formats report `(1, 1)`.
+    pub fn block_size(&self) -> (u32, u32) {
+        match self {
+            TranscodeFormat::Rgba32
+            | TranscodeFormat::Rgb565
+            | TranscodeFormat::Bgr565
+            | TranscodeFormat::Rgba4444 => (1, 1),
+            _ => (4, 4),
+        }
+    }
+
+    /// Returns the size in bytes of one block (or texel, if uncompressed)
+    pub fn bytes_per_block(&self) -> u32 {
+        match self {
+            TranscodeFormat::Etc1Rgb
+            | TranscodeFormat::Bc1Rgb
+            | TranscodeFormat::Bc4R
+            | TranscodeFormat::Pvrtc1_4_Rgb
+            | TranscodeFormat::Pvrtc1_4_Rgba => 8,
+            TranscodeFormat::Etc2Rgba
+            | TranscodeFormat::Bc3Rgba
+            | TranscodeFormat::Bc5Rg
+            | TranscodeFormat::Bc7Rgba
+            | TranscodeFormat::Astc_4x4_Rgba => 16,
+            TranscodeFormat::Rgba32 => 4,
+            TranscodeFormat::Rgb565 | TranscodeFormat::Bgr565 | TranscodeFormat::Rgba4444 => 2,
+        }
+    }
+}
+
+/// Supercompression scheme applied to the image data of a KTX2 file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SupercompressionScheme {
+    /// No supercompression
+    None,
+    /// Basis Universal ETC1S (BasisLZ)
+    BasisLz,
+    /// Zstandard
+    Zstd,
+    /// zlib
+    Zlib,
+    /// A vendor or reserved scheme unknown to libktx
+    Other(u32),
+}
+
+impl SupercompressionScheme {
+    /// Get the raw `supercompressionScheme` value
+    pub fn as_raw(&self) -> u32 {
+        match self {
+            SupercompressionScheme::None => ktxSupercmpScheme_KTX_SS_NONE,
+            SupercompressionScheme::BasisLz => ktxSupercmpScheme_KTX_SS_BASIS_LZ,
+            SupercompressionScheme::Zstd => ktxSupercmpScheme_KTX_SS_ZSTD,
+            SupercompressionScheme::Zlib => ktxSupercmpScheme_KTX_SS_ZLIB,
+            SupercompressionScheme::Other(value) => *value,
+        }
+    }
+
+    /// Create a scheme from a raw `supercompressionScheme` value
+    #[allow(non_upper_case_globals)]
+    pub fn from_raw(value: u32) -> Self {
+        match value {
+            ktxSupercmpScheme_KTX_SS_NONE => SupercompressionScheme::None,
+            ktxSupercmpScheme_KTX_SS_BASIS_LZ => SupercompressionScheme::BasisLz,
+            ktxSupercmpScheme_KTX_SS_ZSTD => SupercompressionScheme::Zstd,
+            ktxSupercmpScheme_KTX_SS_ZLIB => SupercompressionScheme::Zlib,
+            other => SupercompressionScheme::Other(other),
+        }
+    }
+}
+
+/// Transfer function (OETF) recorded in the data format descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferFunction {
+    /// Linear values
+    Linear,
+    /// sRGB encoded values
+    Srgb,
+    /// Unspecified or any other transfer function
+    Unknown,
+}
+
+impl TransferFunction {
+    /// Create a transfer function from a raw `khr_df_transfer_e` value
+    #[allow(non_upper_case_globals)]
+    pub fn from_raw(value: u32) -> Self {
+        match value {
+            _khr_df_transfer_e_KHR_DF_TRANSFER_LINEAR => TransferFunction::Linear,
+            _khr_df_transfer_e_KHR_DF_TRANSFER_SRGB => TransferFunction::Srgb,
+            _ => TransferFunction::Unknown,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Conversions between [`Ktx2Texture`] and the `image` crate.
+
+#[cfg(any(feature = "encoders-input", feature = "hdr"))]
+use std::path::Path;
+
+use image::{DynamicImage, ImageBuffer, RgbaImage};
+
+use crate::error::{Error, Result};
+use crate::format::SupercompressionScheme;
+use crate::texture::Ktx2Texture;
+use crate::vk_format::VkFormat;
+
+impl Ktx2Texture {
+    /// Creates a single-level 2D texture from a decoded image
+    ///
+    /// The format follows the image's color type:
+    ///
+    /// | Color type | VkFormat |
+    /// | ---------- | -------- |
+    /// | `L8` | `R8Unorm` |
+    /// | `La8` | `R8G8Unorm` |
+    /// | `Rgb8` | `R8G8B8Unorm` |
+    /// | `Rgba8` | `R8G8B8A8Unorm` |
+    /// | `Rgb32F`, `Rgba32F` | `R32G32B32A32Sfloat` |
+    ///
+    /// Any other color type is converted to RGBA8 first. Use this to keep the
+    /// channel count, [`from_dynamic_image`](Self::from_dynamic_image) for
+    /// four channels with an sRGB choice, and `from_image_bytes` (feature
+    /// `encoders-input`) for still-encoded images.
+    pub fn from_image(img: &DynamicImage) -> Result<Self> {
+        let (width, height) = (img.width(), img.height());
+        let (format, data) = match img {
+            DynamicImage::ImageLuma8(buffer) => (VkFormat::R8Unorm, buffer.as_raw().clone()),
+            DynamicImage::ImageLumaA8(buffer) => (VkFormat::R8G8Unorm, buffer.as_raw().clone()),
+            DynamicImage::ImageRgb8(buffer) => (VkFormat::R8G8B8Unorm, buffer.as_raw().clone()),
+            DynamicImage::ImageRgba8(buffer) => (VkFormat::R8G8B8A8Unorm, buffer.as_raw().clone()),
+            DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+                return Self::from_rgba_f32(width, height, img.to_rgba32f().as_raw());
+            }
+            _ => (VkFormat::R8G8B8A8Unorm, img.to_rgba8().into_raw()),
+        };
+
+        let mut texture = Self::create(width, height, 1, 1, 1, 1, format)?;
+        texture.set_image_data(0, 0, 0, &data)?;
+        Ok(texture)
+    }
+
+    /// Creates a single-level four-channel 2D texture from a decoded image
+    ///
+    /// Unlike [`from_image`](Self::from_image), the channel count is always
+    /// four: 32-bit float images become `R32G32B32A32Sfloat` and everything
+    /// else is converted to RGBA8, tagged `R8G8B8A8Srgb` when `srgb` is set and
+    /// `R8G8B8A8Unorm` otherwise. Float data is linear, so `srgb` does not
+    /// apply to it. 16-bit integer images are reduced to 8 bits per channel.
+    /// Encoded images go through `from_image_bytes` (feature `encoders-input`).
+    pub fn from_dynamic_image(img: &DynamicImage, srgb: bool) -> Result<Self> {
+        let (width, height) = (img.width(), img.height());
+        let (format, data) = match img {
+            DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+                return Self::from_rgba_f32(width, height, img.to_rgba32f().as_raw());
+            }
+            _ if srgb => (VkFormat::R8G8B8A8Srgb, img.to_rgba8().into_raw()),
+            _ => (VkFormat::R8G8B8A8Unorm, img.to_rgba8().into_raw()),
+        };
+
+        Self::from_pixels(width, height, format, &data)
+    }
+
+    /// Creates an `R8G8B8A8Unorm` texture from a precomputed mip chain
+    ///
+    /// `levels[0]` sets the base size and every following level must be half
+    /// the size of the previous one (rounded down, at least 1). An empty or
+    /// malformed chain returns [`Error::InvalidValue`].
+    pub fn from_mip_chain(levels: &[&RgbaImage]) -> Result<Self> {
+        let base = levels.first().ok_or(Error::InvalidValue)?;
+        let (width, height) = base.dimensions();
+        if levels.len() as u32 > Self::full_mip_levels(width, height, 1) {
+            return Err(Error::InvalidValue);
+        }
+
+        for (level, image) in levels.iter().enumerate() {
+            let expected = ((width >> level).max(1), (height >> level).max(1));
+            if image.dimensions() != expected {
+                return Err(Error::InvalidValue);
+            }
+        }
+
+        let mut texture = Self::create(
+            width,
+            height,
+            1,
+            1,
+            1,
+            levels.len() as u32,
+            VkFormat::R8G8B8A8Unorm,
+        )?;
+        for (level, image) in levels.iter().enumerate() {
+            texture.set_image_data(level as u32, 0, 0, image.as_raw())?;
+        }
+
+        Ok(texture)
+    }
+
+    /// Decodes one image of any texture [`decode_rgba8`](Self::decode_rgba8)
+    /// supports into an [`RgbaImage`] the size of `level`
+    ///
+    /// Basis Universal textures are transcoded on an internal copy.
+    pub fn to_rgba_image(&self, level: u32, layer: u32, face: u32) -> Result<RgbaImage> {
+        let (width, height, _) = self.level_dimensions(level)?;
+        let pixels = self.decode_rgba8(level, layer, face)?;
+        RgbaImage::from_raw(width, height, pixels).ok_or(Error::InvalidValue)
+    }
+
+    /// Copies one image of an uncompressed 2D texture into a [`DynamicImage`]
+    ///
+    /// 8-bit one to four channel formats, their BGR variants and
+    /// `R32G32B32A32Sfloat` are supported. Block-compressed and Basis Universal
+    /// textures return [`Error::UnsupportedFormat`]; transcode or use
+    /// [`decode_rgba8`](Self::decode_rgba8) for those.
+    pub fn to_image(&self, level: u32, layer: u32, face: u32) -> Result<DynamicImage> {
+        if level >= self.levels() || layer >= self.layers() || face >= self.faces() {
+            return Err(Error::InvalidValue);
+        }
+        if self.depth() > 1 {
+            return Err(Error::UnsupportedTextureType);
+        }
+
+        let format = self.vk_format();
+        if self.is_compressed() || self.needs_transcoding() {
+            return Err(Error::UnsupportedFormat(format));
+        }
+
+        // Zstd/zlib payloads are only inflated by libktx when loading, so reload a copy
+        if matches!(
+            self.supercompression_scheme(),
+            SupercompressionScheme::Zstd | SupercompressionScheme::Zlib
+        ) {
+            let inflated = Self::from_memory(&self.write_to_memory()?)?;
+            return inflated.to_image(level, layer, face);
+        }
+
+        let width = (self.width() >> level).max(1);
+        let height = (self.height() >> level).max(1);
+        let data = self.get_image_data(level, layer, face)?;
+
+        let image = match format {
+            VkFormat::R8Unorm => {
+                ImageBuffer::from_raw(width, height, data.to_vec()).map(DynamicImage::ImageLuma8)
+            }
+            VkFormat::R8G8Unorm => {
+                ImageBuffer::from_raw(width, height, data.to_vec()).map(DynamicImage::ImageLumaA8)
+            }
+            VkFormat::R8G8B8Unorm => {
+                ImageBuffer::from_raw(width, height, data.to_vec()).map(DynamicImage::ImageRgb8)
+            }
+            VkFormat::B8G8R8Unorm => {
+                let pixels = data
+                    .chunks_exact(3)
+                    .flat_map(|p| [p[2], p[1], p[0]])
+                    .collect();
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+            }
+            VkFormat::R8G8B8A8Unorm | VkFormat::R8G8B8A8Srgb => {
+                ImageBuffer::from_raw(width, height, data.to_vec()).map(DynamicImage::ImageRgba8)
+            }
+            VkFormat::B8G8R8A8Unorm | VkFormat::B8G8R8A8Srgb => {
+                let pixels = data
+                    .chunks_exact(4)
+                    .flat_map(|p| [p[2], p[1], p[0], p[3]])
+                    .collect();
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba8)
+            }
+            VkFormat::R32G32B32A32Sfloat => {
+                let pixels = data
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba32F)
+            }
+            _ => return Err(Error::UnsupportedFormat(format)),
+        };
+
+        image.ok_or(Error::InvalidValue)
+    }
+}
+
+impl TryFrom<&RgbaImage> for Ktx2Texture {
+    type Error = Error;
+
+    /// Creates a single-level `R8G8B8A8Unorm` texture
+    fn try_from(image: &RgbaImage) -> Result<Self> {
+        Self::from_mip_chain(&[image])
+    }
+}
+
+#[cfg(feature = "encoders-input")]
+impl Ktx2Texture {
+    /// Decodes a PNG, JPEG or other image file into a single-level 2D texture
+    ///
+    /// See [`from_image_bytes`](Self::from_image_bytes) for the format choice.
+    /// The file's format is detected from its contents, not its extension.
+    pub fn from_image_file<P: AsRef<Path>>(path: P, srgb: bool) -> Result<Self> {
+        let img = image::ImageReader::open(path)?
+            .with_guessed_format()?
+            .decode()
+            .map_err(decode_error)?;
+        Self::from_decoded_image(&img, srgb)
+    }
+
+    /// Decodes an encoded PNG, JPEG or other image into a single-level 2D
+    /// texture, ready for [`compress_basis`](Self::compress_basis)
+    ///
+    /// Grayscale images without alpha become `R8Unorm` when `srgb` is `false`,
+    /// keeping one channel. Everything else, and grayscale with `srgb` set
+    /// since there is no single-channel sRGB format, is converted to RGBA8
+    /// tagged `R8G8B8A8Srgb` or `R8G8B8A8Unorm`. Higher bit depths are reduced
+    /// to 8 bits. Decoder failures return [`Error::ImageDecode`] with the
+    /// decoder's message. Already decoded images go through
+    /// [`from_image`](Self::from_image) or
+    /// [`from_dynamic_image`](Self::from_dynamic_image), which keep float data.
+    pub fn from_image_bytes(bytes: &[u8], srgb: bool) -> Result<Self> {
+        let img = image::load_from_memory(bytes).map_err(decode_error)?;
+        Self::from_decoded_image(&img, srgb)
+    }
+
+    /// Format choice shared by the encoded-image constructors; unlike
+    /// [`from_dynamic_image`](Self::from_dynamic_image) it keeps grayscale
+    /// as one channel and reduces float images to 8 bits
+    fn from_decoded_image(img: &DynamicImage, srgb: bool) -> Result<Self> {
+        let (width, height) = (img.width(), img.height());
+        match img {
+            DynamicImage::ImageLuma8(_) | DynamicImage::ImageLuma16(_) if !srgb => {
+                Self::from_pixels(width, height, VkFormat::R8Unorm, img.to_luma8().as_raw())
+            }
+            _ if srgb => Self::from_rgba8_srgb(width, height, img.to_rgba8().as_raw()),
+            _ => Self::from_rgba8(width, height, img.to_rgba8().as_raw()),
+        }
+    }
+}
+
+#[cfg(feature = "hdr")]
+impl Ktx2Texture {
+    /// Decodes a Radiance `.hdr` or OpenEXR file into a single-level
+    /// `R32G32B32A32Sfloat` texture
+    ///
+    /// See [`from_hdr_bytes`](Self::from_hdr_bytes).
+    pub fn from_hdr_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let img = image::ImageReader::open(path)?
+            .with_guessed_format()?
+            .decode()
+            .map_err(decode_error)?;
+        Self::from_hdr_image(&img)
+    }
+
+    /// Decodes an encoded Radiance `.hdr` or OpenEXR image into a single-level
+    /// `R32G32B32A32Sfloat` texture
+    ///
+    /// RGB images get an alpha of 1.0. The texels keep their full range, so
+    /// the result can be stored or uploaded as is but not passed to
+    /// [`compress_basis`](Self::compress_basis). Any other encoding returns
+    /// [`Error::UnsupportedFeature`] rather than being promoted from 8 bits.
+    pub fn from_hdr_bytes(bytes: &[u8]) -> Result<Self> {
+        let img = image::load_from_memory(bytes).map_err(decode_error)?;
+        Self::from_hdr_image(&img)
+    }
+
+    fn from_hdr_image(img: &DynamicImage) -> Result<Self> {
+        if !matches!(
+            img,
+            DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_)
+        ) {
+            return Err(Error::UnsupportedFeature);
+        }
+        let pixels = img.to_rgba32f();
+        Self::from_rgba_f32(img.width(), img.height(), pixels.as_raw())
+    }
+}
+
+#[cfg(any(feature = "encoders-input", feature = "hdr"))]
+fn decode_error(error: image::ImageError) -> Error {
+    match error {
+        image::ImageError::IoError(error) => error.into(),
+        error => Error::ImageDecode(error.to_string()),
+    }
+}
@@ -34,19 +34,75 @@
 //! ```
 
 // Internal modules
+mod anim_data;
+#[cfg(feature = "ash")]
+mod ash_support;
+mod batch;
 mod bindings;
 mod compression;
+mod cube_face;
+#[cfg(feature = "dds")]
+mod dds;
+mod dfd;
 mod error;
 mod format;
+#[cfg(feature = "image")]
+mod image_support;
+mod options;
+mod orientation;
+mod stream;
 mod texture;
+mod validation;
+mod view;
 mod vk_format;
+#[cfg(feature = "wgpu")]
+mod wgpu_support;
 
 #[cfg(test)]
 mod tests;
 
+/// Raw libktx bindings generated by bindgen
+///
+/// An escape hatch for libktx functions the safe API does not cover yet. Pass
+/// [`Ktx2Texture::as_raw`] to them to operate on an existing texture.
+///
+/// **Unstable and unsafe.** The contents mirror the bundled libktx headers and
+/// may change with any libktx update, without a semver-major release of this
+/// crate. Nothing here upholds the invariants of the safe wrapper; in
+/// particular, do not free or replace a wrapped texture's image data.
+///
+/// ```rust,no_run
+/// use ktx2_rw::{sys, Ktx2Texture, VkFormat};
+/// # fn main() -> ktx2_rw::Result<()> {
+///
+/// let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8Unorm)?;
+/// let components = unsafe { sys::ktxTexture2_GetNumComponents(texture.as_raw()) };
+/// assert_eq!(components, 2);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "sys")]
+pub mod sys {
+    pub use crate::bindings::*;
+}
+
 // Public API exports
-pub use compression::{BasisCompressionParams, BasisCompressionParamsBuilder};
-pub use error::{Error, Result};
-pub use format::TranscodeFormat;
-pub use texture::Ktx2Texture;
+pub use anim_data::AnimData;
+pub use batch::transcode_many;
+pub use compression::{
+    AstcBlockDimension, AstcMode, AstcParams, AstcParamsBuilder, BasisCompressionParams,
+    BasisCompressionParamsBuilder, CompressionMode, CompressionProgress, CompressionReport,
+    CompressionStage,
+};
+pub use cube_face::CubeFace;
+pub use dfd::{DataFormatDescriptor, DfdSample};
+pub use error::{Error, ParamsError, Result};
+pub use format::{SupercompressionScheme, TranscodeFormat, TransferFunction};
+pub use options::{LoadOptions, TextureCreateFlags, WriteOptions};
+pub use orientation::{Orientation, XOrient, YOrient, ZOrient};
+pub use texture::{ImageRef, Ktx2ArrayBuilder, Ktx2Texture, Ktx2TextureBuilder};
+pub use validation::ValidationWarning;
+pub use view::Ktx2TextureView;
 pub use vk_format::VkFormat;
+#[cfg(feature = "wgpu")]
+pub use wgpu_support::pick_transcode_format_for_wgpu;
@@ -0,0 +1,132 @@
+//! Options controlling how textures are loaded and written.
+
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::bindings::*;
+use crate::format::SupercompressionScheme;
+
+/// Flags passed to libktx when creating a texture from a file or memory
+///
+/// Mirrors `ktxTextureCreateFlagBits`. Combine flags with `|`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::TextureCreateFlags;
+///
+/// let flags = TextureCreateFlags::LOAD_IMAGE_DATA | TextureCreateFlags::SKIP_KV_DATA;
+/// assert!(flags.contains(TextureCreateFlags::SKIP_KV_DATA));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextureCreateFlags(u32);
+
+impl TextureCreateFlags {
+    /// Read only the header, level index and metadata
+    pub const NONE: Self = Self(ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_NO_FLAGS);
+    /// Load the image data along with the header
+    pub const LOAD_IMAGE_DATA: Self =
+        Self(ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_LOAD_IMAGE_DATA_BIT);
+    /// Keep the key/value data as a raw block instead of parsing it, so the
+    /// metadata accessors see no entries
+    pub const RAW_KV_DATA: Self = Self(ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_RAW_KVDATA_BIT);
+    /// Skip the key/value data entirely
+    pub const SKIP_KV_DATA: Self =
+        Self(ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_SKIP_KVDATA_BIT);
+
+    /// Get the raw `ktxTextureCreateFlags` value
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if every flag in `other` is set
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for TextureCreateFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for TextureCreateFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Simple loading options for [`Ktx2Texture::from_file_with_options`]
+///
+/// A friendlier alternative to [`TextureCreateFlags`] for the common case of a
+/// header-only scan.
+///
+/// [`Ktx2Texture::from_file_with_options`]: crate::Ktx2Texture::from_file_with_options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Load the image data; when `false` only the header and metadata are read
+    pub load_image_data: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            load_image_data: true,
+        }
+    }
+}
+
+impl From<LoadOptions> for TextureCreateFlags {
+    fn from(options: LoadOptions) -> Self {
+        if options.load_image_data {
+            TextureCreateFlags::LOAD_IMAGE_DATA
+        } else {
+            TextureCreateFlags::NONE
+        }
+    }
+}
+
+/// Settings for [`Ktx2Texture::write_to_file_with`] and
+/// [`Ktx2Texture::write_to_memory_with`]
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{SupercompressionScheme, WriteOptions};
+///
+/// let options = WriteOptions {
+///     supercompression: Some(SupercompressionScheme::Zstd),
+///     ..WriteOptions::default()
+/// };
+/// assert_eq!(options.zstd_level, 3);
+/// ```
+///
+/// [`Ktx2Texture::write_to_file_with`]: crate::Ktx2Texture::write_to_file_with
+/// [`Ktx2Texture::write_to_memory_with`]: crate::Ktx2Texture::write_to_memory_with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Supercompression scheme of the written file; `None` keeps the current one
+    ///
+    /// Only [`SupercompressionScheme::Zstd`] and [`SupercompressionScheme::None`]
+    /// can be applied. Requesting either for a BasisLZ (ETC1S) texture returns
+    /// [`Error::InvalidOperation`], since its payload is already supercompressed.
+    ///
+    /// [`Error::InvalidOperation`]: crate::Error::InvalidOperation
+    pub supercompression: Option<SupercompressionScheme>,
+    /// Zstandard level from 1 to 22, used when deflating with zstd
+    pub zstd_level: u32,
+    /// Add the default `KTXwriter` entry if the texture has none
+    pub add_writer_metadata: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            supercompression: None,
+            zstd_level: 3,
+            add_writer_metadata: true,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Typed access to the `KTXorientation` metadata entry.
+//!
+//! The KTX2 specification describes the logical orientation of the image data
+//! with one character per dimension: `r`/`l` for X, `d`/`u` for Y and `o`/`i`
+//! for Z.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Direction of increasing X texel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XOrient {
+    /// Rightward (`r`)
+    Right,
+    /// Leftward (`l`)
+    Left,
+}
+
+/// Direction of increasing Y texel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YOrient {
+    /// Downward (`d`), the default for Vulkan-style data
+    Down,
+    /// Upward (`u`), the default for OpenGL-style data
+    Up,
+}
+
+/// Direction of increasing Z texel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZOrient {
+    /// Out of the screen (`o`)
+    Out,
+    /// Into the screen (`i`)
+    In,
+}
+
+/// Logical orientation stored in the `KTXorientation` metadata entry
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{Orientation, XOrient, YOrient};
+///
+/// let orientation: Orientation = "ru".parse().unwrap();
+/// assert_eq!(orientation.x, XOrient::Right);
+/// assert_eq!(orientation.y, YOrient::Up);
+/// assert_eq!(orientation.to_string(), "ru");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub x: XOrient,
+    pub y: YOrient,
+    /// Only present for 3D textures
+    pub z: Option<ZOrient>,
+}
+
+impl Orientation {
+    /// Metadata key defined by the KTX2 specification
+    pub const KEY: &'static str = "KTXorientation";
+
+    /// Creates a 2D orientation
+    pub fn new(x: XOrient, y: YOrient) -> Self {
+        Self { x, y, z: None }
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x = match self.x {
+            XOrient::Right => 'r',
+            XOrient::Left => 'l',
+        };
+        let y = match self.y {
+            YOrient::Down => 'd',
+            YOrient::Up => 'u',
+        };
+        write!(f, "{x}{y}")?;
+        match self.z {
+            Some(ZOrient::Out) => write!(f, "o"),
+            Some(ZOrient::In) => write!(f, "i"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Orientation {
+    type Err = Error;
+
+    /// Parses a 2- or 3-character orientation such as `"rd"` or `"rdi"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        let x = match chars.next() {
+            Some('r') => XOrient::Right,
+            Some('l') => XOrient::Left,
+            _ => return Err(Error::InvalidValue),
+        };
+        let y = match chars.next() {
+            Some('d') => YOrient::Down,
+            Some('u') => YOrient::Up,
+            _ => return Err(Error::InvalidValue),
+        };
+        let z = match chars.next() {
+            None => None,
+            Some('o') => Some(ZOrient::Out),
+            Some('i') => Some(ZOrient::In),
+            Some(_) => return Err(Error::InvalidValue),
+        };
+        if chars.next().is_some() {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self { x, y, z })
+    }
+}
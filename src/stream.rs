@@ -0,0 +1,217 @@
+//! Custom `ktxStream` implementations backed by Rust I/O traits.
+
+use std::any::Any;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::bindings::*;
+
+/// Exposes a Rust I/O object to libktx as a custom `ktxStream`
+///
+/// The callbacks find the adapter through `data.custom_ptr.address`, so it must
+/// stay in place while libktx uses the stream. Panics raised by the wrapped
+/// object are caught at the FFI boundary and resumed by [`finish`](Self::finish).
+pub(crate) struct StreamAdapter<T> {
+    inner: T,
+    /// Bytes written so far, for writers that cannot report their position
+    written: u64,
+    error: Option<io::Error>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<T> StreamAdapter<T> {
+    pub(crate) fn new(inner: T) -> Self {
+        Self {
+            inner,
+            written: 0,
+            error: None,
+            panic: None,
+        }
+    }
+
+    /// Returns the number of bytes written through the stream
+    pub(crate) fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Resumes any panic caught while libktx was driving the stream, then
+    /// returns the last I/O error reported by the wrapped object
+    pub(crate) fn finish(self) -> Option<io::Error> {
+        if let Some(payload) = self.panic {
+            panic::resume_unwind(payload);
+        }
+        self.error
+    }
+
+    /// Returns a stream with no callbacks that points back at `self`
+    fn empty_stream(&mut self) -> ktxStream {
+        // Safety: every field of ktxStream is a nullable pointer or an integer
+        let mut stream: ktxStream = unsafe { std::mem::zeroed() };
+        stream.destruct = Some(destruct);
+        stream.type_ = streamType_eStreamTypeCustom;
+        stream.data.custom_ptr.address = self as *mut Self as *mut libc::c_void;
+        stream.closeOnDestruct = false;
+        stream
+    }
+
+    /// Runs `op` on the wrapped object, translating failures to `failure`
+    fn call(
+        &mut self,
+        failure: ktx_error_code_e,
+        op: impl FnOnce(&mut T) -> io::Result<()>,
+    ) -> ktx_error_code_e {
+        if self.panic.is_some() {
+            return failure;
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(|| op(&mut self.inner))) {
+            Ok(Ok(())) => ktx_error_code_e_KTX_SUCCESS,
+            Ok(Err(e)) => {
+                let code = if e.kind() == io::ErrorKind::UnexpectedEof {
+                    ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF
+                } else {
+                    failure
+                };
+                self.error = Some(e);
+                code
+            }
+            Err(payload) => {
+                self.panic = Some(payload);
+                failure
+            }
+        }
+    }
+}
+
+impl<R: Read + Seek> StreamAdapter<R> {
+    /// Returns a read-only stream over the wrapped reader
+    pub(crate) fn reader_stream(&mut self) -> ktxStream {
+        let mut stream = self.empty_stream();
+        stream.read = Some(read::<R>);
+        stream.skip = Some(skip::<R>);
+        stream.getpos = Some(getpos::<R>);
+        stream.setpos = Some(setpos::<R>);
+        stream.getsize = Some(getsize::<R>);
+        stream
+    }
+}
+
+impl<W: Write> StreamAdapter<W> {
+    /// Returns a write-only, forward-only stream over the wrapped writer
+    pub(crate) fn writer_stream(&mut self) -> ktxStream {
+        let mut stream = self.empty_stream();
+        stream.write = Some(write::<W>);
+        stream.getpos = Some(written_pos::<W>);
+        stream.setpos = Some(written_setpos::<W>);
+        stream.getsize = Some(written_size::<W>);
+        stream
+    }
+}
+
+/// Recovers the adapter installed by [`StreamAdapter::empty_stream`]
+///
+/// # Safety
+///
+/// `stream` must come from a `StreamAdapter<T>` that is still alive.
+unsafe fn adapter<'a, T>(stream: *mut ktxStream) -> &'a mut StreamAdapter<T> {
+    &mut *((*stream).data.custom_ptr.address as *mut StreamAdapter<T>)
+}
+
+unsafe extern "C" fn read<R: Read>(
+    stream: *mut ktxStream,
+    dst: *mut libc::c_void,
+    count: ktx_size_t,
+) -> ktx_error_code_e {
+    let buf = std::slice::from_raw_parts_mut(dst as *mut u8, count);
+    adapter::<R>(stream).call(ktx_error_code_e_KTX_FILE_READ_ERROR, |reader| {
+        reader.read_exact(buf)
+    })
+}
+
+unsafe extern "C" fn skip<R: Seek>(stream: *mut ktxStream, count: ktx_size_t) -> ktx_error_code_e {
+    adapter::<R>(stream).call(ktx_error_code_e_KTX_FILE_SEEK_ERROR, |reader| {
+        reader.seek(SeekFrom::Current(count as i64)).map(drop)
+    })
+}
+
+unsafe extern "C" fn getpos<R: Seek>(
+    stream: *mut ktxStream,
+    offset: *mut ktx_off_t,
+) -> ktx_error_code_e {
+    adapter::<R>(stream).call(ktx_error_code_e_KTX_FILE_SEEK_ERROR, |reader| {
+        *offset = reader.stream_position()? as ktx_off_t;
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn setpos<R: Seek>(
+    stream: *mut ktxStream,
+    offset: ktx_off_t,
+) -> ktx_error_code_e {
+    adapter::<R>(stream).call(ktx_error_code_e_KTX_FILE_SEEK_ERROR, |reader| {
+        reader.seek(SeekFrom::Start(offset as u64)).map(drop)
+    })
+}
+
+unsafe extern "C" fn getsize<R: Seek>(
+    stream: *mut ktxStream,
+    size: *mut ktx_size_t,
+) -> ktx_error_code_e {
+    adapter::<R>(stream).call(ktx_error_code_e_KTX_FILE_SEEK_ERROR, |reader| {
+        let position = reader.stream_position()?;
+        let end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(position))?;
+        *size = end as ktx_size_t;
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn write<W: Write>(
+    stream: *mut ktxStream,
+    src: *const libc::c_void,
+    size: ktx_size_t,
+    count: ktx_size_t,
+) -> ktx_error_code_e {
+    let len = size.saturating_mul(count);
+    let buf = std::slice::from_raw_parts(src as *const u8, len);
+    let adapter = adapter::<W>(stream);
+    let result = adapter.call(ktx_error_code_e_KTX_FILE_WRITE_ERROR, |writer| {
+        writer.write_all(buf)
+    });
+    if result == ktx_error_code_e_KTX_SUCCESS {
+        adapter.written += len as u64;
+    }
+    result
+}
+
+unsafe extern "C" fn written_pos<W>(
+    stream: *mut ktxStream,
+    offset: *mut ktx_off_t,
+) -> ktx_error_code_e {
+    *offset = adapter::<W>(stream).written as ktx_off_t;
+    ktx_error_code_e_KTX_SUCCESS
+}
+
+/// Writers only move forward, so only the current position can be set
+unsafe extern "C" fn written_setpos<W>(
+    stream: *mut ktxStream,
+    offset: ktx_off_t,
+) -> ktx_error_code_e {
+    if adapter::<W>(stream).written == offset as u64 {
+        ktx_error_code_e_KTX_SUCCESS
+    } else {
+        ktx_error_code_e_KTX_FILE_SEEK_ERROR
+    }
+}
+
+unsafe extern "C" fn written_size<W>(
+    stream: *mut ktxStream,
+    size: *mut ktx_size_t,
+) -> ktx_error_code_e {
+    *size = adapter::<W>(stream).written as ktx_size_t;
+    ktx_error_code_e_KTX_SUCCESS
+}
+
+/// libktx keeps a copy of the stream in the texture and destructs it when the
+/// texture is destroyed; the adapter is owned on the Rust side, so do nothing
+unsafe extern "C" fn destruct(_stream: *mut ktxStream) {}
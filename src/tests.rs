@@ -1,8 +1,20 @@
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+use crate::anim_data::AnimData;
+use crate::batch::transcode_many;
 use crate::bindings::*;
-use crate::compression::BasisCompressionParams;
-use crate::error::Error;
-use crate::format::TranscodeFormat;
-use crate::texture::Ktx2Texture;
+use crate::compression::{
+    AstcBlockDimension, AstcParams, BasisCompressionParams, CompressionMode, CompressionStage,
+};
+use crate::cube_face::CubeFace;
+use crate::dfd::DataFormatDescriptor;
+use crate::error::{Error, ParamsError};
+use crate::format::{SupercompressionScheme, TranscodeFormat, TransferFunction};
+use crate::options::{LoadOptions, TextureCreateFlags, WriteOptions};
+use crate::orientation::{Orientation, XOrient, YOrient, ZOrient};
+use crate::texture::{write_atomically, Ktx2ArrayBuilder, Ktx2Texture};
+use crate::validation::ValidationWarning;
+use crate::view::Ktx2TextureView;
 use crate::vk_format::VkFormat;
 
 // ============================================================================
@@ -85,6 +97,9 @@ fn test_error_display() {
     let error = Error::OutOfMemory;
     assert!(error.to_string().contains("Not enough memory"));
 
+    let error = Error::UnsupportedTranscodeTarget(TranscodeFormat::Pvrtc1_4_Rgb);
+    assert!(error.to_string().contains("Pvrtc1_4_Rgb"));
+
     let error = Error::Other(12345);
     assert!(error.to_string().contains("12345"));
 }
@@ -156,6 +171,83 @@ fn test_all_transcode_format_conversions() {
     }
 }
 
+#[test]
+fn test_transcode_format_block_size() {
+    assert_eq!(TranscodeFormat::Bc7Rgba.block_size(), (4, 4));
+    assert_eq!(TranscodeFormat::Bc7Rgba.bytes_per_block(), 16);
+    assert_eq!(TranscodeFormat::Etc1Rgb.bytes_per_block(), 8);
+    assert_eq!(TranscodeFormat::Rgba32.block_size(), (1, 1));
+    assert_eq!(TranscodeFormat::Rgba32.bytes_per_block(), 4);
+    assert_eq!(TranscodeFormat::Rgba4444.bytes_per_block(), 2);
+}
+
+#[test]
+fn test_transcode_format_is_compatible_with() {
+    assert!(!TranscodeFormat::Pvrtc1_4_Rgb.is_compatible_with(100, 200));
+    assert!(!TranscodeFormat::Pvrtc1_4_Rgba.is_compatible_with(48, 32));
+    assert!(!TranscodeFormat::Pvrtc1_4_Rgba.is_compatible_with(256, 128));
+    assert!(TranscodeFormat::Pvrtc1_4_Rgb.is_compatible_with(256, 256));
+    assert!(TranscodeFormat::Bc7Rgba.is_compatible_with(100, 200));
+    assert!(!TranscodeFormat::Bc7Rgba.is_compatible_with(0, 16));
+}
+
+#[test]
+fn test_transcode_format_to_vk_format() {
+    assert_eq!(
+        TranscodeFormat::Bc7Rgba.to_vk_format(false),
+        Some(VkFormat::Bc7UnormBlock)
+    );
+    assert_eq!(
+        TranscodeFormat::Bc7Rgba.to_vk_format(true),
+        Some(VkFormat::Bc7SrgbBlock)
+    );
+    assert_eq!(
+        TranscodeFormat::Etc2Rgba.to_vk_format(false),
+        Some(VkFormat::Etc2R8G8B8A8UnormBlock)
+    );
+    assert_eq!(
+        TranscodeFormat::Rgba32.to_vk_format(false),
+        Some(VkFormat::R8G8B8A8Unorm)
+    );
+    assert_eq!(
+        TranscodeFormat::Bc5Rg.to_vk_format(true),
+        Some(VkFormat::Bc5UnormBlock)
+    );
+    assert_eq!(
+        TranscodeFormat::Bc1Rgb.to_vk_format(true),
+        Some(VkFormat::Bc1RgbSrgbBlock)
+    );
+    assert_eq!(
+        TranscodeFormat::Pvrtc1_4_Rgb.to_vk_format(false),
+        Some(VkFormat::Pvrtc1_4bppUnormBlockImg)
+    );
+    assert_eq!(
+        TranscodeFormat::Pvrtc1_4_Rgba.to_vk_format(true),
+        Some(VkFormat::Pvrtc1_4bppSrgbBlockImg)
+    );
+    for (format, expected) in [
+        (TranscodeFormat::Rgb565, VkFormat::R5G6B5UnormPack16),
+        (TranscodeFormat::Bgr565, VkFormat::B5G6R5UnormPack16),
+        (TranscodeFormat::Rgba4444, VkFormat::R4G4B4A4UnormPack16),
+    ] {
+        assert_eq!(format.to_vk_format(false), Some(expected));
+        assert_eq!(format.to_vk_format(true), Some(expected));
+    }
+
+    // Every target maps, and every mapping leads back to the same target
+    // except opaque PVRTC1, which shares its VkFormat with the RGBA target
+    for format in TranscodeFormat::ALL {
+        for srgb in [false, true] {
+            let vk_format = format.to_vk_format(srgb).unwrap();
+            let expected = match format {
+                TranscodeFormat::Pvrtc1_4_Rgb => TranscodeFormat::Pvrtc1_4_Rgba,
+                _ => format,
+            };
+            assert_eq!(TranscodeFormat::from_vk_format(vk_format), Some(expected));
+        }
+    }
+}
+
 #[test]
 fn test_transcode_format_debug() {
     let format = TranscodeFormat::Bc7Rgba;
@@ -170,6 +262,251 @@ fn test_transcode_format_clone() {
     assert_eq!(format, cloned);
 }
 
+#[test]
+fn test_transcode_format_from_vk_format() {
+    let mappings = [
+        (VkFormat::Bc1RgbUnormBlock, TranscodeFormat::Bc1Rgb),
+        (VkFormat::Bc1RgbSrgbBlock, TranscodeFormat::Bc1Rgb),
+        (VkFormat::Bc3UnormBlock, TranscodeFormat::Bc3Rgba),
+        (VkFormat::Bc3SrgbBlock, TranscodeFormat::Bc3Rgba),
+        (VkFormat::Bc4UnormBlock, TranscodeFormat::Bc4R),
+        (VkFormat::Bc5UnormBlock, TranscodeFormat::Bc5Rg),
+        (VkFormat::Bc7UnormBlock, TranscodeFormat::Bc7Rgba),
+        (VkFormat::Bc7SrgbBlock, TranscodeFormat::Bc7Rgba),
+        (VkFormat::Etc2R8G8B8UnormBlock, TranscodeFormat::Etc1Rgb),
+        (VkFormat::Etc2R8G8B8SrgbBlock, TranscodeFormat::Etc1Rgb),
+        (VkFormat::Etc2R8G8B8A8UnormBlock, TranscodeFormat::Etc2Rgba),
+        (VkFormat::Etc2R8G8B8A8SrgbBlock, TranscodeFormat::Etc2Rgba),
+        (VkFormat::Astc4x4UnormBlock, TranscodeFormat::Astc_4x4_Rgba),
+        (VkFormat::Astc4x4SrgbBlock, TranscodeFormat::Astc_4x4_Rgba),
+        (VkFormat::R8G8B8A8Unorm, TranscodeFormat::Rgba32),
+        (VkFormat::R8G8B8A8Srgb, TranscodeFormat::Rgba32),
+        (
+            VkFormat::Pvrtc1_4bppUnormBlockImg,
+            TranscodeFormat::Pvrtc1_4_Rgba,
+        ),
+        (
+            VkFormat::Pvrtc1_4bppSrgbBlockImg,
+            TranscodeFormat::Pvrtc1_4_Rgba,
+        ),
+        (VkFormat::R5G6B5UnormPack16, TranscodeFormat::Rgb565),
+        (VkFormat::B5G6R5UnormPack16, TranscodeFormat::Bgr565),
+        (VkFormat::R4G4B4A4UnormPack16, TranscodeFormat::Rgba4444),
+    ];
+
+    for (vk_format, expected) in mappings {
+        assert_eq!(
+            TranscodeFormat::from_vk_format(vk_format),
+            Some(expected),
+            "{vk_format:?}"
+        );
+    }
+
+    for vk_format in [
+        VkFormat::Undefined,
+        VkFormat::R32Sfloat,
+        VkFormat::Bc6hUfloatBlock,
+        VkFormat::Etc2R8G8B8A1UnormBlock,
+        VkFormat::Astc8x8UnormBlock,
+    ] {
+        assert_eq!(TranscodeFormat::from_vk_format(vk_format), None);
+    }
+}
+
+// ============================================================================
+// VkFormat Tests
+// ============================================================================
+
+#[test]
+fn test_vk_format_display() {
+    assert_eq!(
+        format!("{}", VkFormat::Bc7UnormBlock),
+        "VK_FORMAT_BC7_UNORM_BLOCK"
+    );
+    assert_eq!(
+        VkFormat::R8G8B8A8Unorm.to_string(),
+        "VK_FORMAT_R8G8B8A8_UNORM"
+    );
+    assert_eq!(
+        VkFormat::Astc4x4SrgbBlock.to_string(),
+        "VK_FORMAT_ASTC_4x4_SRGB_BLOCK"
+    );
+    assert_eq!(VkFormat::Undefined.to_string(), "VK_FORMAT_UNDEFINED");
+}
+
+#[test]
+fn test_vk_format_srgb_variants() {
+    assert!(!VkFormat::R8G8B8A8Unorm.is_srgb());
+    assert!(VkFormat::R8G8B8A8Srgb.is_srgb());
+    assert_eq!(
+        VkFormat::R8G8B8A8Unorm.srgb_variant(),
+        Some(VkFormat::R8G8B8A8Srgb)
+    );
+    assert_eq!(
+        VkFormat::R8G8B8A8Srgb.unorm_variant(),
+        Some(VkFormat::R8G8B8A8Unorm)
+    );
+    assert_eq!(
+        VkFormat::Bc7SrgbBlock.srgb_variant(),
+        Some(VkFormat::Bc7SrgbBlock)
+    );
+    assert_eq!(
+        VkFormat::Astc4x4UnormBlock.unorm_variant(),
+        Some(VkFormat::Astc4x4UnormBlock)
+    );
+
+    // Formats without a pair
+    assert_eq!(VkFormat::R16Unorm.srgb_variant(), None);
+    assert_eq!(VkFormat::R32G32B32A32Sfloat.unorm_variant(), None);
+    assert_eq!(VkFormat::Bc4UnormBlock.srgb_variant(), None);
+
+    // Every sRGB format pairs with its UNORM counterpart
+    assert_eq!(VkFormat::R8Unorm.srgb_variant(), Some(VkFormat::R8Srgb));
+    assert_eq!(
+        VkFormat::Pvrtc1_2bppSrgbBlockImg.unorm_variant(),
+        Some(VkFormat::Pvrtc1_2bppUnormBlockImg)
+    );
+    for &format in VkFormat::ALL.iter().filter(|format| format.is_srgb()) {
+        let unorm = format.unorm_variant().unwrap();
+        assert_eq!(unorm.name(), format.name().replace("_SRGB", "_UNORM"));
+        assert_eq!(unorm.srgb_variant(), Some(format));
+    }
+}
+
+#[test]
+fn test_vk_format_bc_signed_and_hdr_round_trip() {
+    let cases = [
+        (VkFormat::Bc1RgbaUnormBlock, 133),
+        (VkFormat::Bc3UnormBlock, 137),
+        (VkFormat::Bc4UnormBlock, 139),
+        (VkFormat::Bc4SnormBlock, 140),
+        (VkFormat::Bc5UnormBlock, 141),
+        (VkFormat::Bc5SnormBlock, 142),
+        (VkFormat::Bc6hUfloatBlock, 143),
+        (VkFormat::Bc6hSfloatBlock, 144),
+        (VkFormat::Astc8x8UnormBlock, 171),
+    ];
+
+    for (format, raw) in cases {
+        assert_eq!(format.as_raw(), raw);
+        assert_eq!(VkFormat::from_raw(raw), Some(format));
+    }
+}
+
+#[test]
+fn test_vk_format_spec_values() {
+    // Values from vulkan_core.h
+    let cases = [
+        (VkFormat::R4G4UnormPack8, 1),
+        (VkFormat::R5G6B5UnormPack16, 4),
+        (VkFormat::A1R5G5B5UnormPack16, 8),
+        (VkFormat::R8Snorm, 10),
+        (VkFormat::R8Srgb, 15),
+        (VkFormat::B8G8R8Sint, 35),
+        (VkFormat::A8B8G8R8SrgbPack32, 57),
+        (VkFormat::A2R10G10B10UnormPack32, 58),
+        (VkFormat::A2B10G10R10UintPack32, 68),
+        (VkFormat::R16Unorm, 70),
+        (VkFormat::R16G16B16A16Uint, 95),
+        (VkFormat::R32Uint, 98),
+        (VkFormat::R64G64B64A64Sfloat, 121),
+        (VkFormat::B10G11R11UfloatPack32, 122),
+        (VkFormat::E5B9G9R9UfloatPack32, 123),
+        (VkFormat::D16Unorm, 124),
+        (VkFormat::X8D24UnormPack32, 125),
+        (VkFormat::D32Sfloat, 126),
+        (VkFormat::S8Uint, 127),
+        (VkFormat::D16UnormS8Uint, 128),
+        (VkFormat::D24UnormS8Uint, 129),
+        (VkFormat::D32SfloatS8Uint, 130),
+        (VkFormat::Bc1RgbSrgbBlock, 132),
+        (VkFormat::Bc2UnormBlock, 135),
+        (VkFormat::Bc2SrgbBlock, 136),
+        (VkFormat::EacR11UnormBlock, 153),
+        (VkFormat::EacR11G11SnormBlock, 156),
+        (VkFormat::Astc5x4UnormBlock, 159),
+        (VkFormat::Astc6x6SrgbBlock, 166),
+        (VkFormat::Astc10x10UnormBlock, 179),
+        (VkFormat::Astc12x12SrgbBlock, 184),
+        (VkFormat::Pvrtc1_2bppUnormBlockImg, 1000054000),
+        (VkFormat::Pvrtc2_4bppSrgbBlockImg, 1000054007),
+        (VkFormat::Astc4x4SfloatBlock, 1000066000),
+        (VkFormat::Astc12x12SfloatBlock, 1000066013),
+        (VkFormat::G8B8G8R8_422Unorm, 1000156000),
+        (VkFormat::G8B8R8_3plane420Unorm, 1000156002),
+        (VkFormat::R10X6UnormPack16, 1000156007),
+        (VkFormat::G16B16R16_3plane444Unorm, 1000156033),
+        (VkFormat::G8B8R8_2plane444Unorm, 1000330000),
+        (VkFormat::A4R4G4B4UnormPack16, 1000340000),
+        (VkFormat::A8Unorm, 1000470001),
+    ];
+    for (format, raw) in cases {
+        assert_eq!(format.as_raw(), raw, "{format:?}");
+        assert_eq!(VkFormat::from_raw(raw), Some(format));
+    }
+
+    assert_eq!(
+        VkFormat::Astc10x10UnormBlock.name(),
+        "VK_FORMAT_ASTC_10x10_UNORM_BLOCK"
+    );
+    assert_eq!(VkFormat::from_raw(185), None);
+    assert_eq!(VkFormat::from_raw(1000066014), None);
+
+    // ALL is sorted and agrees with from_raw
+    assert!(VkFormat::ALL
+        .windows(2)
+        .all(|w| w[0].as_raw() < w[1].as_raw()));
+    for &format in VkFormat::ALL {
+        assert_eq!(VkFormat::from_raw(format.as_raw()), Some(format));
+        assert!(format.name().starts_with("VK_FORMAT_"));
+    }
+}
+
+#[test]
+fn test_vk_format_extended_layouts() {
+    assert_eq!(VkFormat::Astc10x6UnormBlock.block_size(), (10, 6));
+    assert_eq!(VkFormat::Pvrtc1_2bppUnormBlockImg.block_size(), (8, 4));
+    assert_eq!(VkFormat::G8B8G8R8_422Unorm.block_size(), (2, 1));
+    assert_eq!(VkFormat::G8B8G8R8_422Unorm.bytes_per_block(), 4);
+    assert_eq!(VkFormat::G8B8R8_2plane420Unorm.bytes_per_block(), 0);
+    assert_eq!(VkFormat::D32SfloatS8Uint.bytes_per_block(), 5);
+    assert_eq!(VkFormat::R64G64B64A64Sfloat.bytes_per_block(), 32);
+    assert_eq!(VkFormat::R10X6G10X6Unorm2pack16.bytes_per_block(), 4);
+    assert_eq!(VkFormat::D24UnormS8Uint.channel_count(), 2);
+    assert_eq!(VkFormat::E5B9G9R9UfloatPack32.channel_count(), 3);
+    assert!(VkFormat::A2B10G10R10UnormPack32.has_alpha());
+    assert!(VkFormat::A2B10G10R10UnormPack32.is_packed());
+    assert!(!VkFormat::R8G8B8A8Unorm.is_packed());
+}
+
+#[test]
+fn test_vk_format_channels() {
+    assert_eq!(VkFormat::Undefined.channel_count(), 0);
+    assert_eq!(VkFormat::R8Unorm.channel_count(), 1);
+    assert_eq!(VkFormat::R8G8Unorm.channel_count(), 2);
+    assert_eq!(VkFormat::R8G8B8Unorm.channel_count(), 3);
+    assert_eq!(VkFormat::R8G8B8A8Unorm.channel_count(), 4);
+    assert_eq!(VkFormat::Bc5UnormBlock.channel_count(), 2);
+    assert_eq!(VkFormat::Bc6hUfloatBlock.channel_count(), 3);
+
+    assert!(VkFormat::R8G8B8A8Unorm.has_alpha());
+    assert!(VkFormat::Bc7SrgbBlock.has_alpha());
+    assert!(!VkFormat::B8G8R8Unorm.has_alpha());
+    assert!(!VkFormat::Bc1RgbUnormBlock.has_alpha());
+}
+
+#[test]
+fn test_vk_format_block_size() {
+    assert_eq!(VkFormat::R8G8B8A8Unorm.block_size(), (1, 1));
+    assert_eq!(VkFormat::R8G8B8A8Unorm.bytes_per_block(), 4);
+    assert_eq!(VkFormat::R32G32B32A32Sfloat.bytes_per_block(), 16);
+    assert_eq!(VkFormat::Bc1RgbUnormBlock.block_size(), (4, 4));
+    assert_eq!(VkFormat::Bc1RgbUnormBlock.bytes_per_block(), 8);
+    assert_eq!(VkFormat::Bc7SrgbBlock.bytes_per_block(), 16);
+    assert_eq!(VkFormat::Astc8x8UnormBlock.block_size(), (8, 8));
+    assert_eq!(VkFormat::Undefined.bytes_per_block(), 0);
+}
+
 // ============================================================================
 // BasisCompressionParams Tests
 // ============================================================================
@@ -178,6 +515,8 @@ fn test_transcode_format_clone() {
 fn test_basis_compression_params_default() {
     let params = BasisCompressionParams::builder().build();
     assert!(!params.uastc);
+    assert!(!params.verbose);
+    assert!(!params.no_sse);
     assert_eq!(params.thread_count, 1);
     assert!(!params.no_endpoint_rdo);
     assert!(!params.no_selector_rdo);
@@ -240,6 +579,42 @@ fn test_basis_compression_params_swizzle() {
     assert_eq!(params.input_swizzle, [2, 1, 0, 3]);
 }
 
+#[test]
+fn test_basis_compression_params_swizzle_str() {
+    let params = BasisCompressionParams::builder()
+        .input_swizzle_str("bgra")
+        .unwrap()
+        .build();
+    assert_eq!(params.input_swizzle, [2, 1, 0, 3]);
+
+    let params = BasisCompressionParams::builder()
+        .input_swizzle_str("rrr1")
+        .unwrap()
+        .build();
+    assert_eq!(
+        params.input_swizzle,
+        [0, 0, 0, BasisCompressionParams::SWIZZLE_ONE]
+    );
+
+    let ktx_params: ktxBasisParams = (&params).into();
+    assert_eq!(ktx_params.inputSwizzle.map(|c| c as u8), *b"rrr1");
+
+    // Identity swizzle is sent as "no swizzle"
+    let ktx_params: ktxBasisParams = (&BasisCompressionParams::builder().build()).into();
+    assert_eq!(ktx_params.inputSwizzle, [0; 4]);
+}
+
+#[test]
+fn test_basis_compression_params_swizzle_str_invalid() {
+    for swizzle in ["rgbx", "rgb", "rgbaa", "RGBA", ""] {
+        let result = BasisCompressionParams::builder().input_swizzle_str(swizzle);
+        assert_eq!(
+            result.err(),
+            Some(ParamsError::InvalidSwizzle(swizzle.to_string()))
+        );
+    }
+}
+
 #[test]
 fn test_basis_compression_params_builder_basic() {
     let params = BasisCompressionParams::builder()
@@ -262,6 +637,8 @@ fn test_basis_compression_params_builder_basic() {
 fn test_basis_compression_params_builder_all_methods() {
     let params = BasisCompressionParams::builder()
         .uastc(true)
+        .verbose(true)
+        .no_sse(true)
         .thread_count(8)
         .compression_level(6)
         .quality_level(255)
@@ -282,6 +659,8 @@ fn test_basis_compression_params_builder_all_methods() {
         .build();
 
     assert!(params.uastc);
+    assert!(params.verbose);
+    assert!(params.no_sse);
     assert_eq!(params.thread_count, 8);
     assert_eq!(params.compression_level, 6);
     assert_eq!(params.quality_level, 255);
@@ -430,6 +809,196 @@ fn test_basis_compression_params_builder_equivalence() {
     assert_eq!(manual_params.input_swizzle, builder_params.input_swizzle);
 }
 
+#[test]
+fn test_basis_compression_params_verbose_no_sse_conversion() {
+    let ktx_params: ktxBasisParams = (&BasisCompressionParams::builder().build()).into();
+    assert!(!ktx_params.verbose);
+    assert!(!ktx_params.noSSE);
+
+    let params = BasisCompressionParams::builder()
+        .verbose(true)
+        .no_sse(true)
+        .build();
+    let ktx_params: ktxBasisParams = (&params).into();
+    assert!(ktx_params.verbose);
+    assert!(ktx_params.noSSE);
+}
+
+#[test]
+fn test_thread_count_auto_detect() {
+    let params = BasisCompressionParams::builder().thread_count(0).build();
+    assert_eq!(params.thread_count, 0);
+    let ktx_params: ktxBasisParams = (&params).into();
+    assert!(ktx_params.threadCount >= 1);
+
+    let ktx_params: ktxBasisParams = (&BasisCompressionParams::builder().build()).into();
+    assert_eq!(ktx_params.threadCount, 1);
+
+    let params = AstcParams::builder().thread_count(0).build();
+    let ktx_params: ktxAstcParams = (&params).into();
+    assert!(ktx_params.threadCount >= 1);
+}
+
+#[test]
+fn test_basis_compression_params_try_build_defaults() {
+    assert!(BasisCompressionParams::builder().try_build().is_ok());
+    assert!(BasisCompressionParams::builder()
+        .uastc(true)
+        .quality_level(4)
+        .try_build()
+        .is_ok());
+}
+
+#[test]
+fn test_basis_compression_params_try_build_ranges() {
+    let out_of_range = |result: Result<BasisCompressionParams, ParamsError>| match result {
+        Err(ParamsError::OutOfRange { field, .. }) => field,
+        other => panic!("expected OutOfRange, got {other:?}"),
+    };
+
+    let etc1s = || BasisCompressionParams::builder().uastc(false);
+    let uastc = || {
+        BasisCompressionParams::builder()
+            .uastc(true)
+            .quality_level(2)
+    };
+
+    assert_eq!(
+        out_of_range(etc1s().quality_level(300).try_build()),
+        "quality_level"
+    );
+    assert_eq!(
+        out_of_range(etc1s().quality_level(0).try_build()),
+        "quality_level"
+    );
+    assert_eq!(
+        out_of_range(uastc().quality_level(5).try_build()),
+        "quality_level"
+    );
+    assert_eq!(
+        out_of_range(etc1s().compression_level(99).try_build()),
+        "compression_level"
+    );
+    assert_eq!(
+        out_of_range(etc1s().max_endpoints(20000).try_build()),
+        "max_endpoints"
+    );
+    assert_eq!(
+        out_of_range(etc1s().selector_rdo_threshold(-1.0).try_build()),
+        "selector_rdo_threshold"
+    );
+    assert_eq!(
+        out_of_range(uastc().uastc_rdo_quality_scalar(0.0).try_build()),
+        "uastc_rdo_quality_scalar"
+    );
+    assert_eq!(
+        out_of_range(uastc().uastc_rdo_dict_size(16).try_build()),
+        "uastc_rdo_dict_size"
+    );
+}
+
+#[test]
+fn test_basis_compression_params_try_build_etc1s_only() {
+    let result = BasisCompressionParams::builder()
+        .uastc(true)
+        .quality_level(2)
+        .max_endpoints(8000)
+        .try_build();
+    assert_eq!(
+        result.unwrap_err(),
+        ParamsError::Etc1sOnly {
+            field: "max_endpoints"
+        }
+    );
+
+    let error = ParamsError::OutOfRange {
+        field: "quality_level",
+        value: 300.0,
+        min: 1.0,
+        max: 255.0,
+    };
+    assert_eq!(
+        error.to_string(),
+        "`quality_level` is 300, outside the accepted range 1..=255"
+    );
+}
+
+#[test]
+fn test_basis_compression_params_preset_fast() {
+    let params = BasisCompressionParams::preset_fast().build();
+    assert!(!params.uastc);
+    assert_eq!(params.compression_level, 0);
+    assert_eq!(params.quality_level, 128);
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_basis_compression_params_preset_high_quality() {
+    let params = BasisCompressionParams::preset_high_quality().build();
+    assert!(params.uastc);
+    assert_eq!(
+        params.uastc_flags,
+        ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_VERYSLOW
+    );
+    assert!(!params.uastc_rdo);
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_basis_compression_params_preset_normal_map() {
+    let params = BasisCompressionParams::preset_normal_map().build();
+    assert!(!params.uastc);
+    assert!(params.normal_map);
+    assert!(params.separate_rg_to_color_alpha);
+    assert!(params.no_endpoint_rdo);
+    assert!(params.no_selector_rdo);
+    assert_eq!(params.quality_level, 255);
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_basis_compression_params_preset_ui() {
+    let params = BasisCompressionParams::preset_ui().build();
+    assert!(params.uastc);
+    assert_eq!(
+        params.uastc_flags,
+        ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_DEFAULT
+    );
+    assert!(!params.uastc_rdo);
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_basis_compression_params_preset_override() {
+    let params = BasisCompressionParams::preset_fast()
+        .thread_count(8)
+        .build();
+    assert_eq!(params.thread_count, 8);
+    assert_eq!(params.compression_level, 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_basis_compression_params_serde_round_trip() {
+    let params = BasisCompressionParams::preset_normal_map()
+        .thread_count(4)
+        .build();
+
+    let json = serde_json::to_string(&params).unwrap();
+    let parsed: BasisCompressionParams = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{parsed:?}"), format!("{params:?}"));
+
+    // Omitted fields fall back to the builder defaults
+    let parsed: BasisCompressionParams = serde_json::from_str(r#"{"uastc": true}"#).unwrap();
+    let expected = BasisCompressionParams::builder().uastc(true).build();
+    assert_eq!(format!("{parsed:?}"), format!("{expected:?}"));
+
+    let format: TranscodeFormat = serde_json::from_str(r#""Bc7Rgba""#).unwrap();
+    assert_eq!(format, TranscodeFormat::Bc7Rgba);
+    let format: VkFormat = serde_json::from_str(r#""R8G8B8A8Srgb""#).unwrap();
+    assert_eq!(format, VkFormat::R8G8B8A8Srgb);
+}
+
 // ============================================================================
 // Ktx2Texture Creation Tests
 // ============================================================================
@@ -467,6 +1036,22 @@ fn test_texture_create_invalid_dimensions() {
     assert_eq!(result.unwrap_err(), Error::InvalidValue);
 }
 
+#[test]
+fn test_texture_create_raw_format() {
+    // Raw VkFormat values are still accepted (37 = VK_FORMAT_R8G8B8A8_UNORM)
+    let texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, 37u32).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+}
+
+#[test]
+fn test_texture_create_undefined_format() {
+    let result = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::Undefined);
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    let result = Ktx2Texture::create(64, 64, 1, 1, 1, 1, 0u32);
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
 #[test]
 fn test_texture_create_excessive_dimensions() {
     // Dimensions too large should fail
@@ -485,6 +1070,29 @@ fn test_texture_create_cubemap() {
     assert!(texture.is_cubemap());
 }
 
+#[test]
+fn test_texture_create_cubemap_convenience() {
+    let texture = Ktx2Texture::create_cubemap(64, 7, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert!(texture.is_cubemap());
+    assert!(!texture.is_array());
+    assert_eq!((texture.width(), texture.height()), (64, 64));
+    assert_eq!(texture.faces(), 6);
+    assert_eq!(texture.levels(), 7);
+
+    let array = Ktx2Texture::create_cubemap_array(32, 4, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert!(array.is_cubemap());
+    assert!(array.is_array());
+    assert_eq!(array.layers(), 4);
+
+    for result in [
+        Ktx2Texture::create_cubemap(0, 1, VkFormat::R8G8B8A8Unorm),
+        Ktx2Texture::create_cubemap(64, 8, VkFormat::R8G8B8A8Unorm),
+        Ktx2Texture::create_cubemap_array(64, 0, 1, VkFormat::R8G8B8A8Unorm),
+    ] {
+        assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    }
+}
+
 #[test]
 fn test_texture_create_array() {
     let result = Ktx2Texture::create(256, 256, 1, 8, 1, 1, VkFormat::R8G8B8A8Unorm); // Array with 8 layers
@@ -515,6 +1123,139 @@ fn test_texture_create_3d() {
     assert_eq!(texture.depth(), 64);
 }
 
+#[test]
+fn test_full_mip_levels() {
+    assert_eq!(Ktx2Texture::full_mip_levels(256, 256, 1), 9);
+    assert_eq!(Ktx2Texture::full_mip_levels(640, 480, 1), 10);
+    assert_eq!(Ktx2Texture::full_mip_levels(1, 1, 1), 1);
+    assert_eq!(Ktx2Texture::full_mip_levels(1, 300, 1), 9);
+    assert_eq!(Ktx2Texture::full_mip_levels(16, 16, 64), 7);
+
+    let levels = Ktx2Texture::full_mip_levels(640, 480, 1);
+    let texture = Ktx2Texture::create(640, 480, 1, 1, 1, levels, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(texture.levels(), 10);
+}
+
+#[test]
+fn test_texture_builder_cubemap() {
+    let texture = Ktx2Texture::builder()
+        .dimensions(128, 128)
+        .format(VkFormat::R8G8B8A8Unorm)
+        .faces(6)
+        .levels(8)
+        .build()
+        .unwrap();
+
+    assert!(texture.is_cubemap());
+    assert_eq!(texture.width(), 128);
+    assert_eq!(texture.height(), 128);
+    assert_eq!(texture.depth(), 1);
+    assert_eq!(texture.layers(), 1);
+    assert_eq!(texture.levels(), 8);
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+}
+
+#[test]
+fn test_array_builder() {
+    let layers: Vec<Vec<u8>> = (0..4u8)
+        .map(|i| [i * 60, 0, 0, 255].repeat(8 * 8))
+        .collect();
+    let mut builder = Ktx2ArrayBuilder::new(8, 8, VkFormat::R8G8B8A8Unorm).levels(2);
+    for layer in &layers {
+        builder = builder.push_layer(layer).unwrap();
+    }
+    assert_eq!(
+        builder.clone().push_layer(&[0; 4]).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 256,
+            actual: 4
+        }
+    );
+
+    let texture = builder.build().unwrap();
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert!(reloaded.is_array());
+    assert_eq!(reloaded.layers(), 4);
+    assert_eq!(reloaded.levels(), 2);
+    for (index, layer) in layers.iter().enumerate() {
+        assert_eq!(reloaded.get_image_data(0, index as u32, 0).unwrap(), layer);
+    }
+
+    let single = Ktx2ArrayBuilder::new(8, 8, VkFormat::R8G8B8A8Unorm)
+        .push_layer(&layers[0])
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(single.is_array());
+
+    let empty = Ktx2ArrayBuilder::new(8, 8, VkFormat::R8G8B8A8Unorm);
+    assert_eq!(empty.build().unwrap_err(), Error::InvalidValue);
+}
+
+#[test]
+fn test_texture_builder_configurations() {
+    let flat = Ktx2Texture::builder()
+        .width(512)
+        .height(256)
+        .format(VkFormat::R8G8B8A8Srgb)
+        .levels(10)
+        .build()
+        .unwrap();
+    assert_eq!((flat.width(), flat.height(), flat.depth()), (512, 256, 1));
+    assert_eq!(flat.levels(), 10);
+    assert!(!flat.is_cubemap() && !flat.is_array());
+    assert_eq!(unsafe { (*flat.as_raw()).numDimensions }, 2);
+
+    let cube_array = Ktx2Texture::builder()
+        .width(64)
+        .height(64)
+        .format(VkFormat::R8G8B8A8Unorm)
+        .cubemap(true)
+        .array_layers(4)
+        .build()
+        .unwrap();
+    assert!(cube_array.is_cubemap() && cube_array.is_array());
+    assert_eq!((cube_array.faces(), cube_array.layers()), (6, 4));
+
+    let volume = Ktx2Texture::builder()
+        .dimensions(32, 32)
+        .depth(16)
+        .format(VkFormat::R8Unorm)
+        .build()
+        .unwrap();
+    assert_eq!(volume.depth(), 16);
+    assert_eq!(unsafe { (*volume.as_raw()).numDimensions }, 3);
+
+    // A single row can still be declared 2D, but a volume cannot be 2D
+    let row = Ktx2Texture::builder()
+        .dimensions(64, 1)
+        .num_dimensions(2)
+        .format(VkFormat::R8Unorm)
+        .build()
+        .unwrap();
+    assert_eq!(unsafe { (*row.as_raw()).numDimensions }, 2);
+    let result = Ktx2Texture::builder()
+        .dimensions(32, 32)
+        .depth(16)
+        .num_dimensions(2)
+        .format(VkFormat::R8Unorm)
+        .build();
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
+#[test]
+fn test_texture_builder_validation() {
+    // Missing format
+    let result = Ktx2Texture::builder().dimensions(64, 64).build();
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    // Missing dimensions
+    let result = Ktx2Texture::builder()
+        .format(VkFormat::R8G8B8A8Unorm)
+        .build();
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
 // ============================================================================
 // Texture Property Tests
 // ============================================================================
@@ -543,6 +1284,33 @@ fn test_texture_compression_status() {
     assert!(!texture.needs_transcoding());
 }
 
+#[test]
+fn test_texture_image_size() {
+    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 3, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(texture.image_size(0).unwrap(), 256 * 256 * 4);
+    assert_eq!(texture.image_size(2).unwrap(), 64 * 64 * 4);
+    assert_eq!(texture.image_size(3).unwrap_err(), Error::InvalidValue);
+
+    let header =
+        Ktx2Texture::from_memory_with_flags(&sample_ktx2_bytes(), TextureCreateFlags::NONE)
+            .unwrap();
+    assert!(!header.has_image_data());
+    assert_eq!(header.image_size(1).unwrap(), 8 * 8 * 4);
+}
+
+#[test]
+fn test_texture_num_components() {
+    for (format, expected) in [
+        (VkFormat::R8Unorm, 1),
+        (VkFormat::R8G8Unorm, 2),
+        (VkFormat::R8G8B8Unorm, 3),
+        (VkFormat::R8G8B8A8Srgb, 4),
+    ] {
+        let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, format).unwrap();
+        assert_eq!(texture.num_components().unwrap(), expected, "{format:?}");
+    }
+}
+
 // ============================================================================
 // Memory-based Texture Tests
 // ============================================================================
@@ -561,54 +1329,880 @@ fn test_texture_from_invalid_memory() {
     assert!(result.is_err());
 }
 
-// ============================================================================
-// Image Data Tests
-// ============================================================================
+/// Reader that serves at most `chunk` bytes per `read` call
+struct ChunkedReader {
+    inner: Cursor<Vec<u8>>,
+    chunk: usize,
+}
 
-#[test]
-fn test_texture_set_image_data_empty() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
-    let empty_data: &[u8] = &[];
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.chunk);
+        self.inner.read(&mut buf[..len])
+    }
+}
 
-    let result = texture.set_image_data(0, 0, 0, empty_data);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+impl Seek for ChunkedReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn sample_ktx2_bytes() -> Vec<u8> {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 2, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(16, 16))
+        .unwrap();
+    texture
+        .set_image_data(1, 0, 0, &gradient_rgba(8, 8))
+        .unwrap();
+    texture.set_metadata("tool", b"reader\0").unwrap();
+    texture.write_to_memory().unwrap()
+}
+
+/// Returns a path in the temp directory unique to this test process
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("ktx2-rw-{}-{name}", std::process::id()))
 }
 
 #[test]
-fn test_texture_set_image_data_invalid_indices() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
-    let data = vec![255u8; 1024];
+fn test_texture_from_file_header_only() {
+    let path = temp_path("header-only.ktx2");
+    std::fs::write(&path, sample_ktx2_bytes()).unwrap();
 
-    // Invalid level
-    let result = texture.set_image_data(10, 0, 0, &data);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    let texture = Ktx2Texture::from_file_with_flags(&path, TextureCreateFlags::NONE).unwrap();
+    assert_eq!(texture.width(), 16);
+    assert_eq!(texture.height(), 16);
+    assert_eq!(texture.levels(), 2);
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(
+        texture.supercompression_scheme(),
+        SupercompressionScheme::None
+    );
+    assert_eq!(texture.get_metadata("tool").unwrap(), b"reader\0");
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap_err(),
+        Error::InvalidOperation
+    );
 
-    // Invalid layer
-    let result = texture.set_image_data(0, 10, 0, &data);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    let skipped = Ktx2Texture::from_file_with_flags(
+        &path,
+        TextureCreateFlags::LOAD_IMAGE_DATA | TextureCreateFlags::SKIP_KV_DATA,
+    )
+    .unwrap();
+    assert_eq!(skipped.get_metadata("tool").unwrap_err(), Error::NotFound);
+    assert_eq!(
+        skipped.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(16, 16)
+    );
 
-    // Invalid face
-    let result = texture.set_image_data(0, 0, 10, &data);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    std::fs::remove_file(&path).unwrap();
 }
 
 #[test]
-fn test_texture_get_image_data_invalid_indices() {
-    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_from_memory_with_flags() {
+    let bytes = sample_ktx2_bytes();
 
-    // Invalid level
-    let result = texture.get_image_data(10, 0, 0);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    let texture = Ktx2Texture::from_memory_with_flags(&bytes, TextureCreateFlags::NONE).unwrap();
+    assert_eq!(texture.width(), 16);
+    assert_eq!(texture.levels(), 2);
+    assert_eq!(
+        texture.get_image_data(1, 0, 0).unwrap_err(),
+        Error::InvalidOperation
+    );
 
-    // Invalid layer
-    let result = texture.get_image_data(0, 10, 0);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    let raw = Ktx2Texture::from_memory_with_flags(
+        &bytes,
+        TextureCreateFlags::LOAD_IMAGE_DATA | TextureCreateFlags::RAW_KV_DATA,
+    )
+    .unwrap();
+    assert_eq!(raw.metadata_iter().count(), 0);
+    assert_eq!(raw.get_image_data(1, 0, 0).unwrap(), gradient_rgba(8, 8));
+}
+
+#[test]
+fn test_texture_load_options() {
+    let path = temp_path("load-options.ktx2");
+    let bytes = sample_ktx2_bytes();
+    std::fs::write(&path, &bytes).unwrap();
+
+    let header_only = LoadOptions {
+        load_image_data: false,
+    };
+    let from_file = Ktx2Texture::from_file_with_options(&path, header_only).unwrap();
+    let from_memory = Ktx2Texture::from_memory_with_options(&bytes, header_only).unwrap();
+    for texture in [&from_file, &from_memory] {
+        assert_eq!((texture.width(), texture.height()), (16, 16));
+        assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+        assert_eq!(
+            texture.get_image_data(0, 0, 0).unwrap_err(),
+            Error::InvalidOperation
+        );
+    }
+
+    let full = Ktx2Texture::from_file_with_options(&path, LoadOptions::default()).unwrap();
+    assert_eq!(full.get_image_data(0, 0, 0).unwrap(), gradient_rgba(16, 16));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_texture_deferred_image_data() {
+    let bytes = sample_ktx2_bytes();
+    let path = temp_path("deferred.ktx2");
+    std::fs::write(&path, &bytes).unwrap();
+
+    let mut from_file = Ktx2Texture::from_file_with_flags(&path, TextureCreateFlags::NONE).unwrap();
+    // The caller's buffer does not need to outlive a memory open
+    let mut from_memory =
+        Ktx2Texture::from_memory_with_flags(&bytes.clone(), TextureCreateFlags::NONE).unwrap();
+
+    for texture in [&mut from_file, &mut from_memory] {
+        assert!(!texture.has_image_data());
+        texture.load_image_data().unwrap();
+        assert!(texture.has_image_data());
+        assert_eq!(
+            texture.get_image_data(0, 0, 0).unwrap(),
+            gradient_rgba(16, 16)
+        );
+        assert_eq!(
+            texture.get_image_data(1, 0, 0).unwrap(),
+            gradient_rgba(8, 8)
+        );
+
+        // A second call is a no-op
+        texture.load_image_data().unwrap();
+        assert_eq!(
+            texture.get_image_data(1, 0, 0).unwrap(),
+            gradient_rgba(8, 8)
+        );
+    }
+
+    let loaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert!(loaded.has_image_data());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_texture_file_non_ascii_path() {
+    let path = temp_path("テクスチャ-ü.ktx2");
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+
+    texture.write_to_file(&path).unwrap();
+    let reloaded = Ktx2Texture::from_file(&path).unwrap();
+    assert!(reloaded.content_eq(&texture));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_texture_write_to_file_atomic() {
+    let dir = temp_path("atomic");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("texture.ktx2");
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+
+    texture.write_to_file_atomic(&path).unwrap();
+    let original = std::fs::read(&path).unwrap();
+    assert!(Ktx2Texture::from_file(&path).unwrap().content_eq(&texture));
+
+    // Fail halfway through a rewrite: the old file stays and the temp file goes
+    let result = write_atomically(&path, |file| {
+        file.write_all(&original[..original.len() / 2])?;
+        Err(Error::FileWriteError)
+    });
+    assert_eq!(result.unwrap_err(), Error::FileWriteError);
+    assert_eq!(std::fs::read(&path).unwrap(), original);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    // The KTXwriter entry goes into the file only, not into the texture
+    let fresh = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    fresh.write_to_file_atomic(&path).unwrap();
+    assert_eq!(fresh.writer(), Ok(None));
+    assert!(Ktx2Texture::from_file(&path)
+        .unwrap()
+        .writer()
+        .unwrap()
+        .is_some_and(|w| w.starts_with("ktx2-rw")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_texture_file_non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::env::temp_dir().join(OsStr::from_bytes(b"ktx2-rw-\xff\xfe.ktx2"));
+    assert!(path.to_str().is_none());
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+
+    texture.write_to_file(&path).unwrap();
+    let reloaded = Ktx2Texture::from_file(&path).unwrap();
+    assert!(reloaded.content_eq(&texture));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_texture_from_vec_uses_buffer_in_place() {
+    let bytes = sample_ktx2_bytes();
+    let expected = Ktx2Texture::from_memory(&bytes).unwrap();
+    let range = bytes.as_ptr_range();
+
+    let mut texture = Ktx2Texture::from_vec(bytes).unwrap();
+    assert!(texture.has_image_data());
+    for (level, size) in [(0, 16), (1, 8)] {
+        let image = texture.get_image_data(level, 0, 0).unwrap();
+        assert!(range.contains(&image.as_ptr()));
+        assert!(image.as_ptr_range().end <= range.end);
+        assert_eq!(image, gradient_rgba(size, size));
+    }
+    assert!(texture.content_eq(&expected));
+
+    // Replacing the data moves it out of the buffer first
+    texture.deflate_zstd(3).unwrap();
+    assert_eq!(
+        texture.supercompression_scheme(),
+        SupercompressionScheme::Zstd
+    );
+    assert!(texture.content_eq(&expected));
+}
+
+#[test]
+fn test_texture_new_from_raw_level_data() {
+    // R8 levels of 5x3, 2x1 and 1x1 exercise libktx's level padding
+    let mut expected = Ktx2Texture::create(5, 3, 1, 2, 1, 3, VkFormat::R8Unorm).unwrap();
+    for level in 0..3 {
+        for layer in 0..2 {
+            let size = expected.image_size(level).unwrap();
+            let fill = (level * 2 + layer + 1) as u8;
+            expected
+                .set_image_data(level, layer, 0, &vec![fill; size])
+                .unwrap();
+        }
+    }
+    let data = expected.raw_data().unwrap().to_vec();
+    let range = data.as_ptr_range();
+
+    let texture =
+        Ktx2Texture::new_from_raw_level_data(5, 3, 1, 2, 1, 3, VkFormat::R8Unorm, data).unwrap();
+    assert_eq!(texture.data_size(), expected.data_size());
+    for level in 0..3 {
+        for layer in 0..2 {
+            let image = texture.get_image_data(level, layer, 0).unwrap();
+            assert!(range.contains(&image.as_ptr()));
+            assert_eq!(image, expected.get_image_data(level, layer, 0).unwrap());
+        }
+    }
+    assert!(texture.content_eq(&expected));
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert!(reloaded.content_eq(&expected));
+
+    let short = vec![0; expected.data_size() - 1];
+    assert_eq!(
+        Ktx2Texture::new_from_raw_level_data(5, 3, 1, 2, 1, 3, VkFormat::R8Unorm, short)
+            .unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: expected.data_size(),
+            actual: expected.data_size() - 1
+        }
+    );
+}
+
+#[test]
+fn test_texture_from_vec_supercompressed() {
+    let mut texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    texture.deflate_zstd(3).unwrap();
+    let bytes = texture.write_to_memory().unwrap();
+
+    let loaded = Ktx2Texture::from_vec(bytes).unwrap();
+    assert!(loaded.has_image_data());
+    assert_eq!(loaded.get_image_data(1, 0, 0).unwrap(), gradient_rgba(8, 8));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_texture_from_file_mmap() {
+    let path = temp_path("mmap.ktx2");
+    std::fs::write(&path, sample_ktx2_bytes()).unwrap();
+
+    let mapped = Ktx2Texture::from_file_mmap(&path).unwrap();
+    let read = Ktx2Texture::from_file(&path).unwrap();
+    assert!(mapped.content_eq(&read));
+    assert_eq!(mapped.write_to_memory(), read.write_to_memory());
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_texture_view() {
+    let bytes = sample_ktx2_bytes();
+    let view = Ktx2TextureView::from_memory(&bytes).unwrap();
+
+    assert_eq!((view.width(), view.height(), view.depth()), (16, 16, 1));
+    assert_eq!((view.layers(), view.faces(), view.levels()), (1, 1, 2));
+    assert_eq!(view.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert!(!view.needs_transcoding());
+    assert_eq!(view.supercompression_scheme(), SupercompressionScheme::None);
+    assert_eq!(view.get_metadata_str("tool").unwrap(), "reader");
+
+    let owned = view.to_owned().unwrap();
+    assert!(owned.has_image_data());
+    assert_eq!(owned.get_image_data(1, 0, 0).unwrap(), gradient_rgba(8, 8));
+
+    // The owned texture is independent of the source bytes
+    drop(view);
+    drop(bytes);
+    assert_eq!(owned.width(), 16);
+}
+
+#[test]
+fn test_header_only_transcode_to_fails() {
+    let bytes = sample_ktx2_bytes();
+    let texture = Ktx2Texture::from_memory_with_flags(&bytes, TextureCreateFlags::NONE).unwrap();
+    assert_eq!(
+        texture.transcode_to(TranscodeFormat::Rgba32).unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_image_round_trip() {
+    use image::{DynamicImage, GrayImage, RgbaImage};
+
+    let rgba = RgbaImage::from_raw(8, 4, gradient_rgba(8, 4)).unwrap();
+    let texture = Ktx2Texture::from_image(&DynamicImage::ImageRgba8(rgba.clone())).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(
+        (texture.width(), texture.height(), texture.levels()),
+        (8, 4, 1)
+    );
+    assert_eq!(
+        texture.to_image(0, 0, 0).unwrap(),
+        DynamicImage::ImageRgba8(rgba)
+    );
+
+    let gray = GrayImage::from_fn(5, 3, |x, y| image::Luma([(x * 10 + y) as u8]));
+    let texture = Ktx2Texture::from_image(&DynamicImage::ImageLuma8(gray.clone())).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8Unorm);
+    assert_eq!(
+        texture.to_image(0, 0, 0).unwrap(),
+        DynamicImage::ImageLuma8(gray)
+    );
+
+    // Color types without a direct mapping are widened to RGBA8
+    let rgb16 = DynamicImage::new_rgb16(2, 2);
+    let texture = Ktx2Texture::from_image(&rgb16).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+
+    assert_eq!(texture.to_image(1, 0, 0).unwrap_err(), Error::InvalidValue);
+}
+
+#[cfg(feature = "encoders-input")]
+#[test]
+fn test_texture_from_image_bytes() {
+    use image::{GrayImage, ImageFormat, RgbImage};
+
+    let encode = |image: image::DynamicImage| {
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, ImageFormat::Png).unwrap();
+        png.into_inner()
+    };
+
+    let rgb = RgbImage::from_fn(6, 3, |x, y| image::Rgb([x as u8 * 40, y as u8 * 80, 7]));
+    let png = encode(rgb.clone().into());
+    let texture = Ktx2Texture::from_image_bytes(&png, true).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Srgb);
+    assert_eq!((texture.width(), texture.height()), (6, 3));
+    let pixels = texture.get_image_data(0, 0, 0).unwrap();
+    assert_eq!(&pixels[4 * 7..4 * 8], &[40, 80, 7, 255]);
+
+    let gray = GrayImage::from_fn(4, 4, |x, y| image::Luma([(x + y * 4) as u8]));
+    let png = encode(gray.clone().into());
+    let texture = Ktx2Texture::from_image_bytes(&png, false).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8Unorm);
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        gray.as_raw().as_slice()
+    );
+    let texture = Ktx2Texture::from_image_bytes(&png, true).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Srgb);
+
+    let path = temp_path("from-image-file.png");
+    std::fs::write(&path, &png).unwrap();
+    let texture = Ktx2Texture::from_image_file(&path, false).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8Unorm);
+
+    match Ktx2Texture::from_image_bytes(b"not an image", false) {
+        Err(Error::ImageDecode(message)) => assert!(!message.is_empty()),
+        other => panic!("expected a decode error, got {other:?}"),
+    }
+    assert!(matches!(
+        Ktx2Texture::from_image_file(temp_path("missing.png"), false),
+        Err(Error::Io { .. })
+    ));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_from_dynamic_image() {
+    use image::{DynamicImage, Rgb, RgbImage};
+
+    // Deliberately not a multiple of the 4x4 block size
+    let rgb = RgbImage::from_fn(13, 7, |x, y| Rgb([(x * 19) as u8, (y * 36) as u8, 200]));
+    let texture = Ktx2Texture::from_dynamic_image(&DynamicImage::ImageRgb8(rgb), true).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Srgb);
+    assert_eq!((texture.width(), texture.height()), (13, 7));
+
+    let pixels = texture.decode_rgba8(0, 0, 0).unwrap();
+    assert_eq!(pixels.len(), 13 * 7 * 4);
+    for (x, y) in [(0, 0), (12, 0), (5, 3), (12, 6)] {
+        let offset = ((y * 13 + x) * 4) as usize;
+        assert_eq!(
+            pixels[offset..offset + 4],
+            [(x * 19) as u8, (y * 36) as u8, 200, 255]
+        );
+    }
+
+    let linear = Ktx2Texture::from_dynamic_image(&DynamicImage::new_luma8(3, 5), false).unwrap();
+    assert_eq!(linear.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!((linear.width(), linear.height()), (3, 5));
+
+    let hdr = Ktx2Texture::from_dynamic_image(&DynamicImage::new_rgb32f(6, 2), true).unwrap();
+    assert_eq!(hdr.vk_format(), VkFormat::R32G32B32A32Sfloat);
+    assert_eq!(hdr.image_size(0).unwrap(), 6 * 2 * 16);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_to_rgba_image_after_compression() {
+    use image::{DynamicImage, RgbaImage};
+
+    let source = RgbaImage::from_raw(64, 64, gradient_rgba(64, 64)).unwrap();
+    let mut texture =
+        Ktx2Texture::from_dynamic_image(&DynamicImage::ImageRgba8(source.clone()), false).unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    let decoded = texture.to_rgba_image(0, 0, 0).unwrap();
+    assert_eq!(decoded.dimensions(), (64, 64));
+
+    let squared_error: f64 = source
+        .as_raw()
+        .iter()
+        .zip(decoded.as_raw())
+        .map(|(&a, &b)| (f64::from(a) - f64::from(b)).powi(2))
+        .sum();
+    let mse = squared_error / source.as_raw().len() as f64;
+    let psnr = 10.0 * (255.0f64.powi(2) / mse.max(f64::EPSILON)).log10();
+    assert!(psnr > 30.0, "PSNR {psnr:.1} dB");
+
+    assert_eq!(
+        texture.to_rgba_image(1, 0, 0).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_from_mip_chain() {
+    use image::RgbaImage;
+
+    let chain: Vec<RgbaImage> = [(8, 4), (4, 2), (2, 1)]
+        .into_iter()
+        .map(|(w, h)| RgbaImage::from_raw(w, h, gradient_rgba(w, h)).unwrap())
+        .collect();
+    let levels: Vec<&RgbaImage> = chain.iter().collect();
+
+    let texture = Ktx2Texture::from_mip_chain(&levels).unwrap();
+    assert_eq!(texture.levels(), 3);
+    for (level, image) in chain.iter().enumerate() {
+        let level = level as u32;
+        let decoded = texture.to_image(level, 0, 0).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), image.dimensions());
+        assert_eq!(texture.get_image_data(level, 0, 0).unwrap(), image.as_raw());
+    }
+
+    let single = Ktx2Texture::try_from(&chain[0]).unwrap();
+    assert_eq!(single.levels(), 1);
+
+    assert_eq!(
+        Ktx2Texture::from_mip_chain(&[&chain[0], &chain[2]]).unwrap_err(),
+        Error::InvalidValue
+    );
+    assert_eq!(
+        Ktx2Texture::from_mip_chain(&[]).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_texture_from_reader() {
+    let bytes = sample_ktx2_bytes();
+    let expected = Ktx2Texture::from_memory(&bytes).unwrap();
+
+    let from_cursor = Ktx2Texture::from_reader(Cursor::new(bytes.clone())).unwrap();
+    assert!(from_cursor.content_eq(&expected));
+
+    let chunked = ChunkedReader {
+        inner: Cursor::new(bytes),
+        chunk: 7,
+    };
+    let from_chunks = Ktx2Texture::from_reader(chunked).unwrap();
+    assert!(from_chunks.content_eq(&expected));
+}
+
+#[test]
+fn test_texture_from_reader_truncated() {
+    let mut bytes = sample_ktx2_bytes();
+    bytes.truncate(bytes.len() / 2);
+    assert!(Ktx2Texture::from_reader(Cursor::new(bytes)).is_err());
+}
+
+#[test]
+fn test_texture_from_reader_panic() {
+    struct PanickingReader;
+
+    impl Read for PanickingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("reader failure");
+        }
+    }
+
+    impl Seek for PanickingReader {
+        fn seek(&mut self, _pos: SeekFrom) -> io::Result<u64> {
+            Ok(0)
+        }
+    }
+
+    let result = std::panic::catch_unwind(|| Ktx2Texture::from_reader(PanickingReader));
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Image Data Tests
+// ============================================================================
+
+#[test]
+fn test_texture_set_image_data_empty() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let empty_data: &[u8] = &[];
+
+    let result = texture.set_image_data(0, 0, 0, empty_data);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
+#[test]
+fn test_texture_set_image_data_invalid_indices() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let data = vec![255u8; 1024];
+
+    // Invalid level
+    let result = texture.set_image_data(10, 0, 0, &data);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    // Invalid layer
+    let result = texture.set_image_data(0, 10, 0, &data);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    // Invalid face
+    let result = texture.set_image_data(0, 0, 10, &data);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
+#[test]
+fn test_texture_from_rgba8() {
+    let texture = Ktx2Texture::from_rgba8(16, 8, &gradient_rgba(16, 8)).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(
+        (texture.width(), texture.height(), texture.levels()),
+        (16, 8, 1)
+    );
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(16, 8)
+    );
+
+    let srgb = Ktx2Texture::from_rgba8_srgb(16, 8, &gradient_rgba(16, 8)).unwrap();
+    assert_eq!(srgb.vk_format(), VkFormat::R8G8B8A8Srgb);
+
+    assert_eq!(
+        Ktx2Texture::from_rgba8(16, 8, &gradient_rgba(8, 8)).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 512,
+            actual: 256
+        }
+    );
+
+    let gray = Ktx2Texture::from_pixels(4, 4, VkFormat::R8Unorm, &[7; 16]).unwrap();
+    assert_eq!(gray.get_image_data(0, 0, 0).unwrap(), [7; 16]);
+    assert_eq!(
+        Ktx2Texture::from_pixels(4, 4, VkFormat::R8Unorm, &[7; 64]).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 16,
+            actual: 64
+        }
+    );
+}
+
+#[test]
+fn test_texture_from_rgba_float() {
+    let texels = [
+        1.5f32,
+        -0.0,
+        f32::MAX,
+        f32::from_bits(1),
+        65504.0,
+        -f32::MIN_POSITIVE,
+        0.1,
+        f32::INFINITY,
+    ];
+    let texture = Ktx2Texture::from_rgba_f32(2, 1, &texels).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R32G32B32A32Sfloat);
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    let stored: Vec<u32> = reloaded
+        .get_image_data(0, 0, 0)
+        .unwrap()
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    let expected: Vec<u32> = texels.iter().map(|v| v.to_bits()).collect();
+    assert_eq!(stored, expected);
+
+    // 1.0, -2.0, 65504 (largest half), smallest subnormal half
+    let halves = [0x3C00u16, 0xC000, 0x7BFF, 0x0001];
+    let texture = Ktx2Texture::from_rgba_f16(1, 1, &halves).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R16G16B16A16Sfloat);
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    let stored: Vec<u16> = reloaded
+        .get_image_data(0, 0, 0)
+        .unwrap()
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(stored, halves);
+
+    assert_eq!(
+        Ktx2Texture::from_rgba_f32(2, 2, &texels).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 64,
+            actual: 32
+        }
+    );
+
+    let mut texture = Ktx2Texture::from_rgba_f32(2, 1, &texels).unwrap();
+    assert_eq!(
+        texture.compress_basis_simple(128).unwrap_err(),
+        Error::UnsupportedFormat(VkFormat::R32G32B32A32Sfloat)
+    );
+    assert!(!texture.needs_transcoding());
+    let mut texture = Ktx2Texture::from_rgba_f16(1, 1, &halves).unwrap();
+    assert_eq!(
+        texture.compress_basis_simple(128).unwrap_err(),
+        Error::UnsupportedFormat(VkFormat::R16G16B16A16Sfloat)
+    );
+    assert!(!texture.needs_transcoding());
+}
+
+#[cfg(feature = "hdr")]
+#[test]
+fn test_texture_from_hdr_bytes() {
+    use image::{ImageFormat, Rgb32FImage};
+
+    let hdr = Rgb32FImage::from_fn(4, 2, |x, y| image::Rgb([x as f32 * 2.0, y as f32, 0.5]));
+    let mut encoded = Cursor::new(Vec::new());
+    image::DynamicImage::from(hdr)
+        .write_to(&mut encoded, ImageFormat::Hdr)
+        .unwrap();
+
+    let texture = Ktx2Texture::from_hdr_bytes(encoded.get_ref()).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R32G32B32A32Sfloat);
+    assert_eq!((texture.width(), texture.height()), (4, 2));
+    let texel: Vec<f32> = texture.get_image_data(0, 0, 0).unwrap()[16 * 7..16 * 8]
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    // RGBE keeps these values exactly
+    assert_eq!(texel, [6.0, 1.0, 0.5, 1.0]);
+
+    let mut png = Cursor::new(Vec::new());
+    image::DynamicImage::new_rgb8(2, 2)
+        .write_to(&mut png, ImageFormat::Png)
+        .unwrap();
+    assert_eq!(
+        Ktx2Texture::from_hdr_bytes(png.get_ref()).unwrap_err(),
+        Error::UnsupportedFeature
+    );
+}
+
+#[test]
+fn test_texture_set_image_data_size_mismatch() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 2, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let short = vec![0u8; 16 * 16 * 4 - 1];
+    assert_eq!(
+        texture.set_image_data(0, 0, 0, &short).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 1024,
+            actual: 1023
+        }
+    );
+
+    // A full base level is too long for the next one
+    let base = gradient_rgba(16, 16);
+    assert_eq!(
+        texture.set_image_data(1, 0, 0, &base).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 256,
+            actual: 1024
+        }
+    );
+    assert!(texture.set_image_data(0, 0, 0, &base).is_ok());
+}
+
+#[test]
+fn test_extract_levels() {
+    let mut texture = Ktx2Texture::create(32, 32, 1, 1, 1, 6, VkFormat::R8G8B8A8Unorm).unwrap();
+    for level in 0..6 {
+        let size = 32 >> level;
+        texture
+            .set_image_data(level, 0, 0, &gradient_rgba(size, size))
+            .unwrap();
+    }
+    texture.set_metadata("tool", b"extract\0").unwrap();
+
+    let extracted = texture.extract_levels(2..4).unwrap();
+    assert_eq!(extracted.levels(), 2);
+    assert_eq!((extracted.width(), extracted.height()), (8, 8));
+    assert_eq!(extracted.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(
+        extracted.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(8, 8)
+    );
+    assert_eq!(
+        extracted.get_image_data(1, 0, 0).unwrap(),
+        gradient_rgba(4, 4)
+    );
+    assert_eq!(extracted.get_metadata("tool").unwrap(), b"extract\0");
+
+    for range in [3..3, 4..7] {
+        assert_eq!(
+            texture.extract_levels(range).unwrap_err(),
+            Error::InvalidValue
+        );
+    }
+}
+
+#[test]
+fn test_level_dimensions() {
+    let texture = Ktx2Texture::create(64, 16, 4, 1, 1, 7, VkFormat::R8Unorm).unwrap();
+    assert_eq!(texture.level_dimensions(0).unwrap(), (64, 16, 4));
+    assert_eq!(texture.level_dimensions(3).unwrap(), (8, 2, 1));
+    assert_eq!(texture.level_dimensions(6).unwrap(), (1, 1, 1));
+    assert_eq!(
+        texture.level_dimensions(7).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_texture_raw_data() {
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    let raw = texture.raw_data().unwrap();
+    assert_eq!(raw.len(), texture.data_size());
+    assert_eq!(raw.len(), 16 * 16 * 4 + 8 * 8 * 4);
+
+    // Every image lies inside the slice
+    for level in 0..2 {
+        let image = texture.get_image_data(level, 0, 0).unwrap().as_ptr_range();
+        assert!(raw.as_ptr_range().contains(&image.start));
+        assert!(image.end <= raw.as_ptr_range().end);
+    }
+
+    let header =
+        Ktx2Texture::from_memory_with_flags(&sample_ktx2_bytes(), TextureCreateFlags::NONE)
+            .unwrap();
+    assert!(header.raw_data().is_none());
+}
+
+#[test]
+fn test_texture_clear_image() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 1, 1, 2, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.clear_image(1, 0, 0, 0xFF).unwrap();
+
+    let cleared = texture.get_image_data(1, 0, 0).unwrap();
+    assert_eq!(cleared.len(), 4 * 4 * 4);
+    assert!(cleared.iter().all(|&b| b == 0xFF));
+    assert!(texture
+        .get_image_data(0, 0, 0)
+        .unwrap()
+        .iter()
+        .all(|&b| b == 0));
+
+    assert_eq!(
+        texture.clear_image(2, 0, 0, 0).unwrap_err(),
+        Error::InvalidValue
+    );
+
+    let mut compressed = Ktx2Texture::create(8, 8, 1, 1, 1, 1, VkFormat::Bc1RgbUnormBlock).unwrap();
+    assert_eq!(
+        compressed.clear_image(0, 0, 0, 0).unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_texture_clear_image_3d() {
+    let mut texture = Ktx2Texture::create(4, 4, 4, 1, 1, 2, VkFormat::R8Unorm).unwrap();
+    texture.clear_image(0, 0, 3, 0xAB).unwrap();
+    texture.clear_image(1, 0, 1, 0xCD).unwrap();
+
+    assert!(texture
+        .get_image_data(0, 0, 3)
+        .unwrap()
+        .iter()
+        .all(|&b| b == 0xAB));
+    assert!(texture
+        .get_image_data(1, 0, 1)
+        .unwrap()
+        .iter()
+        .all(|&b| b == 0xCD));
+    assert!(texture
+        .get_image_data(0, 0, 2)
+        .unwrap()
+        .iter()
+        .all(|&b| b == 0));
+
+    assert_eq!(
+        texture.clear_image(1, 0, 2, 0).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_texture_get_image_data_invalid_indices() {
+    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    // Invalid level
+    let result = texture.get_image_data(10, 0, 0);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    // Invalid layer
+    let result = texture.get_image_data(0, 10, 0);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
 
     // Invalid face
     let result = texture.get_image_data(0, 0, 10);
@@ -616,110 +2210,1547 @@ fn test_texture_get_image_data_invalid_indices() {
     assert_eq!(result.unwrap_err(), Error::InvalidValue);
 }
 
+#[test]
+fn test_get_image_data_mut() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let original = gradient_rgba(8, 8);
+    texture.set_image_data(0, 0, 0, &original).unwrap();
+
+    for pixel in texture
+        .get_image_data_mut(0, 0, 0)
+        .unwrap()
+        .chunks_exact_mut(4)
+    {
+        pixel[0] = 255 - pixel[0];
+    }
+
+    let data = texture.get_image_data(0, 0, 0).unwrap();
+    for (pixel, source) in data.chunks_exact(4).zip(original.chunks_exact(4)) {
+        assert_eq!(pixel[0], 255 - source[0]);
+        assert_eq!(&pixel[1..], &source[1..]);
+    }
+
+    assert_eq!(
+        texture.get_image_data_mut(1, 0, 0).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_get_image_data_mut_compressed() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(16, 16))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    assert_eq!(
+        texture.get_image_data_mut(0, 0, 0).unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_iter_images() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 2, 6, 3, VkFormat::R8Unorm).unwrap();
+    for (level, size) in [(0u32, 64usize), (1, 16), (2, 4)] {
+        for layer in 0..2 {
+            for face in 0..6 {
+                let value = (level * 100 + layer * 10 + face) as u8;
+                texture
+                    .set_image_data(level, layer, face, &vec![value; size])
+                    .unwrap();
+            }
+        }
+    }
+
+    let images: Vec<_> = texture.iter_images().unwrap().collect();
+    assert_eq!(
+        images.len() as u32,
+        texture.levels() * texture.layers() * texture.faces()
+    );
+
+    let mut expected = Vec::new();
+    for level in 0..3 {
+        for layer in 0..2 {
+            for face in 0..6 {
+                expected.push((level, layer, face));
+            }
+        }
+    }
+    let order: Vec<_> = images
+        .iter()
+        .map(|image| (image.level, image.layer, image.face))
+        .collect();
+    assert_eq!(order, expected);
+
+    for image in &images {
+        let value = (image.level * 100 + image.layer * 10 + image.face) as u8;
+        assert!(image.data.iter().all(|&byte| byte == value));
+        assert_eq!(image.data.len(), 64 >> (2 * image.level));
+    }
+}
+
+#[test]
+fn test_iter_images_3d() {
+    let mut texture = Ktx2Texture::create(8, 8, 4, 1, 1, 3, VkFormat::R8Unorm).unwrap();
+    for (level, slices) in [(0u32, 4u32), (1, 2), (2, 1)] {
+        for slice in 0..slices {
+            texture
+                .get_image_data_mut(level, 0, slice)
+                .unwrap()
+                .fill((level * 10 + slice) as u8);
+        }
+    }
+
+    let images: Vec<_> = texture.iter_images().unwrap().collect();
+    assert_eq!(images.len(), 4 + 2 + 1);
+    let order: Vec<_> = images
+        .iter()
+        .map(|image| (image.level, image.face))
+        .collect();
+    assert_eq!(
+        order,
+        [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (2, 0)]
+    );
+    for image in &images {
+        let value = (image.level * 10 + image.face) as u8;
+        assert!(image.data.iter().all(|&byte| byte == value));
+    }
+
+    assert_eq!(
+        texture.get_image_data(1, 0, 2).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_iter_images_without_image_data() {
+    let texture =
+        Ktx2Texture::from_memory_with_flags(&sample_ktx2_bytes(), TextureCreateFlags::NONE)
+            .unwrap();
+    assert_eq!(texture.iter_images().err(), Some(Error::InvalidOperation));
+}
+
+#[test]
+fn test_set_cube_face() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 6, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    for face in CubeFace::ALL {
+        let color = [face.index() as u8 * 40, 0, 0, 255];
+        texture
+            .set_cube_face(0, 0, face, &color.repeat(16))
+            .unwrap();
+    }
+
+    for (index, face) in [
+        CubeFace::PosX,
+        CubeFace::NegX,
+        CubeFace::PosY,
+        CubeFace::NegY,
+        CubeFace::PosZ,
+        CubeFace::NegZ,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        assert_eq!(face.index(), index as u32);
+        assert_eq!(CubeFace::from_index(index as u32), Some(face));
+        let data = texture.get_image_data(0, 0, index as u32).unwrap();
+        assert_eq!(data[..4], [index as u8 * 40, 0, 0, 255]);
+    }
+    assert_eq!(CubeFace::from_index(6), None);
+
+    let mut flat = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(
+        flat.set_cube_face(0, 0, CubeFace::PosX, &[0; 64])
+            .unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_face_data_by_name() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 2, 6, 1, VkFormat::R8Unorm).unwrap();
+    for face in CubeFace::ALL {
+        let value = 10 + face.index() as u8;
+        texture.set_face_data(0, 1, face, &[value; 16]).unwrap();
+    }
+
+    for (index, face) in CubeFace::ALL.into_iter().enumerate() {
+        let raw = texture.get_image_data(0, 1, index as u32).unwrap();
+        assert_eq!(raw, [10 + index as u8; 16]);
+        assert_eq!(texture.get_face_data(0, 1, face).unwrap(), raw);
+    }
+    assert_eq!(
+        texture.get_face_data(0, 0, CubeFace::NegZ).unwrap(),
+        [0; 16]
+    );
+
+    let mut flat = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    assert_eq!(
+        flat.get_face_data(0, 0, CubeFace::PosX).unwrap_err(),
+        Error::InvalidOperation
+    );
+    assert_eq!(
+        flat.set_face_data(0, 0, CubeFace::PosX, &[0; 16])
+            .unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_cubemap_from_faces() {
+    let colors: Vec<Vec<u8>> = (0..6u8)
+        .map(|i| [i * 40, 255 - i * 40, i, 255].repeat(8 * 8))
+        .collect();
+    let faces: [&[u8]; 6] = std::array::from_fn(|i| colors[i].as_slice());
+
+    let texture = Ktx2Texture::cubemap_from_faces(8, VkFormat::R8G8B8A8Unorm, faces).unwrap();
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert!(reloaded.is_cubemap());
+    for (index, face) in CubeFace::ALL.into_iter().enumerate() {
+        assert_eq!(reloaded.get_face_data(0, 0, face).unwrap(), colors[index]);
+    }
+
+    let mipmapped =
+        Ktx2Texture::cubemap_from_faces_with_levels(8, 4, VkFormat::R8G8B8A8Unorm, faces).unwrap();
+    assert_eq!(mipmapped.levels(), 4);
+    assert_eq!(
+        mipmapped.get_face_data(0, 0, CubeFace::NegZ).unwrap(),
+        colors[5]
+    );
+
+    let mut short = faces;
+    short[3] = &colors[3][..16];
+    assert_eq!(
+        Ktx2Texture::cubemap_from_faces(8, VkFormat::R8G8B8A8Unorm, short).unwrap_err(),
+        Error::ImageSizeMismatch {
+            expected: 256,
+            actual: 16
+        }
+    );
+}
+
+#[test]
+fn test_swizzle_channels_bgra_to_rgba() {
+    let mut texture = Ktx2Texture::create(2, 2, 1, 1, 1, 2, VkFormat::B8G8R8A8Unorm).unwrap();
+    let bgra = [10u8, 20, 30, 40].repeat(4);
+    texture.set_image_data(0, 0, 0, &bgra).unwrap();
+    texture.set_image_data(1, 0, 0, &bgra[..4]).unwrap();
+
+    texture.swizzle_channels([2, 1, 0, 3]).unwrap();
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        [30u8, 20, 10, 40].repeat(4)
+    );
+    assert_eq!(texture.get_image_data(1, 0, 0).unwrap(), [30, 20, 10, 40]);
+
+    assert_eq!(
+        texture.swizzle_channels([0, 1, 2, 4]).unwrap_err(),
+        Error::InvalidValue
+    );
+
+    let mut rg = Ktx2Texture::create(2, 2, 1, 1, 1, 1, VkFormat::R8G8Unorm).unwrap();
+    assert_eq!(
+        rg.swizzle_channels([1, 0, 2, 3]).unwrap_err(),
+        Error::UnsupportedFormat(VkFormat::R8G8Unorm)
+    );
+}
+
+#[test]
+fn test_swizzle_channels_3d() {
+    let mut texture = Ktx2Texture::create(2, 2, 2, 1, 1, 2, VkFormat::B8G8R8A8Unorm).unwrap();
+    for (level, slice, texels) in [(0, 0, 4), (0, 1, 4), (1, 0, 1)] {
+        texture
+            .get_image_data_mut(level, 0, slice)
+            .unwrap()
+            .copy_from_slice(&[10u8, 20, 30, 40].repeat(texels));
+    }
+
+    texture.swizzle_channels([2, 1, 0, 3]).unwrap();
+    for image in texture.iter_images().unwrap() {
+        assert_eq!(
+            image.data,
+            [30u8, 20, 10, 40].repeat(image.data.len() / 4),
+            "level {} slice {}",
+            image.level,
+            image.face
+        );
+    }
+}
+
+#[test]
+fn test_reinterpret_format() {
+    let mut texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    let before = texture.get_image_data(0, 0, 0).unwrap().to_vec();
+
+    texture.reinterpret_format(VkFormat::R8G8B8A8Srgb).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Srgb);
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap(), before);
+
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert_eq!(reloaded.vk_format(), VkFormat::R8G8B8A8Srgb);
+    assert_eq!(
+        unsafe { ktxTexture2_GetOETF(reloaded.as_raw()) },
+        _khr_df_transfer_e_KHR_DF_TRANSFER_SRGB
+    );
+
+    // Same block size, but not an sRGB/UNORM counterpart
+    for format in [
+        VkFormat::B8G8R8A8Unorm,
+        VkFormat::R8G8B8A8Uint,
+        VkFormat::R32Sfloat,
+    ] {
+        assert_eq!(
+            texture.reinterpret_format(format).unwrap_err(),
+            Error::InvalidValue
+        );
+    }
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Srgb);
+
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+    assert_eq!(
+        texture
+            .reinterpret_format(VkFormat::R8G8B8A8Srgb)
+            .unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_transfer_function() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Srgb).unwrap();
+    assert_eq!(texture.transfer_function(), TransferFunction::Srgb);
+    assert!(texture.is_srgb());
+
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Undefined);
+    assert!(texture.is_srgb());
+
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert!(reloaded.is_srgb());
+    assert_eq!(
+        texture
+            .set_transfer_function(TransferFunction::Linear)
+            .unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_set_transfer_function() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Srgb).unwrap();
+    texture
+        .set_transfer_function(TransferFunction::Linear)
+        .unwrap();
+    assert_eq!(texture.transfer_function(), TransferFunction::Linear);
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+
+    // Formats without an sRGB pair keep their label
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    assert!(!texture.is_srgb());
+    texture
+        .set_transfer_function(TransferFunction::Srgb)
+        .unwrap();
+    assert!(texture.is_srgb());
+    assert_eq!(texture.vk_format(), VkFormat::R8Unorm);
+
+    assert_eq!(
+        texture
+            .set_transfer_function(TransferFunction::Unknown)
+            .unwrap_err(),
+        Error::InvalidValue
+    );
+
+    // Block-compressed formats change through their sRGB/UNORM pair only
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::Bc7UnormBlock).unwrap();
+    texture
+        .set_transfer_function(TransferFunction::Srgb)
+        .unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Bc7SrgbBlock);
+    assert!(texture.is_srgb());
+
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::Bc4UnormBlock).unwrap();
+    assert_eq!(
+        texture
+            .set_transfer_function(TransferFunction::Srgb)
+            .unwrap_err(),
+        Error::InvalidOperation
+    );
+    assert!(!texture.is_srgb());
+}
+
+#[test]
+fn test_premultiplied_alpha() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert!(!texture.is_premultiplied());
+    texture.set_premultiplied(true).unwrap();
+    assert!(texture.is_premultiplied());
+
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+    assert!(texture.is_premultiplied());
+    assert_eq!(
+        texture.set_premultiplied(false).unwrap_err(),
+        Error::InvalidOperation
+    );
+
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert!(reloaded.is_premultiplied());
+
+    let mut straight = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    straight.set_premultiplied(true).unwrap();
+    straight.set_premultiplied(false).unwrap();
+    assert!(!straight.is_premultiplied());
+}
+
+#[test]
+fn test_dfd_round_trip() {
+    let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8Unorm).unwrap();
+    let mut dfd = texture.dfd().unwrap();
+    assert_eq!(dfd.texel_block_dimensions, [1, 1, 1, 1]);
+    assert_eq!(dfd.bytes_planes, [2, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(dfd.samples.len(), 2);
+    assert_eq!(dfd.samples[1].bit_offset, 8);
+    assert_eq!(dfd.samples[1].bit_length, 8);
+
+    let words = dfd.to_words().unwrap();
+    assert_eq!(DataFormatDescriptor::from_words(&words).unwrap(), dfd);
+
+    // Tag the second channel as alpha instead of green
+    dfd.samples[1].channel_id = 15;
+    let custom = Ktx2Texture::create_with_dfd(4, 4, 1, 1, 1, 1, VkFormat::R8G8Unorm, &dfd).unwrap();
+    assert_eq!(custom.dfd().unwrap(), dfd);
+    assert_eq!(custom.image_size(0).unwrap(), 4 * 4 * 2);
+
+    dfd.samples[0].bit_length = 0;
+    assert_eq!(dfd.to_words().unwrap_err(), Error::InvalidValue);
+    assert_eq!(
+        DataFormatDescriptor::from_words(&words[..3]).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_flip_y() {
+    let mut texture = Ktx2Texture::create(2, 3, 1, 1, 1, 2, VkFormat::R8G8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &[1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3])
+        .unwrap();
+    texture.set_image_data(1, 0, 0, &[7, 8]).unwrap();
+
+    texture.flip_y().unwrap();
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        [3, 3, 3, 3, 2, 2, 2, 2, 1, 1, 1, 1]
+    );
+    assert_eq!(texture.get_image_data(1, 0, 0).unwrap(), [7, 8]);
+    assert_eq!(
+        texture.orientation().unwrap(),
+        Some(Orientation::new(XOrient::Right, YOrient::Up))
+    );
+
+    texture.flip_y().unwrap();
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap()[..4], [1, 1, 1, 1]);
+    assert_eq!(texture.orientation().unwrap().unwrap().to_string(), "rd");
+}
+
+#[test]
+fn test_flip_y_3d() {
+    let mut texture = Ktx2Texture::create(1, 2, 2, 1, 1, 2, VkFormat::R8Unorm).unwrap();
+    texture.set_image_data(0, 0, 0, &[1, 2]).unwrap();
+    texture.set_image_data(0, 0, 1, &[3, 4]).unwrap();
+    texture.set_image_data(1, 0, 0, &[5]).unwrap();
+
+    texture.flip_y().unwrap();
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap(), [2, 1]);
+    assert_eq!(texture.get_image_data(0, 0, 1).unwrap(), [4, 3]);
+    assert_eq!(texture.get_image_data(1, 0, 0).unwrap(), [5]);
+    assert_eq!(texture.orientation().unwrap().unwrap().to_string(), "rui");
+}
+
+#[test]
+fn test_flip_y_rejects_compressed() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture.compress_basis_simple(128).unwrap();
+    assert_eq!(texture.flip_y().unwrap_err(), Error::InvalidOperation);
+}
+
+#[test]
+fn test_set_all_level_data() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 1, 1, 4, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let levels: Vec<Vec<u8>> = (0..4u32)
+        .map(|level| {
+            let size = (8 >> level) as usize;
+            vec![level as u8 + 1; size * size * 4]
+        })
+        .collect();
+    let slices: Vec<&[u8]> = levels.iter().map(Vec::as_slice).collect();
+    texture.set_all_level_data(0, 0, &slices).unwrap();
+
+    for (level, expected) in levels.iter().enumerate() {
+        let data = texture.get_image_data(level as u32, 0, 0).unwrap();
+        assert_eq!(data, expected.as_slice());
+    }
+}
+
+#[test]
+fn test_set_all_level_data_3d_slice() {
+    let mut texture = Ktx2Texture::create(4, 4, 2, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    texture.set_all_level_data(0, 1, &[&[5; 16]]).unwrap();
+    assert_eq!(texture.get_image_data(0, 0, 1).unwrap(), [5; 16]);
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap(), [0; 16]);
+
+    // Slice 1 is gone once the mip chain reaches depth 1
+    let mut texture = Ktx2Texture::create(4, 4, 2, 1, 1, 2, VkFormat::R8Unorm).unwrap();
+    assert_eq!(
+        texture
+            .set_all_level_data(0, 1, &[&[5; 16], &[5; 4]])
+            .unwrap_err(),
+        Error::InvalidValue
+    );
+    texture
+        .set_all_level_data(0, 0, &[&[5; 16], &[6; 4]])
+        .unwrap();
+    assert_eq!(texture.get_image_data(1, 0, 0).unwrap(), [6; 4]);
+}
+
+#[test]
+fn test_set_all_level_data_mismatch() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 1, 1, 4, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    // Wrong level count
+    let base = vec![0xFFu8; 8 * 8 * 4];
+    let result = texture.set_all_level_data(0, 0, &[&base]);
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+
+    // Wrong size for the last level must not write the earlier ones
+    texture
+        .set_image_data(0, 0, 0, &vec![0u8; 8 * 8 * 4])
+        .unwrap();
+    let level1 = vec![0xFFu8; 4 * 4 * 4];
+    let level2 = vec![0xFFu8; 2 * 2 * 4];
+    let wrong = vec![0xFFu8; 3];
+    let result = texture.set_all_level_data(0, 0, &[&base, &level1, &level2, &wrong]);
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    assert!(texture
+        .get_image_data(0, 0, 0)
+        .unwrap()
+        .iter()
+        .all(|&b| b == 0));
+}
+
+#[test]
+fn test_content_eq_round_trip() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 2, 1, 2, VkFormat::R8G8B8A8Unorm).unwrap();
+    for layer in 0..2 {
+        texture
+            .set_image_data(0, layer, 0, &gradient_rgba(16, 16))
+            .unwrap();
+        texture
+            .set_image_data(1, layer, 0, &gradient_rgba(8, 8))
+            .unwrap();
+    }
+    texture.set_metadata("tool", b"test\0").unwrap();
+
+    let bytes = texture.write_to_memory().unwrap();
+    let mut reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert!(texture.content_eq(&reloaded));
+    assert!(reloaded.content_eq(&texture));
+
+    reloaded.get_image_data_mut(1, 1, 0).unwrap()[0] ^= 0xFF;
+    assert!(!texture.content_eq(&reloaded));
+}
+
+#[test]
+fn test_content_eq_3d() {
+    let mut texture = Ktx2Texture::create(4, 4, 4, 1, 1, 2, VkFormat::R8Unorm).unwrap();
+    for (level, slices) in [(0u32, 4u32), (1, 2)] {
+        for slice in 0..slices {
+            texture
+                .get_image_data_mut(level, 0, slice)
+                .unwrap()
+                .fill((level * 10 + slice) as u8);
+        }
+    }
+
+    let copy = texture.try_clone().unwrap();
+    assert!(texture.content_eq(&copy));
+
+    let bytes = texture.write_to_memory().unwrap();
+    let mut reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert!(texture.content_eq(&reloaded));
+
+    reloaded.get_image_data_mut(0, 0, 3).unwrap()[0] ^= 0xFF;
+    assert!(!texture.content_eq(&reloaded));
+}
+
+#[test]
+fn test_content_eq_with_writer() {
+    let mut texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    texture.set_writer("tool a").unwrap();
+    let mut copy = texture.try_clone().unwrap();
+    assert!(copy.content_eq_with_writer(&texture));
+
+    copy.set_writer("tool b").unwrap();
+    assert!(copy.content_eq(&texture));
+    assert!(!copy.content_eq_with_writer(&texture));
+}
+
+#[test]
+fn test_content_eq_mismatch() {
+    let a = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let b = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Srgb).unwrap();
+    let c = Ktx2Texture::create(16, 8, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert!(!a.content_eq(&b));
+    assert!(!a.content_eq(&c));
+
+    let mut d = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    d.set_image_data(0, 0, 0, &[0; 16]).unwrap();
+    let bytes = d.write_to_memory().unwrap();
+    let mut e = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert!(d.content_eq(&e));
+    e.set_metadata("extra", b"1").unwrap();
+    assert!(!d.content_eq(&e));
+}
+
+// ============================================================================
+// Metadata Tests
+// ============================================================================
+
+#[test]
+fn test_texture_metadata_roundtrip() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let key = "test_key";
+    let value = b"test_value_data";
+
+    // Set metadata
+    let result = texture.set_metadata(key, value);
+    assert!(result.is_ok());
+
+    // Get metadata
+    let result = texture.get_metadata(key);
+    assert!(result.is_ok());
+    let retrieved_value = result.unwrap();
+    assert_eq!(retrieved_value, value);
+}
+
+#[test]
+fn test_texture_metadata_not_found() {
+    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let result = texture.get_metadata("nonexistent_key");
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::NotFound);
+}
+
+#[test]
+fn test_texture_metadata_invalid_key() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    // Key with null byte should fail
+    let invalid_key = "test\0key";
+    let value = b"test_value";
+
+    let result = texture.set_metadata(invalid_key, value);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+}
+
+#[test]
+fn test_texture_set_metadata_map() {
+    use std::collections::BTreeMap;
+
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_metadata("source", b"old").unwrap();
+
+    let entries: BTreeMap<String, Vec<u8>> = [
+        ("source", &b"scan.png"[..]),
+        ("pipeline", b"{\"step\":3}"),
+        ("commit", b"abc123\0"),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value.to_vec()))
+    .collect();
+    texture.set_metadata_map(&entries).unwrap();
+    for (key, value) in &entries {
+        assert_eq!(&texture.get_metadata(key).unwrap(), value);
+    }
+    assert_eq!(texture.metadata_keys().unwrap().len(), 3);
+
+    let mut invalid = BTreeMap::new();
+    invalid.insert("a".to_string(), b"1".to_vec());
+    invalid.insert("b\0c".to_string(), b"2".to_vec());
+    assert_eq!(
+        texture.set_metadata_map(&invalid).unwrap_err(),
+        Error::InvalidMetadataKey("b\0c".to_string())
+    );
+    // Nothing was written
+    assert_eq!(texture.get_metadata("a").unwrap_err(), Error::NotFound);
+}
+
+#[test]
+fn test_texture_metadata_ref() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_metadata("hot_key", b"0123456789abcdef")
+        .unwrap();
+
+    let owned = texture.get_metadata("hot_key").unwrap();
+    let borrowed = texture.get_metadata_ref("hot_key").unwrap();
+    assert_eq!(borrowed, owned.as_slice());
+
+    // The borrowed slice points straight into the hash list storage
+    let (_, stored) = texture
+        .metadata_iter()
+        .find(|(key, _)| key == "hot_key")
+        .unwrap();
+    assert_eq!(borrowed.as_ptr(), stored.as_ptr());
+    assert_ne!(borrowed.as_ptr(), owned.as_ptr());
+
+    assert_eq!(
+        texture.get_metadata_ref("missing").unwrap_err(),
+        Error::NotFound
+    );
+}
+
+#[test]
+fn test_texture_with_metadata() {
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let blob: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    texture.set_metadata("icc_profile", &blob).unwrap();
+
+    let checksum = texture.with_metadata("icc_profile", fnv1a).unwrap();
+    assert_eq!(checksum, fnv1a(&blob));
+    assert_eq!(
+        texture.with_metadata("icc_profile", |value| value.len()),
+        Ok(blob.len())
+    );
+
+    assert_eq!(
+        texture.with_metadata("missing", fnv1a).unwrap_err(),
+        Error::NotFound
+    );
+    assert_eq!(
+        texture.with_metadata("bad\0key", fnv1a).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_texture_metadata_str() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    texture.set_metadata_str("tool", "baker 1.0").unwrap();
+    assert_eq!(texture.get_metadata("tool").unwrap(), b"baker 1.0\0");
+    assert_eq!(texture.get_metadata_str("tool").unwrap(), "baker 1.0");
+
+    // Values without a terminator are returned unchanged
+    texture.set_metadata("raw", b"plain").unwrap();
+    assert_eq!(texture.get_metadata_str("raw").unwrap(), "plain");
+
+    // Only a single trailing NUL is stripped
+    texture.set_metadata("double", b"x\0\0").unwrap();
+    assert_eq!(texture.get_metadata_str("double").unwrap(), "x\0");
+
+    texture.set_metadata("binary", &[0xFF, 0xFE, 0x00]).unwrap();
+    assert_eq!(
+        texture.get_metadata_str("binary").unwrap_err(),
+        Error::InvalidValue
+    );
+    assert_eq!(
+        texture.set_metadata_str("tool", "bad\0value").unwrap_err(),
+        Error::InvalidValue
+    );
+    assert_eq!(
+        texture.get_metadata_str("missing").unwrap_err(),
+        Error::NotFound
+    );
+}
+
+#[test]
+fn test_texture_metadata_iter() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_metadata("alpha", b"1").unwrap();
+    texture.set_metadata("beta", b"22").unwrap();
+    texture.set_metadata("gamma", b"333").unwrap();
+
+    let entries: Vec<(String, Vec<u8>)> = texture
+        .metadata_iter()
+        .map(|(key, value)| (key, value.to_vec()))
+        .collect();
+    for (key, value) in [("alpha", &b"1"[..]), ("beta", b"22"), ("gamma", b"333")] {
+        assert!(entries.contains(&(key.to_string(), value.to_vec())));
+    }
+
+    // libktx adds its own entries on write
+    let bytes = texture.write_to_memory().unwrap();
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    let keys = reloaded.metadata_keys().unwrap();
+    for key in ["alpha", "beta", "gamma", "KTXwriter"] {
+        assert!(keys.iter().any(|k| k == key), "missing {key} in {keys:?}");
+    }
+}
+
+#[test]
+fn test_texture_metadata_with_prefix() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_metadata("MyTool.author", b"me").unwrap();
+    texture.set_metadata("MyTool.version", b"2").unwrap();
+    texture.set_metadata("Other.author", b"you").unwrap();
+    texture.set_metadata("MyToolkit", b"x").unwrap();
+
+    let mut entries = texture.metadata_with_prefix("MyTool.");
+    entries.sort();
+    assert_eq!(
+        entries,
+        [
+            ("MyTool.author".to_string(), b"me".to_vec()),
+            ("MyTool.version".to_string(), b"2".to_vec()),
+        ]
+    );
+    assert_eq!(texture.metadata_with_prefix("Other.").len(), 1);
+    assert!(texture.metadata_with_prefix("mytool.").is_empty());
+}
+
+#[test]
+fn test_texture_metadata_replace() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_metadata("Author", b"a").unwrap();
+    texture.set_metadata("Author", b"b").unwrap();
+    assert_eq!(texture.get_metadata("Author").unwrap(), b"b");
+
+    let bytes = texture.write_to_memory().unwrap();
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    let authors: Vec<&[u8]> = reloaded
+        .metadata_iter()
+        .filter(|(key, _)| key == "Author")
+        .map(|(_, value)| value)
+        .collect();
+    assert_eq!(authors, vec![&b"b"[..]]);
+}
+
+#[test]
+fn test_texture_metadata_delete() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_metadata("source_path", b"/home/artist/wood.png")
+        .unwrap();
+    texture.set_metadata("keep_me", b"yes").unwrap();
+
+    texture.delete_metadata("source_path").unwrap();
+    assert_eq!(texture.get_metadata("source_path"), Err(Error::NotFound));
+
+    let bytes = texture.write_to_memory().unwrap();
+    assert!(!bytes
+        .windows(b"source_path".len())
+        .any(|window| window == b"source_path"));
+
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert_eq!(reloaded.get_metadata("keep_me").unwrap(), b"yes");
+    assert_eq!(reloaded.get_metadata("source_path"), Err(Error::NotFound));
+}
+
+#[test]
+fn test_texture_metadata_delete_errors() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    assert_eq!(texture.delete_metadata("missing"), Err(Error::NotFound));
+    assert_eq!(
+        texture.delete_metadata("bad\0key"),
+        Err(Error::InvalidValue)
+    );
+}
+
+#[test]
+fn test_orientation_parse() {
+    let orientation: Orientation = "rd".parse().unwrap();
+    assert_eq!(orientation, Orientation::new(XOrient::Right, YOrient::Down));
+
+    let orientation: Orientation = "lui".parse().unwrap();
+    assert_eq!(orientation.x, XOrient::Left);
+    assert_eq!(orientation.y, YOrient::Up);
+    assert_eq!(orientation.z, Some(ZOrient::In));
+    assert_eq!(orientation.to_string(), "lui");
+
+    for invalid in ["", "r", "dr", "rdx", "rdoo", "RD"] {
+        assert_eq!(invalid.parse::<Orientation>(), Err(Error::InvalidValue));
+    }
+}
+
+#[test]
+fn test_texture_orientation_round_trip() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(texture.orientation(), Ok(None));
+
+    let orientation = Orientation::new(XOrient::Right, YOrient::Up);
+    texture.set_orientation(orientation).unwrap();
+    assert_eq!(texture.get_metadata("KTXorientation").unwrap(), b"ru\0");
+
+    let bytes = texture.write_to_memory().unwrap();
+    let mut reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert_eq!(reloaded.orientation(), Ok(Some(orientation)));
+
+    reloaded.set_metadata("KTXorientation", b"xy\0").unwrap();
+    assert_eq!(reloaded.orientation(), Err(Error::InvalidValue));
+}
+
+#[test]
+fn test_texture_anim_data_round_trip() {
+    let mut texture = Ktx2Texture::create(8, 8, 1, 4, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(texture.anim_data(), Ok(None));
+
+    texture.set_anim_data(1, 24, 0).unwrap();
+    assert_eq!(
+        texture.get_metadata(AnimData::KEY).unwrap(),
+        [1, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    let bytes = texture.write_to_memory().unwrap();
+    let mut reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert_eq!(
+        reloaded.anim_data(),
+        Ok(Some(AnimData {
+            duration: 1,
+            timescale: 24,
+            loop_count: 0
+        }))
+    );
+
+    reloaded.set_metadata(AnimData::KEY, &[1, 2, 3]).unwrap();
+    assert_eq!(reloaded.anim_data(), Err(Error::FileDataError));
+}
+
+#[test]
+fn test_texture_writer_default() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(16, 16))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+    assert_eq!(texture.writer(), Ok(None));
+
+    let bytes = texture.write_to_memory().unwrap();
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    let writer = reloaded.writer().unwrap().unwrap();
+    assert!(
+        writer.starts_with(concat!("ktx2-rw ", env!("CARGO_PKG_VERSION"))),
+        "{writer}"
+    );
+}
+
+#[test]
+fn test_texture_writer_override() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_writer("my-pipeline 2.0").unwrap();
+    assert_eq!(
+        texture.writer().unwrap().as_deref(),
+        Some("my-pipeline 2.0")
+    );
+
+    let bytes = texture.write_to_memory().unwrap();
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    let writer = reloaded.writer().unwrap().unwrap();
+    assert!(writer.starts_with("my-pipeline 2.0"), "{writer}");
+
+    assert_eq!(texture.set_writer("bad\0writer"), Err(Error::InvalidValue));
+}
+
+#[test]
+fn test_texture_write_leaves_metadata_untouched() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture.set_metadata("zeta", b"1").unwrap();
+    texture.set_metadata("alpha", b"2").unwrap();
+    let keys = texture.metadata_keys().unwrap();
+
+    let bytes = texture.write_to_memory().unwrap();
+    assert_eq!(texture.metadata_keys().unwrap(), keys);
+    assert_eq!(texture.writer(), Ok(None));
+
+    // Concurrent writes through a shared reference produce the same file
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| texture.write_to_memory().unwrap()))
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), bytes);
+        }
+    });
+    assert_eq!(texture.metadata_keys().unwrap(), keys);
+}
+
+// ============================================================================
+// Compression Tests
+// ============================================================================
+
+#[test]
+fn test_compress_basis_simple() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    // Add some dummy image data first
+    let image_data = vec![128u8; 256 * 256 * 4]; // RGBA data
+    texture.set_image_data(0, 0, 0, &image_data).unwrap();
+
+    let result = texture.compress_basis_simple(128);
+    // Note: This might fail due to missing image data or other reasons
+    // but it should not panic
+    let _result = result;
+}
+
+#[test]
+fn test_compress_basis_with_params() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    // Add some dummy image data first
+    let image_data = vec![128u8; 256 * 256 * 4]; // RGBA data
+    texture.set_image_data(0, 0, 0, &image_data).unwrap();
+
+    let params = BasisCompressionParams::builder().build();
+    let result = texture.compress_basis(&params);
+    // Note: This might fail due to missing image data or other reasons
+    // but it should not panic
+    let _result = result;
+}
+
+#[test]
+fn test_uncompressed_size() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    assert_eq!(texture.uncompressed_size().unwrap(), texture.data_size());
+
+    let params = BasisCompressionParams::builder().uastc(true).build();
+    texture.compress_basis(&params).unwrap();
+    let encoded_size = texture.data_size();
+    assert_eq!(texture.uncompressed_size().unwrap(), encoded_size);
+
+    texture.deflate_zstd(3).unwrap();
+    assert!(texture.data_size() < encoded_size);
+    assert_eq!(texture.uncompressed_size().unwrap(), encoded_size);
+    assert!(texture.uncompressed_size().unwrap() > texture.data_size());
+}
+
+#[test]
+fn test_compress_basis_reported() {
+    for uastc in [false, true] {
+        let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+        texture
+            .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+            .unwrap();
+
+        let params = BasisCompressionParams::builder().uastc(uastc).build();
+        let report = texture.compress_basis_reported(&params).unwrap();
+
+        let expected_mode = if params.uastc {
+            CompressionMode::Uastc
+        } else {
+            CompressionMode::Etc1s
+        };
+        assert_eq!(report.mode, expected_mode);
+        assert_eq!(report.original_size, 64 * 64 * 4);
+        assert_eq!(report.compressed_size, texture.data_size());
+        assert!(report.ratio > 0.0);
+    }
+}
+
+#[test]
+fn test_compress_basis_with_progress() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+
+    let mut reports = Vec::new();
+    let params = BasisCompressionParams::builder().build();
+    texture
+        .compress_basis_with_progress(&params, |progress| reports.push(progress))
+        .unwrap();
+
+    let stages: Vec<_> = reports.iter().map(|p| p.stage).collect();
+    assert_eq!(
+        stages,
+        vec![CompressionStage::Started, CompressionStage::Finished]
+    );
+    assert!(reports.iter().all(|p| p.image_count == 1));
+    assert_eq!(reports.last().unwrap().fraction, 1.0);
+    assert!(texture.needs_transcoding());
+}
+
+#[test]
+fn test_astc_params_conversion() {
+    let params = AstcParams::builder()
+        .block_dimension(AstcBlockDimension::D6x6)
+        .quality_level(AstcParams::QUALITY_THOROUGH)
+        .perceptual(true)
+        .thread_count(4)
+        .build();
+
+    let ktx_params: ktxAstcParams = (&params).into();
+    assert_eq!(
+        ktx_params.blockDimension,
+        ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6
+    );
+    assert_eq!(
+        ktx_params.mode,
+        ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_LDR
+    );
+    assert_eq!(ktx_params.qualityLevel, AstcParams::QUALITY_THOROUGH);
+    assert!(ktx_params.perceptual);
+    assert_eq!(ktx_params.threadCount, 4);
+    assert_eq!(AstcBlockDimension::D6x6.size(), (6, 6));
+}
+
+#[test]
+fn test_compress_astc() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+
+    texture.compress_astc(&AstcParams::default()).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Astc4x4UnormBlock);
+    assert!(texture.is_compressed());
+    assert!(!texture.needs_transcoding());
+
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Srgb).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+
+    let params = AstcParams::builder()
+        .block_dimension(AstcBlockDimension::D8x8)
+        .build();
+    texture.compress_astc(&params).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Astc8x8SrgbBlock);
+}
+
+#[test]
+fn test_compress_astc_normal_map() {
+    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(256, 256))
+        .unwrap();
+
+    let params = AstcParams::builder()
+        .quality_level(AstcParams::QUALITY_FAST)
+        .normal_map(true)
+        .build();
+    texture.compress_astc(&params).unwrap();
+    assert!(texture.is_compressed());
+
+    // One 16-byte block per 4x4 texels
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap().len(), 64 * 64 * 16);
+}
+
+// ============================================================================
+// Write to Memory Tests
+// ============================================================================
+
+#[test]
+fn test_write_to_memory() {
+    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let result = texture.write_to_memory();
+    assert!(result.is_ok());
+
+    let data = result.unwrap();
+    assert!(!data.is_empty());
+
+    // Should start with KTX2 identifier
+    assert!(data.len() >= 12);
+    // KTX2 files start with specific bytes
+    let expected_header = [
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    assert_eq!(&data[0..12], &expected_header);
+}
+
+#[test]
+fn test_write_to_memory_repeated_drop() {
+    // Exercises the copy-and-free path; under ASAN or Miri a mismatched free
+    // of the libktx buffer shows up here
+    let texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+
+    let first = texture.write_to_memory().unwrap();
+    for _ in 0..64 {
+        let data = texture.write_to_memory().unwrap();
+        assert_eq!(data, first);
+        drop(data);
+    }
+}
+
+#[test]
+fn test_write_into_reuses_buffer() {
+    let mut buf = Vec::new();
+
+    for (size, key) in [(8u32, "first"), (16, "second"), (32, "third")] {
+        let mut texture =
+            Ktx2Texture::create(size, size, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+        texture.set_metadata(key, b"value").unwrap();
+        texture.write_into(&mut buf).unwrap();
+
+        assert_eq!(&buf[..12], b"\xABKTX 20\xBB\r\n\x1A\n");
+        let reloaded = Ktx2Texture::from_memory(&buf).unwrap();
+        assert_eq!(reloaded.width(), size);
+        assert_eq!(reloaded.get_metadata(key).unwrap(), b"value");
+        assert_eq!(buf, texture.write_to_memory().unwrap());
+    }
+}
+
+#[test]
+fn test_write_to_memory_with_zstd() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    let options = WriteOptions {
+        supercompression: Some(SupercompressionScheme::Zstd),
+        ..WriteOptions::default()
+    };
+    let bytes = texture.write_to_memory_with(&options).unwrap();
+
+    // The copy was deflated, the texture itself was not
+    assert_eq!(
+        texture.supercompression_scheme(),
+        SupercompressionScheme::None
+    );
+    let header = Ktx2Texture::from_memory_with_flags(&bytes, TextureCreateFlags::NONE).unwrap();
+    assert_eq!(
+        header.supercompression_scheme(),
+        SupercompressionScheme::Zstd
+    );
+    assert!(Ktx2Texture::from_memory(&bytes)
+        .unwrap()
+        .content_eq(&texture));
+}
+
+#[test]
+fn test_write_to_memory_with_default_options() {
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    assert_eq!(
+        texture
+            .write_to_memory_with(&WriteOptions::default())
+            .unwrap(),
+        texture.write_to_memory().unwrap()
+    );
+
+    let fresh = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    let options = WriteOptions {
+        add_writer_metadata: false,
+        ..WriteOptions::default()
+    };
+    let bytes = fresh.write_to_memory_with(&options).unwrap();
+    let writer = Ktx2Texture::from_memory(&bytes).unwrap().writer().unwrap();
+    assert!(!writer.is_some_and(|w| w.starts_with("ktx2-rw")));
+}
+
+#[test]
+fn test_write_to_memory_with_writer_leaves_texture_untouched() {
+    let mut texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    texture.set_metadata("tool", b"test\0").unwrap();
+    let keys = texture.metadata_keys().unwrap();
+
+    let options = WriteOptions {
+        add_writer_metadata: true,
+        ..WriteOptions::default()
+    };
+    let bytes = texture.write_to_memory_with(&options).unwrap();
+    assert_eq!(texture.metadata_keys().unwrap(), keys);
+
+    let writer = Ktx2Texture::from_memory(&bytes).unwrap().writer().unwrap();
+    assert!(writer.is_some_and(|w| w.starts_with("ktx2-rw")));
+}
+
+#[test]
+fn test_deflate_zstd_round_trip() {
+    let mut texture = Ktx2Texture::create(32, 32, 1, 1, 1, 2, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(32, 32))
+        .unwrap();
+    texture
+        .set_image_data(1, 0, 0, &gradient_rgba(16, 16))
+        .unwrap();
+    assert!(!texture.is_supercompressed());
+
+    for level in [0, 23] {
+        assert_eq!(
+            texture.deflate_zstd(level).unwrap_err(),
+            Error::InvalidValue
+        );
+    }
+    assert!(!texture.is_supercompressed());
+
+    texture.deflate_zstd(19).unwrap();
+    assert!(texture.is_supercompressed());
+    let bytes = texture.write_to_memory().unwrap();
+
+    let reloaded = Ktx2Texture::from_memory(&bytes).unwrap();
+    assert_eq!(
+        reloaded.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(32, 32)
+    );
+    assert_eq!(
+        reloaded.get_image_data(1, 0, 0).unwrap(),
+        gradient_rgba(16, 16)
+    );
+
+    texture.inflate().unwrap();
+    assert!(!texture.is_supercompressed());
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(32, 32)
+    );
+}
+
+#[test]
+fn test_write_to_memory_with_zstd_rejects_basis_lz() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture.compress_basis_simple(128).unwrap();
+    assert_eq!(
+        texture.supercompression_scheme(),
+        SupercompressionScheme::BasisLz
+    );
+
+    let options = WriteOptions {
+        supercompression: Some(SupercompressionScheme::Zstd),
+        ..WriteOptions::default()
+    };
+    assert_eq!(
+        texture.write_to_memory_with(&options).unwrap_err(),
+        Error::InvalidOperation
+    );
+}
+
+#[test]
+fn test_write_to_writer_matches_memory() {
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+
+    let mut cursor = Cursor::new(Vec::new());
+    let written = texture.write_to_writer(&mut cursor).unwrap();
+    let streamed = cursor.into_inner();
+
+    assert_eq!(written, streamed.len() as u64);
+    assert_eq!(streamed, texture.write_to_memory().unwrap());
+}
+
+#[test]
+fn test_write_to_writer_error() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let texture = Ktx2Texture::create(8, 8, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let err = texture.write_to_writer(FailingWriter).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Io {
+            kind: io::ErrorKind::BrokenPipe,
+            message: "pipe closed".to_string(),
+        }
+    );
+}
+
+// ============================================================================
+// Batch Tests
+// ============================================================================
+
+#[test]
+fn test_transcode_many_preserves_order() {
+    let params = BasisCompressionParams::builder().uastc(true).build();
+    let mut inputs: Vec<Vec<u8>> = [16u32, 32, 64]
+        .iter()
+        .map(|&size| {
+            let mut texture =
+                Ktx2Texture::create(size, size, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+            texture
+                .set_image_data(0, 0, 0, &gradient_rgba(size, size))
+                .unwrap();
+            texture.compress_basis(&params).unwrap();
+            texture.write_to_memory().unwrap()
+        })
+        .collect();
+    inputs.push(b"not a ktx2 file".to_vec());
+
+    let results = transcode_many(inputs, TranscodeFormat::Rgba32);
+    assert_eq!(results.len(), 4);
+
+    for (result, size) in results.iter().zip([16u32, 32, 64]) {
+        let texture = Ktx2Texture::from_memory(result.as_ref().unwrap()).unwrap();
+        assert_eq!(texture.width(), size);
+        assert!(!texture.needs_transcoding());
+        assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    }
+    assert!(results[3].is_err());
+}
+
 // ============================================================================
-// Metadata Tests
+// Thread Safety Tests
 // ============================================================================
 
 #[test]
-fn test_texture_metadata_roundtrip() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_into_raw_from_raw() {
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
+    let expected = texture.write_to_memory().unwrap();
 
-    let key = "test_key";
-    let value = b"test_value_data";
+    let raw = texture.into_raw();
+    assert!(!raw.is_null());
+    assert_eq!(unsafe { (*raw).baseWidth }, 16);
 
-    // Set metadata
-    let result = texture.set_metadata(key, value);
-    assert!(result.is_ok());
+    let texture = unsafe { Ktx2Texture::from_raw(raw) };
+    assert_eq!(texture.levels(), 2);
+    assert_eq!(texture.write_to_memory().unwrap(), expected);
+}
 
-    // Get metadata
-    let result = texture.get_metadata(key);
-    assert!(result.is_ok());
-    let retrieved_value = result.unwrap();
-    assert_eq!(retrieved_value, value);
+#[test]
+fn test_texture_as_raw() {
+    let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8G8Unorm).unwrap();
+    let raw = texture.as_raw();
+    assert_eq!(unsafe { ktxTexture2_GetNumComponents(raw) }, 2);
+    assert_eq!(unsafe { (*raw).baseWidth }, 4);
 }
 
+#[cfg(feature = "sys")]
 #[test]
-fn test_texture_metadata_not_found() {
-    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_sys_bindings_with_as_raw() {
+    let texture = Ktx2Texture::create(8, 4, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let raw: *mut crate::sys::ktxTexture2 = texture.as_raw();
+    assert_eq!(unsafe { crate::sys::ktxTexture2_GetNumComponents(raw) }, 4);
+}
 
-    let result = texture.get_metadata("nonexistent_key");
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::NotFound);
+#[test]
+#[should_panic(expected = "null")]
+fn test_texture_from_raw_null() {
+    let _ = unsafe { Ktx2Texture::from_raw(std::ptr::null_mut()) };
 }
 
 #[test]
-fn test_texture_metadata_invalid_key() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_into_raw_copies_borrowed_data() {
+    let expected = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
 
-    // Key with null byte should fail
-    let invalid_key = "test\0key";
-    let value = b"test_value";
+    let from_vec = Ktx2Texture::from_vec(sample_ktx2_bytes()).unwrap();
+    let deferred =
+        Ktx2Texture::from_memory_with_flags(&sample_ktx2_bytes(), TextureCreateFlags::NONE)
+            .unwrap();
 
-    let result = texture.set_metadata(invalid_key, value);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Error::InvalidValue);
+    for texture in [from_vec, deferred] {
+        let texture = unsafe { Ktx2Texture::from_raw(texture.into_raw()) };
+        assert!(texture.has_image_data());
+        assert!(texture.content_eq(&expected));
+    }
 }
 
-// ============================================================================
-// Compression Tests
-// ============================================================================
-
 #[test]
-fn test_compress_basis_simple() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_clone_is_independent() {
+    let texture = Ktx2Texture::from_memory(&sample_ktx2_bytes()).unwrap();
 
-    // Add some dummy image data first
-    let image_data = vec![128u8; 256 * 256 * 4]; // RGBA data
-    texture.set_image_data(0, 0, 0, &image_data).unwrap();
+    let mut copy = texture.try_clone().unwrap();
+    assert!(copy.content_eq(&texture));
 
-    let result = texture.compress_basis_simple(128);
-    // Note: This might fail due to missing image data or other reasons
-    // but it should not panic
-    let _result = result;
+    copy.set_metadata("tool", b"copy\0").unwrap();
+    copy.get_image_data_mut(0, 0, 0).unwrap()[0] ^= 0xFF;
+    assert_eq!(texture.get_metadata_str("tool").unwrap(), "reader");
+    assert_eq!(
+        texture.get_image_data(0, 0, 0).unwrap(),
+        gradient_rgba(16, 16)
+    );
+    assert!(!copy.content_eq(&texture));
 }
 
 #[test]
-fn test_compress_basis_with_params() {
-    let mut texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_clone_keeps_compression_state() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+    texture.deflate_zstd(3).unwrap();
 
-    // Add some dummy image data first
-    let image_data = vec![128u8; 256 * 256 * 4]; // RGBA data
-    texture.set_image_data(0, 0, 0, &image_data).unwrap();
+    let copy = texture.try_clone().unwrap();
+    assert!(copy.needs_transcoding());
+    assert_eq!(copy.supercompression_scheme(), SupercompressionScheme::Zstd);
+    assert_eq!(copy.write_to_memory(), texture.write_to_memory());
 
-    let params = BasisCompressionParams::builder().build();
-    let result = texture.compress_basis(&params);
-    // Note: This might fail due to missing image data or other reasons
-    // but it should not panic
-    let _result = result;
+    let header =
+        Ktx2Texture::from_memory_with_flags(&sample_ktx2_bytes(), TextureCreateFlags::NONE)
+            .unwrap();
+    let header_copy = header.try_clone().unwrap();
+    assert!(!header_copy.has_image_data());
+    assert_eq!(header_copy.levels(), 2);
 }
 
-// ============================================================================
-// Write to Memory Tests
-// ============================================================================
-
 #[test]
-fn test_write_to_memory() {
-    let texture = Ktx2Texture::create(256, 256, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+fn test_texture_clone_header_only_file() {
+    let path = temp_path("clone-header.ktx2");
+    std::fs::write(&path, sample_ktx2_bytes()).unwrap();
 
-    let result = texture.write_to_memory();
-    assert!(result.is_ok());
+    let mut header = Ktx2Texture::from_file_with_flags(&path, TextureCreateFlags::NONE).unwrap();
+    header.set_metadata("extra", b"1").unwrap();
+    let mut copy = header.try_clone().unwrap();
+    assert!(!copy.has_image_data());
+    assert_eq!(copy.get_metadata("extra").unwrap(), b"1");
 
-    let data = result.unwrap();
-    assert!(!data.is_empty());
+    copy.load_image_data().unwrap();
+    header.load_image_data().unwrap();
+    assert!(copy.content_eq(&header));
 
-    // Should start with KTX2 identifier
-    assert!(data.len() >= 12);
-    // KTX2 files start with specific bytes
-    let expected_header = [
-        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
-    ];
-    assert_eq!(&data[0..12], &expected_header);
+    // A rewritten or removed file is not silently copied
+    let header = Ktx2Texture::from_file_with_flags(&path, TextureCreateFlags::NONE).unwrap();
+    let mut other = Ktx2Texture::create(8, 8, 1, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    other.set_metadata("padding", &[0; 4096]).unwrap();
+    std::fs::write(&path, other.write_to_memory().unwrap()).unwrap();
+    assert_eq!(header.try_clone().err(), Some(Error::FileDataError));
+    std::fs::remove_file(&path).unwrap();
+    assert!(header.try_clone().is_err());
 }
 
-// ============================================================================
-// Thread Safety Tests
-// ============================================================================
-
 #[test]
 fn test_texture_send_sync() {
     fn assert_send<T: Send>() {}
@@ -780,3 +3811,726 @@ fn test_texture_non_power_of_two_sizes() {
         assert!(result.is_ok(), "Failed to create {width}x{height} texture");
     }
 }
+
+// ============================================================================
+// Decode Tests
+// ============================================================================
+
+fn gradient_rgba(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            data.push((x * 255 / (width - 1).max(1)) as u8);
+            data.push((y * 255 / (height - 1).max(1)) as u8);
+            data.push(128);
+            data.push(255);
+        }
+    }
+    data
+}
+
+#[test]
+fn test_decode_rgba8_uncompressed() {
+    let mut texture = Ktx2Texture::create(16, 16, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let source = gradient_rgba(16, 16);
+    texture.set_image_data(0, 0, 0, &source).unwrap();
+
+    let decoded = texture.decode_rgba8(0, 0, 0).unwrap();
+    assert_eq!(decoded, source);
+}
+
+#[test]
+fn test_decode_rgba8_3d_slice() {
+    let mut texture = Ktx2Texture::create(2, 1, 2, 1, 1, 1, VkFormat::R8Unorm).unwrap();
+    texture.set_image_data(0, 0, 1, &[7, 9]).unwrap();
+
+    assert_eq!(
+        texture.decode_rgba8(0, 0, 1).unwrap(),
+        [7, 0, 0, 255, 9, 0, 0, 255]
+    );
+    assert_eq!(
+        texture.decode_rgba8(0, 0, 2).unwrap_err(),
+        Error::InvalidValue
+    );
+}
+
+#[test]
+fn test_decode_rgba8_expands_rgb() {
+    let mut texture = Ktx2Texture::create(2, 1, 1, 1, 1, 1, VkFormat::R8G8B8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &[10, 20, 30, 40, 50, 60])
+        .unwrap();
+
+    let decoded = texture.decode_rgba8(0, 0, 0).unwrap();
+    assert_eq!(decoded, [10, 20, 30, 255, 40, 50, 60, 255]);
+}
+
+#[test]
+fn test_decode_rgba8_compressed_gradient() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    let source = gradient_rgba(64, 64);
+    texture.set_image_data(0, 0, 0, &source).unwrap();
+
+    let params = BasisCompressionParams::builder().uastc(true).build();
+    texture.compress_basis(&params).unwrap();
+    assert!(texture.needs_transcoding());
+
+    let decoded = texture.decode_rgba8(0, 0, 0).unwrap();
+    assert_eq!(decoded.len(), 64 * 64 * 4);
+
+    for (x, y) in [(0, 0), (63, 0), (0, 63), (32, 32), (63, 63)] {
+        let i = ((y * 64 + x) * 4) as usize;
+        for c in 0..4 {
+            let diff = (decoded[i + c] as i32 - source[i + c] as i32).abs();
+            assert!(diff <= 16, "pixel ({x}, {y}) channel {c} differs by {diff}");
+        }
+    }
+
+    // Decoding must not transcode the texture itself
+    assert!(texture.needs_transcoding());
+}
+
+#[test]
+fn test_decode_rgba8_unsupported_format() {
+    let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R32G32B32A32Sfloat).unwrap();
+
+    let result = texture.decode_rgba8(0, 0, 0);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::UnsupportedFormat(VkFormat::R32G32B32A32Sfloat)
+    );
+}
+
+// ============================================================================
+// Transcode Tests
+// ============================================================================
+
+#[test]
+fn test_transcode_uncompressed_texture() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+
+    let result = texture.transcode_basis(TranscodeFormat::Bc7Rgba);
+    assert_eq!(result.unwrap_err(), Error::InvalidOperation);
+}
+
+#[test]
+fn test_transcode_pvrtc1_non_power_of_two() {
+    let mut texture = Ktx2Texture::create(48, 32, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(48, 32))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    let result = texture.transcode_basis(TranscodeFormat::Pvrtc1_4_Rgba);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::UnsupportedTranscodeTarget(TranscodeFormat::Pvrtc1_4_Rgba)
+    );
+    assert!(texture.needs_transcoding());
+}
+
+#[test]
+fn test_available_transcode_formats() {
+    let mut texture = Ktx2Texture::create(48, 32, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert!(texture.available_transcode_formats().is_empty());
+
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(48, 32))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    let formats = texture.available_transcode_formats();
+    assert!(formats.contains(&TranscodeFormat::Bc7Rgba));
+    assert!(formats.contains(&TranscodeFormat::Astc_4x4_Rgba));
+    // Opaque targets would drop alpha, and PVRTC1 needs square power-of-two sizes
+    assert!(!formats.contains(&TranscodeFormat::Bc1Rgb));
+    assert!(!formats.contains(&TranscodeFormat::Pvrtc1_4_Rgba));
+    for format in formats {
+        assert!(format.num_components() >= 4);
+    }
+}
+
+#[test]
+fn test_transcode_to_vk_format() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    let result = texture.transcode_to_vk_format(VkFormat::R32Sfloat);
+    assert_eq!(result.unwrap_err(), Error::UnsupportedFeature);
+    assert!(texture.needs_transcoding());
+
+    texture
+        .transcode_to_vk_format(VkFormat::Bc7UnormBlock)
+        .unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Bc7UnormBlock);
+    assert!(!texture.needs_transcoding());
+
+    // The requested sRGB/UNORM variant wins over the source's transfer function
+    for (source, target) in [
+        (VkFormat::R8G8B8A8Unorm, VkFormat::Bc7SrgbBlock),
+        (VkFormat::R8G8B8A8Srgb, VkFormat::Bc3UnormBlock),
+        (VkFormat::R8G8B8A8Unorm, VkFormat::R8G8B8A8Srgb),
+    ] {
+        let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, source).unwrap();
+        texture
+            .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+            .unwrap();
+        texture
+            .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+            .unwrap();
+
+        texture.transcode_to_vk_format(target).unwrap();
+        assert_eq!(texture.vk_format(), target);
+        assert_eq!(texture.is_srgb(), target.is_srgb());
+    }
+}
+
+#[test]
+fn test_ensure_transcoded_only_once() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    assert_eq!(texture.ensure_transcoded(TranscodeFormat::Rgba32), Ok(true));
+    assert!(!texture.needs_transcoding());
+    assert_eq!(
+        texture.ensure_transcoded(TranscodeFormat::Rgba32),
+        Ok(false)
+    );
+}
+
+#[test]
+fn test_estimate_transcoded_size() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 7, VkFormat::R8G8B8A8Unorm).unwrap();
+    for level in 0..7 {
+        let size = 64 >> level;
+        texture
+            .set_image_data(level, 0, 0, &gradient_rgba(size, size))
+            .unwrap();
+    }
+    texture
+        .compress_basis(&BasisCompressionParams::builder().uastc(true).build())
+        .unwrap();
+
+    for format in [
+        TranscodeFormat::Bc7Rgba,
+        TranscodeFormat::Etc1Rgb,
+        TranscodeFormat::Rgba32,
+        TranscodeFormat::Rgb565,
+    ] {
+        let estimate = texture.estimate_transcoded_size(format).unwrap();
+
+        let transcoded = texture.transcode_to(format).unwrap();
+        let actual: usize = (0..transcoded.levels())
+            .map(|level| transcoded.get_image_data(level, 0, 0).unwrap().len())
+            .sum();
+
+        let difference = estimate.abs_diff(actual);
+        assert!(
+            difference * 20 <= actual,
+            "{format:?}: estimate {estimate}, actual {actual}"
+        );
+    }
+}
+
+#[test]
+fn test_transcode_to_leaves_source_untouched() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 1, VkFormat::R8G8B8A8Unorm).unwrap();
+    texture
+        .set_image_data(0, 0, 0, &gradient_rgba(64, 64))
+        .unwrap();
+    texture.compress_basis_simple(128).unwrap();
+    assert!(texture.needs_transcoding());
+
+    for format in [
+        TranscodeFormat::Bc7Rgba,
+        TranscodeFormat::Etc2Rgba,
+        TranscodeFormat::Rgba32,
+    ] {
+        let transcoded = texture.transcode_to(format).unwrap();
+        assert!(!transcoded.needs_transcoding());
+        assert_eq!(transcoded.width(), 64);
+        assert_eq!(transcoded.height(), 64);
+        assert!(transcoded.get_image_data(0, 0, 0).is_ok());
+    }
+
+    assert!(texture.needs_transcoding());
+}
+
+#[test]
+fn test_texture_memory_footprint() {
+    let mut texture = Ktx2Texture::create(64, 64, 1, 1, 1, 7, VkFormat::R8G8B8A8Unorm).unwrap();
+    let footprint = texture.memory_footprint();
+    assert!(footprint >= texture.data_size());
+
+    texture.set_metadata("large", &[0; 4096]).unwrap();
+    assert!(texture.memory_footprint() >= footprint + 4096);
+
+    // Header-only loads hold no image data but still have a footprint
+    let data = texture.write_to_memory().unwrap();
+    let header_only = Ktx2Texture::from_memory_with_options(
+        &data,
+        LoadOptions {
+            load_image_data: false,
+        },
+    )
+    .unwrap();
+    assert!(header_only.memory_footprint() > 0);
+}
+
+/// Builds an uncompressed 2D KTX1 file with one level
+fn ktx1_file(gl_internal_format: u32, width: u32, height: u32, data: &[u8]) -> Vec<u8> {
+    const GL_UNSIGNED_BYTE: u32 = 0x1401;
+    const GL_RGBA: u32 = 0x1908;
+
+    let mut file = vec![
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    for word in [
+        0x0403_0201, // endianness
+        GL_UNSIGNED_BYTE,
+        1, // glTypeSize
+        GL_RGBA,
+        gl_internal_format,
+        GL_RGBA,
+        width,
+        height,
+        0, // pixelDepth
+        0, // numberOfArrayElements
+        1, // numberOfFaces
+        1, // numberOfMipmapLevels
+        0, // bytesOfKeyValueData
+        data.len() as u32,
+    ] {
+        file.extend_from_slice(&u32::to_le_bytes(word));
+    }
+    file.extend_from_slice(data);
+    file
+}
+
+#[test]
+fn test_texture_from_ktx1_memory() {
+    const GL_RGBA8: u32 = 0x8058;
+
+    let pixels: Vec<u8> = (0..4 * 2 * 4).map(|i| i as u8 * 7).collect();
+    let file = ktx1_file(GL_RGBA8, 4, 2, &pixels);
+
+    let texture = Ktx2Texture::from_ktx1_memory(&file).unwrap();
+    assert_eq!(texture.width(), 4);
+    assert_eq!(texture.height(), 2);
+    assert_eq!(texture.levels(), 1);
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap(), &pixels[..]);
+
+    // KTX1 is only accepted through the explicit conversion
+    assert!(matches!(
+        Ktx2Texture::from_memory(&file),
+        Err(Error::UnknownFileFormat)
+    ));
+    let ktx2 = texture.write_to_memory().unwrap();
+    assert!(matches!(
+        Ktx2Texture::from_ktx1_memory(&ktx2),
+        Err(Error::UnknownFileFormat)
+    ));
+}
+
+#[test]
+fn test_error_code_round_trip() {
+    for code in 1..=ktx_error_code_e_KTX_ERROR_MAX_ENUM {
+        assert_eq!(Error::from(code).code(), code);
+    }
+    assert_eq!(Error::from(1000).code(), 1000);
+
+    assert_eq!(Error::try_from_code(ktx_error_code_e_KTX_SUCCESS), None);
+    assert_eq!(
+        Error::try_from_code(ktx_error_code_e_KTX_NOT_FOUND),
+        Some(Error::NotFound)
+    );
+
+    assert_eq!(
+        Error::OutOfMemory.code(),
+        ktx_error_code_e_KTX_OUT_OF_MEMORY
+    );
+    assert_eq!(
+        Error::ImageSizeMismatch {
+            expected: 4,
+            actual: 3
+        }
+        .code(),
+        ktx_error_code_e_KTX_INVALID_VALUE
+    );
+    assert_eq!(
+        Error::from(io::Error::from(io::ErrorKind::UnexpectedEof)).code(),
+        ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF
+    );
+}
+
+#[test]
+fn test_texture_create_1d() {
+    let texture = Ktx2Texture::create_1d(512, 1, 10, VkFormat::R8G8B8A8Unorm).unwrap();
+    assert_eq!(texture.num_dimensions(), 1);
+    assert!(texture.is_1d());
+    assert!(!texture.is_array());
+    assert_eq!(texture.height(), 1);
+    assert_eq!(texture.levels(), 10);
+    assert_eq!(texture.validate().unwrap(), vec![]);
+
+    let array = Ktx2Texture::create_1d(64, 4, 1, VkFormat::R8Unorm).unwrap();
+    assert!(array.is_1d());
+    assert!(array.is_array());
+    assert_eq!(array.layers(), 4);
+
+    assert!(!Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::R8Unorm)
+        .unwrap()
+        .is_1d());
+    assert!(matches!(
+        Ktx2Texture::create_1d(512, 1, 11, VkFormat::R8Unorm),
+        Err(Error::InvalidValue)
+    ));
+    assert!(matches!(
+        Ktx2Texture::create_1d(0, 1, 1, VkFormat::R8Unorm),
+        Err(Error::InvalidValue)
+    ));
+}
+
+#[test]
+fn test_texture_validate() {
+    let mut texture = Ktx2Texture::create(32, 16, 1, 1, 1, 3, VkFormat::R8G8B8A8Srgb).unwrap();
+    assert_eq!(texture.validate().unwrap(), vec![]);
+
+    texture.set_metadata("KTXcustom", b"1").unwrap();
+    texture.set_metadata("KTXwriter", b"no terminator").unwrap();
+    texture.set_anim_data(1, 30, 0).unwrap();
+    texture.set_metadata("KTXorientation", b"rdo\0").unwrap();
+    let warnings = texture.validate().unwrap();
+    assert_eq!(warnings.len(), 4);
+    assert!(warnings.contains(&ValidationWarning::UnknownReservedKey("KTXcustom".into())));
+    assert!(warnings.contains(&ValidationWarning::MissingNulTerminator("KTXwriter".into())));
+    assert!(warnings.contains(&ValidationWarning::AnimDataWithoutArray));
+    assert!(
+        warnings.contains(&ValidationWarning::OrientationDimensionMismatch {
+            expected: 2,
+            actual: 3
+        })
+    );
+
+    texture.set_metadata("KTXorientation", b"xy\0").unwrap();
+    assert!(matches!(texture.validate(), Err(Error::FileDataError)));
+}
+
+#[cfg(feature = "wgpu")]
+#[test]
+fn test_wgpu_format_mapping() {
+    use crate::pick_transcode_format_for_wgpu;
+    use wgpu_types::{AstcBlock, AstcChannel, Features, TextureFormat};
+
+    assert_eq!(
+        TranscodeFormat::Bc7Rgba.to_wgpu(true),
+        Some(TextureFormat::Bc7RgbaUnormSrgb)
+    );
+    assert_eq!(
+        TranscodeFormat::Astc_4x4_Rgba.to_wgpu(false),
+        Some(TextureFormat::Astc {
+            block: AstcBlock::B4x4,
+            channel: AstcChannel::Unorm,
+        })
+    );
+    assert_eq!(
+        TranscodeFormat::Bc5Rg.to_wgpu(true),
+        Some(TextureFormat::Bc5RgUnorm)
+    );
+    assert_eq!(TranscodeFormat::Pvrtc1_4_Rgba.to_wgpu(false), None);
+    assert_eq!(TranscodeFormat::Rgb565.to_wgpu(false), None);
+
+    // Every sampleable transcode target agrees with its VkFormat mapping
+    for format in TranscodeFormat::ALL {
+        for srgb in [false, true] {
+            if let Some(vk_format) = format.to_vk_format(srgb) {
+                assert_eq!(vk_format.to_wgpu(), format.to_wgpu(srgb), "{format:?}");
+            }
+        }
+    }
+    assert_eq!(VkFormat::R8G8B8Unorm.to_wgpu(), None);
+    assert_eq!(VkFormat::Pvrtc1_4bppUnormBlockImg.to_wgpu(), None);
+    assert_eq!(
+        VkFormat::Astc6x6SfloatBlock.to_wgpu(),
+        Some(TextureFormat::Astc {
+            block: AstcBlock::B6x6,
+            channel: AstcChannel::Hdr
+        })
+    );
+    assert_eq!(
+        VkFormat::D32Sfloat.to_wgpu(),
+        Some(TextureFormat::Depth32Float)
+    );
+    assert_eq!(
+        VkFormat::B8G8R8A8Srgb.to_wgpu(),
+        Some(TextureFormat::Bgra8UnormSrgb)
+    );
+
+    let all = Features::TEXTURE_COMPRESSION_BC
+        | Features::TEXTURE_COMPRESSION_ASTC
+        | Features::TEXTURE_COMPRESSION_ETC2;
+    assert_eq!(
+        pick_transcode_format_for_wgpu(all, true),
+        TranscodeFormat::Bc7Rgba
+    );
+    assert_eq!(
+        pick_transcode_format_for_wgpu(Features::TEXTURE_COMPRESSION_ASTC, false),
+        TranscodeFormat::Astc_4x4_Rgba
+    );
+    assert_eq!(
+        pick_transcode_format_for_wgpu(Features::TEXTURE_COMPRESSION_ETC2, true),
+        TranscodeFormat::Etc2Rgba
+    );
+    assert_eq!(
+        pick_transcode_format_for_wgpu(Features::TEXTURE_COMPRESSION_ETC2, false),
+        TranscodeFormat::Etc1Rgb
+    );
+    assert_eq!(
+        pick_transcode_format_for_wgpu(Features::empty(), true),
+        TranscodeFormat::Rgba32
+    );
+}
+
+#[cfg(feature = "ash")]
+#[test]
+fn test_ash_format_conversions() {
+    use ash::vk;
+
+    let pairs = [
+        (VkFormat::Undefined, vk::Format::UNDEFINED),
+        (VkFormat::R4G4UnormPack8, vk::Format::R4G4_UNORM_PACK8),
+        (VkFormat::R8G8B8A8Srgb, vk::Format::R8G8B8A8_SRGB),
+        (VkFormat::D24UnormS8Uint, vk::Format::D24_UNORM_S8_UINT),
+        (VkFormat::Bc7SrgbBlock, vk::Format::BC7_SRGB_BLOCK),
+        (
+            VkFormat::Astc12x12SrgbBlock,
+            vk::Format::ASTC_12X12_SRGB_BLOCK,
+        ),
+        (
+            VkFormat::Pvrtc2_4bppSrgbBlockImg,
+            vk::Format::PVRTC2_4BPP_SRGB_BLOCK_IMG,
+        ),
+        (
+            VkFormat::Astc4x4SfloatBlock,
+            vk::Format::ASTC_4X4_SFLOAT_BLOCK,
+        ),
+        (
+            VkFormat::G8B8R8_2plane420Unorm,
+            vk::Format::G8_B8R8_2PLANE_420_UNORM,
+        ),
+        (
+            VkFormat::A4B4G4R4UnormPack16,
+            vk::Format::A4B4G4R4_UNORM_PACK16,
+        ),
+    ];
+    for (format, ash_format) in pairs {
+        assert_eq!(vk::Format::from(format), ash_format, "{format:?}");
+        assert_eq!(VkFormat::try_from(ash_format).unwrap(), format);
+    }
+
+    // Both sides share the Vulkan numbering, so every variant round-trips
+    for &format in VkFormat::ALL {
+        assert_eq!(
+            VkFormat::try_from(vk::Format::from(format)).unwrap(),
+            format
+        );
+    }
+
+    for unmodeled in [vk::Format::from_raw(185), vk::Format::from_raw(-1)] {
+        assert!(matches!(
+            VkFormat::try_from(unmodeled),
+            Err(Error::InvalidValue)
+        ));
+    }
+
+    let texture = Ktx2Texture::create(4, 4, 1, 1, 1, 1, VkFormat::Bc7SrgbBlock).unwrap();
+    assert_eq!(texture.vk_format_ash(), vk::Format::BC7_SRGB_BLOCK);
+}
+
+/// Builds a DDS file with the given pixel format words (flags, FourCC, bit
+/// count and RGBA masks), optional DX10 header words and payload
+#[cfg(feature = "dds")]
+fn dds_file(
+    width: u32,
+    height: u32,
+    levels: u32,
+    pixel_format: [u32; 7],
+    caps2: u32,
+    dx10: Option<[u32; 5]>,
+    payload: &[u8],
+) -> Vec<u8> {
+    const DDSD_CAPS_HEIGHT_WIDTH_PIXELFORMAT: u32 = 0x1007;
+    const DDSD_MIPMAPCOUNT: u32 = 0x2_0000;
+
+    let mut header = vec![
+        124,
+        DDSD_CAPS_HEIGHT_WIDTH_PIXELFORMAT | DDSD_MIPMAPCOUNT,
+        height,
+        width,
+        0, // pitch or linear size
+        0, // depth
+        levels,
+    ];
+    header.extend([0; 11]);
+    header.push(32);
+    header.extend(pixel_format);
+    header.extend([0x1000, caps2, 0, 0, 0]);
+    header.extend(dx10.into_iter().flatten());
+
+    let mut file = b"DDS ".to_vec();
+    for word in header {
+        file.extend_from_slice(&word.to_le_bytes());
+    }
+    file.extend_from_slice(payload);
+    file
+}
+
+#[cfg(feature = "dds")]
+#[test]
+fn test_texture_from_dds_bc1_array() {
+    const DDPF_FOURCC: u32 = 0x4;
+    const DXGI_FORMAT_BC1_UNORM: u32 = 71;
+
+    let dx10 = u32::from_le_bytes(*b"DX10");
+    let pixel_format = [DDPF_FOURCC, dx10, 0, 0, 0, 0, 0];
+    // Two layers of an 8x8 chain: 4 blocks, then 1 block, of 8 bytes each
+    let payload: Vec<u8> = (0..2 * (32 + 8)).map(|i| i as u8).collect();
+    let file = dds_file(
+        8,
+        8,
+        2,
+        pixel_format,
+        0,
+        Some([DXGI_FORMAT_BC1_UNORM, 3, 0, 2, 0]),
+        &payload,
+    );
+
+    let texture = Ktx2Texture::from_dds_memory(&file).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::Bc1RgbaUnormBlock);
+    assert_eq!((texture.width(), texture.height()), (8, 8));
+    assert_eq!(texture.levels(), 2);
+    assert_eq!(texture.layers(), 2);
+    assert!(texture.is_array());
+    assert_eq!(texture.get_image_data(0, 0, 0).unwrap(), &payload[..32]);
+    assert_eq!(texture.get_image_data(1, 0, 0).unwrap(), &payload[32..40]);
+    assert_eq!(texture.get_image_data(0, 1, 0).unwrap(), &payload[40..72]);
+    assert_eq!(texture.get_image_data(1, 1, 0).unwrap(), &payload[72..]);
+
+    // The converted texture survives a KTX2 round trip
+    let reloaded = Ktx2Texture::from_memory(&texture.write_to_memory().unwrap()).unwrap();
+    assert_eq!(reloaded.get_image_data(1, 1, 0).unwrap(), &payload[72..]);
+}
+
+#[cfg(feature = "dds")]
+#[test]
+fn test_texture_from_dds_rgba8_cubemap() {
+    const DDPF_ALPHAPIXELS_RGB: u32 = 0x41;
+    const DDSCAPS2_CUBEMAP_WITH_ALL_FACES: u32 = 0xFE00;
+
+    let pixel_format = [
+        DDPF_ALPHAPIXELS_RGB,
+        0,
+        32,
+        0xFF,
+        0xFF00,
+        0xFF_0000,
+        0xFF00_0000,
+    ];
+    let payload: Vec<u8> = (0..6 * 2 * 2 * 4).map(|i| i as u8).collect();
+    let file = dds_file(
+        2,
+        2,
+        1,
+        pixel_format,
+        DDSCAPS2_CUBEMAP_WITH_ALL_FACES,
+        None,
+        &payload,
+    );
+
+    let texture = Ktx2Texture::from_dds_memory(&file).unwrap();
+    assert_eq!(texture.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert!(texture.is_cubemap());
+    for face in 0..6 {
+        let start = face * 16;
+        assert_eq!(
+            texture.get_image_data(0, 0, face as u32).unwrap(),
+            &payload[start..start + 16]
+        );
+    }
+
+    assert!(matches!(
+        Ktx2Texture::from_dds_memory(&file[..file.len() - 1]),
+        Err(Error::FileUnexpectedEof)
+    ));
+    assert!(matches!(
+        Ktx2Texture::from_dds_memory(b"KTX 20"),
+        Err(Error::UnknownFileFormat)
+    ));
+}
+
+#[cfg(feature = "dds")]
+#[test]
+fn test_texture_from_dds_unsupported() {
+    const DDPF_FOURCC: u32 = 0x4;
+    const DDSCAPS2_CUBEMAP_POSITIVEX: u32 = 0x600;
+    const DDSCAPS2_VOLUME: u32 = 0x20_0000;
+    const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+
+    let rgba8 = [0x41, 0, 32, 0xFF, 0xFF00, 0xFF_0000, 0xFF00_0000];
+    let dxt3 = [DDPF_FOURCC, u32::from_le_bytes(*b"DXT3"), 0, 0, 0, 0, 0];
+    let dx10 = [DDPF_FOURCC, u32::from_le_bytes(*b"DX10"), 0, 0, 0, 0, 0];
+    let unsupported = [
+        dds_file(4, 4, 1, dxt3, 0, None, &[0; 16]),
+        dds_file(2, 2, 1, rgba8, DDSCAPS2_VOLUME, None, &[0; 32]),
+        dds_file(2, 2, 1, rgba8, DDSCAPS2_CUBEMAP_POSITIVEX, None, &[0; 16]),
+        dds_file(
+            4,
+            1,
+            1,
+            dx10,
+            0,
+            Some([28, D3D10_RESOURCE_DIMENSION_TEXTURE1D, 0, 1, 0]),
+            &[0; 16],
+        ),
+        dds_file(4, 4, 1, dx10, 0, Some([70, 3, 0, 1, 0]), &[0; 8]),
+    ];
+    for file in unsupported {
+        assert!(matches!(
+            Ktx2Texture::from_dds_memory(&file),
+            Err(Error::UnsupportedFeature)
+        ));
+    }
+
+    // A huge declared array is rejected before any storage is allocated
+    let file = dds_file(
+        4096,
+        4096,
+        1,
+        dx10,
+        0,
+        Some([28, 3, 0, 1 << 20, 0]),
+        &[0; 64],
+    );
+    assert!(matches!(
+        Ktx2Texture::from_dds_memory(&file),
+        Err(Error::FileUnexpectedEof)
+    ));
+}
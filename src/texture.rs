@@ -1,14 +1,34 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::CString;
 use std::fmt;
-use std::path::Path;
+use std::io::{Read, Seek, Write};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 
+use crate::anim_data::AnimData;
 use crate::bindings::*;
-use crate::compression::BasisCompressionParams;
+use crate::compression::{
+    AstcParams, BasisCompressionParams, CompressionMode, CompressionProgress, CompressionReport,
+    CompressionStage,
+};
+use crate::cube_face::CubeFace;
+use crate::dfd::DataFormatDescriptor;
 use crate::error::{Error, Result};
-use crate::format::TranscodeFormat;
+use crate::format::{SupercompressionScheme, TranscodeFormat, TransferFunction};
+use crate::options::{LoadOptions, TextureCreateFlags, WriteOptions};
+use crate::orientation::{Orientation, XOrient, YOrient, ZOrient};
+use crate::stream::StreamAdapter;
+use crate::validation::{ValidationWarning, RESERVED_KEYS, STRING_KEYS};
 use crate::vk_format::VkFormat;
 
+/// Metadata key naming the tool that wrote the file
+const WRITER_KEY: &str = "KTXwriter";
+
 /// Main texture handle for KTX2 textures
 ///
 /// This struct provides a safe, high-level interface to KTX2 textures with automatic
@@ -43,49 +63,345 @@ use crate::vk_format::VkFormat;
 /// ```
 pub struct Ktx2Texture {
     texture: *mut ktxTexture2,
+    /// Bytes behind the libktx memory stream, kept while libktx may read them
+    source: Option<Source>,
+}
+
+/// Backing storage for a texture created from memory
+enum Source {
+    /// Copy of the caller's bytes for image data that is loaded later
+    Owned(Box<[u8]>),
+    /// Mapping created by [`Ktx2Texture::from_file_mmap`]
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+    /// Buffer passed to [`Ktx2Texture::from_vec`]; `pData` points into it
+    Aliased(Vec<u8>),
+    /// File opened without its image data; libktx reads it directly, and the
+    /// path lets [`Ktx2Texture::try_clone`] reopen it while its stamp still
+    /// matches the one taken when it was opened
+    #[cfg(not(windows))]
+    File { path: PathBuf, stamp: FileStamp },
+}
+
+/// Size and modification time of a file, used to notice it was rewritten
+#[cfg(not(windows))]
+type FileStamp = (u64, Option<std::time::SystemTime>);
+
+impl Source {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Source::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Source::Mapped(mapping) => mapping,
+            Source::Aliased(bytes) => bytes,
+            #[cfg(not(windows))]
+            Source::File { .. } => &[],
+        }
+    }
 }
 
 impl Ktx2Texture {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path_str = path.as_ref().to_str().ok_or(Error::InvalidValue)?;
-        let c_path = CString::new(path_str).map_err(|_| Error::InvalidValue)?;
+        Self::from_file_with_flags(path, TextureCreateFlags::LOAD_IMAGE_DATA)
+    }
+
+    /// Loads a texture from a file, passing `flags` to libktx
+    ///
+    /// Without [`TextureCreateFlags::LOAD_IMAGE_DATA`] only the header, level
+    /// index and metadata are read; the image data accessors then return
+    /// [`Error::InvalidOperation`].
+    pub fn from_file_with_flags<P: AsRef<Path>>(
+        path: P,
+        flags: TextureCreateFlags,
+    ) -> Result<Self> {
+        // libktx opens files with fopen(), which only takes ANSI paths on Windows
+        #[cfg(windows)]
+        {
+            let data = std::fs::read(path).map_err(|_| Error::FileOpenFailed)?;
+            Self::from_memory_with_flags(&data, flags)
+        }
+
+        #[cfg(not(windows))]
+        {
+            let c_path = c_path(path.as_ref())?;
+
+            let mut texture: *mut ktxTexture2 = ptr::null_mut();
+
+            let result = unsafe {
+                ktxTexture2_CreateFromNamedFile(c_path.as_ptr(), flags.bits(), &mut texture)
+            };
+
+            if let Some(error) = Error::try_from_code(result) {
+                return Err(error);
+            }
 
+            let source = if flags.contains(TextureCreateFlags::LOAD_IMAGE_DATA) {
+                None
+            } else {
+                let path = path.as_ref();
+                let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+                file_stamp(&path)
+                    .ok()
+                    .map(|stamp| Source::File { path, stamp })
+            };
+            Ok(Self { texture, source })
+        }
+    }
+
+    /// Loads a texture from a file according to `options`
+    pub fn from_file_with_options<P: AsRef<Path>>(path: P, options: LoadOptions) -> Result<Self> {
+        Self::from_file_with_flags(path, options.into())
+    }
+
+    pub fn from_memory(data: &[u8]) -> Result<Self> {
+        Self::from_memory_with_flags(data, TextureCreateFlags::LOAD_IMAGE_DATA)
+    }
+
+    /// Loads a texture from memory, passing `flags` to libktx
+    ///
+    /// See [`from_file_with_flags`](Self::from_file_with_flags).
+    ///
+    /// When the image data is not loaded up front the bytes are copied and kept
+    /// with the texture so [`load_image_data`](Self::load_image_data) can read
+    /// them later.
+    pub fn from_memory_with_flags(data: &[u8], flags: TextureCreateFlags) -> Result<Self> {
+        // libktx reads deferred image data from the stream over these bytes
+        let source = if flags.contains(TextureCreateFlags::LOAD_IMAGE_DATA) {
+            None
+        } else {
+            Some(Source::Owned(data.into()))
+        };
+        let bytes = source.as_ref().map_or(data, Source::as_bytes);
+
+        // Safety: `bytes` is either retained in `source` or fully read up front
+        let mut texture = unsafe { Self::from_memory_unretained(bytes, flags)? };
+        texture.source = source;
+        Ok(texture)
+    }
+
+    /// Creates a texture over `data` without copying or retaining it
+    ///
+    /// # Safety
+    ///
+    /// Unless `flags` loads the image data, libktx keeps reading from `data`, so
+    /// it must outlive the returned texture.
+    pub(crate) unsafe fn from_memory_unretained(
+        data: &[u8],
+        flags: TextureCreateFlags,
+    ) -> Result<Self> {
         let mut texture: *mut ktxTexture2 = ptr::null_mut();
 
+        let result =
+            ktxTexture2_CreateFromMemory(data.as_ptr(), data.len(), flags.bits(), &mut texture);
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(Self {
+            texture,
+            source: None,
+        })
+    }
+
+    /// Loads a KTX (version 1) file and converts it to KTX2
+    ///
+    /// See [`from_ktx1_memory`](Self::from_ktx1_memory).
+    pub fn from_ktx1_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = std::fs::read(path).map_err(|_| Error::FileOpenFailed)?;
+        Self::from_ktx1_memory(&data)
+    }
+
+    /// Loads a KTX (version 1) file from memory and converts it to KTX2
+    ///
+    /// libktx maps the GL internal format to the equivalent `VkFormat` and
+    /// carries the metadata over. GL formats without a Vulkan equivalent return
+    /// [`Error::UnsupportedTextureType`], and data that is not KTX1, including
+    /// KTX2 files, returns [`Error::UnknownFileFormat`].
+    /// [`from_memory`](Self::from_memory) never converts and rejects KTX1 data
+    /// with [`Error::UnknownFileFormat`].
+    pub fn from_ktx1_memory(data: &[u8]) -> Result<Self> {
+        let mut texture: *mut ktxTexture = ptr::null_mut();
+
         let result = unsafe {
-            ktxTexture2_CreateFromNamedFile(
-                c_path.as_ptr(),
-                ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_LOAD_IMAGE_DATA_BIT,
+            ktxTexture_CreateFromMemory(
+                data.as_ptr(),
+                data.len(),
+                TextureCreateFlags::LOAD_IMAGE_DATA.bits(),
                 &mut texture,
             )
         };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        // libktx does not export ktxTexture1_Destroy, so go through the vtable
+        let destroy = |texture: *mut ktxTexture| unsafe {
+            if let Some(destroy) = (*(*texture).vtbl).Destroy {
+                destroy(texture);
+            }
+        };
+
+        if unsafe { (*texture).classId } != class_id_ktxTexture1_c {
+            destroy(texture);
+            return Err(Error::UnknownFileFormat);
+        }
+
+        let mut bytes: *mut ktx_uint8_t = ptr::null_mut();
+        let mut size: ktx_size_t = 0;
+        let result = unsafe {
+            ktxTexture1_WriteKTX2ToMemory(texture as *mut ktxTexture1, &mut bytes, &mut size)
+        };
+        destroy(texture);
+
+        // libktx reports GL formats without a VkFormat as an invalid operation
+        if result == ktx_error_code_e_KTX_INVALID_OPERATION {
+            return Err(Error::UnsupportedTextureType);
+        }
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        if bytes.is_null() {
+            return Err(Error::InvalidOperation);
         }
 
-        Ok(Self { texture })
+        unsafe {
+            let converted = Self::from_memory(std::slice::from_raw_parts(bytes, size));
+            // libktx allocates the output with malloc()
+            libc::free(bytes as *mut libc::c_void);
+            converted
+        }
     }
 
-    pub fn from_memory(data: &[u8]) -> Result<Self> {
+    /// Loads a texture from a buffer the caller no longer needs
+    ///
+    /// The buffer is kept inside the texture and, when the file is not
+    /// supercompressed with Zstandard or zlib, the image data is used in place
+    /// rather than copied into a libktx allocation. Supercompressed payloads
+    /// have to be inflated, so those are still copied and `data` is freed once
+    /// loading finishes.
+    ///
+    /// Operations that make libktx replace the image data, such as
+    /// [`compress_basis`](Self::compress_basis) or
+    /// [`deflate_zstd`](Self::deflate_zstd), first copy it into a libktx
+    /// allocation and release the buffer.
+    pub fn from_vec(data: Vec<u8>) -> Result<Self> {
+        let source = Source::Aliased(data);
+
+        // Safety: `source` moves into the texture, and moving a Vec does not
+        // move its heap allocation
+        let mut texture =
+            unsafe { Self::from_memory_unretained(source.as_bytes(), TextureCreateFlags::NONE)? };
+        let first_level = match texture.supercompression_scheme() {
+            SupercompressionScheme::Zstd | SupercompressionScheme::Zlib => None,
+            _ => first_level_offset(source.as_bytes(), texture.levels()),
+        };
+        texture.source = Some(source);
+
+        let Some(offset) = first_level else {
+            texture.load_image_data()?;
+            return Ok(texture);
+        };
+
+        let bytes = texture.source.as_ref().map_or(&[][..], Source::as_bytes);
+        match offset.checked_add(texture.data_size()) {
+            Some(end) if end <= bytes.len() => {}
+            _ => return Err(Error::FileDataError),
+        }
+
+        // Safety: the range was checked above; Drop and detach_image_data clear
+        // `pData` before libktx could free it. Image edits write through
+        // `pData`, so it is derived from a mutable borrow of the buffer
+        if let Some(Source::Aliased(bytes)) = &mut texture.source {
+            unsafe {
+                (*texture.texture).pData = bytes.as_mut_ptr().add(offset);
+            }
+        }
+
+        Ok(texture)
+    }
+
+    /// Loads a texture from memory according to `options`
+    pub fn from_memory_with_options(data: &[u8], options: LoadOptions) -> Result<Self> {
+        Self::from_memory_with_flags(data, options.into())
+    }
+
+    /// Loads a texture by memory-mapping the file instead of reading it
+    ///
+    /// libktx parses the mapping directly, so large files are not first copied
+    /// into a Rust buffer. The mapping is kept alive by the returned texture.
+    ///
+    /// # Caveat
+    ///
+    /// The file must not be truncated or modified by another process while the
+    /// texture is alive. Doing so may crash the process (e.g. with `SIGBUS`) or
+    /// produce corrupt data, since the mapping reflects the file as it changes.
+    #[cfg(feature = "mmap")]
+    pub fn from_file_mmap<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = std::fs::File::open(path).map_err(|_| Error::FileOpenFailed)?;
+        // Safety: see the caveat above; the mapping is never written through
+        let source = Source::Mapped(unsafe { memmap2::Mmap::map(&file) }?);
+        let bytes = source.as_bytes();
+
         let mut texture: *mut ktxTexture2 = ptr::null_mut();
 
         let result = unsafe {
             ktxTexture2_CreateFromMemory(
-                data.as_ptr(),
-                data.len(),
+                bytes.as_ptr(),
+                bytes.len(),
+                ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_LOAD_IMAGE_DATA_BIT,
+                &mut texture,
+            )
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(Self {
+            texture,
+            source: Some(source),
+        })
+    }
+
+    /// Loads a texture from any seekable reader without buffering it first
+    ///
+    /// Read and seek failures are reported as [`Error::FileReadError`] and
+    /// [`Error::FileSeekError`]. A panic in `reader` is resumed once libktx has
+    /// returned rather than unwinding through it.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+        let mut adapter = StreamAdapter::new(reader);
+        let mut stream = adapter.reader_stream();
+        let mut texture: *mut ktxTexture2 = ptr::null_mut();
+
+        let result = unsafe {
+            ktxTexture2_CreateFromStream(
+                &mut stream,
                 ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_LOAD_IMAGE_DATA_BIT,
                 &mut texture,
             )
         };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        // Wrap first so the texture is freed if a caught panic is resumed
+        let texture = Self {
+            texture,
+            source: None,
+        };
+        let _ = adapter.finish();
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
         }
 
-        Ok(Self { texture })
+        Ok(texture)
     }
 
+    /// Creates a new texture with allocated image storage
+    ///
+    /// `vk_format` accepts either a [`VkFormat`] or a raw `VkFormat` value. An
+    /// undefined (zero) format is rejected with [`Error::InvalidValue`].
     pub fn create(
         width: u32,
         height: u32,
@@ -93,8 +409,271 @@ impl Ktx2Texture {
         layers: u32,
         faces: u32,
         levels: u32,
-        vk_format: VkFormat,
+        vk_format: impl Into<u32>,
+    ) -> Result<Self> {
+        Self::builder()
+            .dimensions(width, height)
+            .depth(depth)
+            .layers(layers)
+            .faces(faces)
+            .levels(levels)
+            .format(vk_format)
+            .build()
+    }
+
+    /// Creates a single-level 2D texture from pixels in `vk_format`
+    ///
+    /// `pixels` must be exactly one `width` x `height` image, otherwise
+    /// [`Error::ImageSizeMismatch`] is returned.
+    pub fn from_pixels(
+        width: u32,
+        height: u32,
+        vk_format: impl Into<u32>,
+        pixels: &[u8],
+    ) -> Result<Self> {
+        let mut texture = Self::create(width, height, 1, 1, 1, 1, vk_format)?;
+        texture.set_image_data(0, 0, 0, pixels)?;
+        Ok(texture)
+    }
+
+    /// Creates a single-level `R32G32B32A32Sfloat` texture from RGBA pixels
+    ///
+    /// `pixels` holds four floats per texel; any other length returns
+    /// [`Error::ImageSizeMismatch`] with sizes in bytes. The values are stored
+    /// bit for bit, so HDR data survives unchanged. Basis Universal cannot
+    /// encode float data; see [`compress_basis`](Self::compress_basis).
+    pub fn from_rgba_f32(width: u32, height: u32, pixels: &[f32]) -> Result<Self> {
+        let bytes: Vec<u8> = pixels.iter().flat_map(|v| v.to_le_bytes()).collect();
+        Self::from_pixels(width, height, VkFormat::R32G32B32A32Sfloat, &bytes)
+    }
+
+    /// Creates a single-level `R16G16B16A16Sfloat` texture from RGBA pixels
+    ///
+    /// Each `u16` is the bit pattern of an IEEE-754 half-precision float, as
+    /// produced by `half::f16::to_bits`. Validates like
+    /// [`from_rgba_f32`](Self::from_rgba_f32).
+    pub fn from_rgba_f16(width: u32, height: u32, pixels: &[u16]) -> Result<Self> {
+        let bytes: Vec<u8> = pixels.iter().flat_map(|v| v.to_le_bytes()).collect();
+        Self::from_pixels(width, height, VkFormat::R16G16B16A16Sfloat, &bytes)
+    }
+
+    /// Creates a single-level `R8G8B8A8Unorm` texture from RGBA8 pixels
+    ///
+    /// See [`from_pixels`](Self::from_pixels).
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> Result<Self> {
+        Self::from_pixels(width, height, VkFormat::R8G8B8A8Unorm, pixels)
+    }
+
+    /// Creates a single-level `R8G8B8A8Srgb` texture from RGBA8 pixels
+    ///
+    /// See [`from_pixels`](Self::from_pixels).
+    pub fn from_rgba8_srgb(width: u32, height: u32, pixels: &[u8]) -> Result<Self> {
+        Self::from_pixels(width, height, VkFormat::R8G8B8A8Srgb, pixels)
+    }
+
+    /// Creates a 1D texture, or a 1D array when `layers` is greater than 1
+    ///
+    /// A zero `width`, `layers` or `levels`, or more levels than a full mip
+    /// chain of `width` has, returns [`Error::InvalidValue`].
+    pub fn create_1d(
+        width: u32,
+        layers: u32,
+        levels: u32,
+        vk_format: impl Into<u32>,
+    ) -> Result<Self> {
+        if levels > Self::full_mip_levels(width, 1, 1) {
+            return Err(Error::InvalidValue);
+        }
+
+        Self::builder()
+            .dimensions(width, 1)
+            .array_layers(layers)
+            .levels(levels)
+            .format(vk_format)
+            .num_dimensions(1)
+            .build()
+    }
+
+    /// Creates a cubemap with six `size` x `size` faces
+    ///
+    /// A zero `size` or `levels`, or more levels than a full mip chain of
+    /// `size` has, returns [`Error::InvalidValue`].
+    pub fn create_cubemap(size: u32, levels: u32, vk_format: impl Into<u32>) -> Result<Self> {
+        Self::create_cubemap_array(size, 1, levels, vk_format)
+    }
+
+    /// Creates an array of `layers` cubemaps with six `size` x `size` faces each
+    ///
+    /// Validates like [`create_cubemap`](Self::create_cubemap), and additionally
+    /// rejects zero `layers` with [`Error::InvalidValue`].
+    pub fn create_cubemap_array(
+        size: u32,
+        layers: u32,
+        levels: u32,
+        vk_format: impl Into<u32>,
+    ) -> Result<Self> {
+        if size == 0 || layers == 0 || levels == 0 {
+            return Err(Error::InvalidValue);
+        }
+        if levels > Self::full_mip_levels(size, size, 1) {
+            return Err(Error::InvalidValue);
+        }
+
+        Self::create(size, size, 1, layers, 6, levels, vk_format)
+    }
+
+    /// Creates a single-level cubemap from six faces in +X, -X, +Y, -Y, +Z, -Z
+    /// order
+    ///
+    /// Each face must be exactly one `size` x `size` image of `vk_format`,
+    /// otherwise [`Error::ImageSizeMismatch`] is returned.
+    pub fn cubemap_from_faces(
+        size: u32,
+        vk_format: impl Into<u32>,
+        faces: [&[u8]; 6],
+    ) -> Result<Self> {
+        Self::cubemap_from_faces_with_levels(size, 1, vk_format, faces)
+    }
+
+    /// Like [`cubemap_from_faces`](Self::cubemap_from_faces), but allocates
+    /// `levels` mip levels
+    ///
+    /// `faces` fills the base level; the smaller levels start zeroed and can be
+    /// filled with [`set_face_data`](Self::set_face_data).
+    pub fn cubemap_from_faces_with_levels(
+        size: u32,
+        levels: u32,
+        vk_format: impl Into<u32>,
+        faces: [&[u8]; 6],
+    ) -> Result<Self> {
+        let mut texture = Self::create_cubemap(size, levels, vk_format)?;
+        for (face, data) in CubeFace::ALL.into_iter().zip(faces) {
+            texture.set_face_data(0, 0, face, data)?;
+        }
+        Ok(texture)
+    }
+
+    /// Creates a new texture described by a caller-supplied data format
+    /// descriptor
+    ///
+    /// Unlike [`create`](Self::create), libktx takes the texel layout from `dfd`
+    /// instead of deriving it from `vk_format`, which lets you describe formats
+    /// libktx does not know or tag channels differently. `vk_format` may be
+    /// [`VkFormat::Undefined`] for formats without a Vulkan equivalent.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_dfd(
+        width: u32,
+        height: u32,
+        depth: u32,
+        layers: u32,
+        faces: u32,
+        levels: u32,
+        vk_format: impl Into<u32>,
+        dfd: &DataFormatDescriptor,
+    ) -> Result<Self> {
+        let builder = Self::builder()
+            .dimensions(width, height)
+            .depth(depth)
+            .layers(layers)
+            .faces(faces)
+            .levels(levels)
+            .format(vk_format);
+        Self::create_impl(
+            &builder,
+            Some(dfd),
+            ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_ALLOC_STORAGE,
+        )
+    }
+
+    /// Creates a texture whose image data is `data`, taking ownership of it
+    ///
+    /// `data` must hold every image in the order and with the level padding
+    /// libktx uses, i.e. exactly what [`data_size`](Self::data_size) and
+    /// [`get_image_data`](Self::get_image_data) describe for a texture made by
+    /// [`create`](Self::create) with the same arguments. Any other length
+    /// returns [`Error::ImageSizeMismatch`]. The buffer is used in place, as
+    /// with [`from_vec`](Self::from_vec), so nothing is copied.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_from_raw_level_data(
+        width: u32,
+        height: u32,
+        depth: u32,
+        layers: u32,
+        faces: u32,
+        levels: u32,
+        vk_format: impl Into<u32>,
+        mut data: Vec<u8>,
+    ) -> Result<Self> {
+        let builder = Self::builder()
+            .dimensions(width, height)
+            .depth(depth)
+            .layers(layers)
+            .faces(faces)
+            .levels(levels)
+            .format(vk_format);
+        if builder.vk_format == VkFormat::Undefined.as_raw() {
+            return Err(Error::InvalidValue);
+        }
+        let mut texture = Self::create_impl(
+            &builder,
+            None,
+            ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_NO_STORAGE,
+        )?;
+
+        // Without storage libktx leaves dataSize at zero, but the level index
+        // is laid out, so the end of the last level gives the expected size
+        let mut expected = 0;
+        for level in 0..levels {
+            let mut offset = 0;
+            let result =
+                unsafe { ktxTexture2_GetImageOffset(texture.texture, level, 0, 0, &mut offset) };
+            if let Some(error) = Error::try_from_code(result) {
+                return Err(error);
+            }
+            let size = unsafe {
+                match (*(*texture.texture).vtbl).GetLevelSize {
+                    Some(func) => func(texture.texture as *mut ktxTexture, level),
+                    None => return Err(Error::UnsupportedFeature),
+                }
+            };
+            expected = expected.max(offset + size);
+        }
+        if data.len() != expected {
+            return Err(Error::ImageSizeMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        // Safety: moving the Vec into the texture does not move its heap
+        // allocation; Drop and detach_image_data clear `pData` before libktx
+        // could free it. Image edits write through `pData`, so it is derived
+        // from a mutable borrow of the buffer
+        unsafe {
+            (*texture.texture).pData = data.as_mut_ptr();
+            (*texture.texture).dataSize = expected;
+        }
+        texture.source = Some(Source::Aliased(data));
+
+        Ok(texture)
+    }
+
+    fn create_impl(
+        builder: &Ktx2TextureBuilder,
+        dfd: Option<&DataFormatDescriptor>,
+        storage: ktxTextureCreateStorageEnum,
     ) -> Result<Self> {
+        let Ktx2TextureBuilder {
+            width,
+            height,
+            depth,
+            layers,
+            faces,
+            levels,
+            vk_format,
+            num_dimensions,
+        } = *builder;
+
         // Validate input parameters
         if width == 0 || height == 0 {
             return Err(Error::InvalidValue);
@@ -109,20 +688,32 @@ impl Ktx2Texture {
             return Err(Error::InvalidValue);
         }
 
+        let inferred_dimensions = if depth > 1 {
+            3
+        } else if height > 1 {
+            2
+        } else {
+            1
+        };
+        let num_dimensions = match num_dimensions {
+            None => inferred_dimensions,
+            Some(n @ 1..=3) if n >= inferred_dimensions => n,
+            Some(_) => return Err(Error::InvalidValue),
+        };
+
+        // libktx copies the descriptor, so it only has to outlive the call
+        let mut dfd_words = dfd.map(DataFormatDescriptor::to_words).transpose()?;
+
         let create_info = ktxTextureCreateInfo {
             glInternalformat: 0,
-            vkFormat: vk_format.as_raw(),
-            pDfd: ptr::null_mut(),
+            vkFormat: vk_format,
+            pDfd: dfd_words
+                .as_mut()
+                .map_or(ptr::null_mut(), |words| words.as_mut_ptr()),
             baseWidth: width,
             baseHeight: height,
             baseDepth: depth,
-            numDimensions: if depth > 1 {
-                3
-            } else if height > 1 {
-                2
-            } else {
-                1
-            },
+            numDimensions: num_dimensions,
             numLevels: levels,
             numLayers: layers,
             numFaces: faces,
@@ -132,16 +723,10 @@ impl Ktx2Texture {
 
         let mut texture: *mut ktxTexture2 = ptr::null_mut();
 
-        let result = unsafe {
-            ktxTexture2_Create(
-                &create_info,
-                ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_ALLOC_STORAGE,
-                &mut texture,
-            )
-        };
+        let result = unsafe { ktxTexture2_Create(&create_info, storage, &mut texture) };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
         }
 
         // Safety: Check that texture was actually created
@@ -149,7 +734,277 @@ impl Ktx2Texture {
             return Err(Error::OutOfMemory);
         }
 
-        Ok(Self { texture })
+        Ok(Self {
+            texture,
+            source: None,
+        })
+    }
+
+    /// Copies the mip levels in `range` into a new texture whose base level is
+    /// `range.start`
+    ///
+    /// Layers, faces, format, data format descriptor and metadata are kept. An
+    /// empty range or one reaching past [`levels()`](Self::levels) returns
+    /// [`Error::InvalidValue`]. Zstd/zlib data is inflated first; BasisLZ
+    /// data cannot be split and returns [`Error::InvalidOperation`], as does a
+    /// texture without loaded image data.
+    pub fn extract_levels(&self, range: Range<u32>) -> Result<Self> {
+        if range.is_empty() || range.end > self.levels() {
+            return Err(Error::InvalidValue);
+        }
+        match self.supercompression_scheme() {
+            SupercompressionScheme::None => {}
+            SupercompressionScheme::Zstd | SupercompressionScheme::Zlib => {
+                return self.inflated()?.extract_levels(range);
+            }
+            _ => return Err(Error::InvalidOperation),
+        }
+        if !self.has_image_data() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let first = range.start;
+        let source = unsafe { &*self.texture };
+        let builder = Self::builder()
+            .dimensions(
+                (source.baseWidth >> first).max(1),
+                (source.baseHeight >> first).max(1),
+            )
+            .depth((source.baseDepth >> first).max(1))
+            .layers(source.numLayers)
+            .faces(source.numFaces)
+            .levels(range.len() as u32)
+            .format(source.vkFormat)
+            .num_dimensions(source.numDimensions);
+        let mut texture = Self::create_impl(
+            &builder,
+            Some(&self.dfd()?),
+            ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_ALLOC_STORAGE,
+        )?;
+
+        unsafe {
+            (*texture.texture).isArray = source.isArray;
+            for level in range {
+                let (from, size) = self.level_range(level)?;
+                let (to, to_size) = texture.level_range(level - first)?;
+                debug_assert_eq!(size, to_size);
+                ptr::copy_nonoverlapping(
+                    source.pData.add(from),
+                    (*texture.texture).pData.add(to),
+                    size,
+                );
+            }
+        }
+
+        for (key, value) in self.metadata_iter() {
+            texture.set_metadata(&key, value)?;
+        }
+        Ok(texture)
+    }
+
+    /// Returns `true` if both textures hold the same images and metadata
+    ///
+    /// Compares dimensions, format and every image of every level, layer and
+    /// face (or depth slice), then the metadata entries ignoring order. The
+    /// `KTXwriter` entry is skipped since libktx rewrites it on every write; use
+    /// [`content_eq_with_writer`](Self::content_eq_with_writer) to include it.
+    ///
+    /// # Cost
+    ///
+    /// Every image is compared byte by byte, so this walks all of the image data
+    /// of both textures. Zstd/zlib supercompressed textures are additionally
+    /// serialized and inflated first. Textures without loaded image data never
+    /// compare equal.
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.content_eq_impl(other, false)
+    }
+
+    /// Like [`content_eq`](Self::content_eq), but also requires the `KTXwriter`
+    /// entries to match
+    pub fn content_eq_with_writer(&self, other: &Self) -> bool {
+        self.content_eq_impl(other, true)
+    }
+
+    fn content_eq_impl(&self, other: &Self, compare_writer: bool) -> bool {
+        if self.texture.is_null() || other.texture.is_null() {
+            return false;
+        }
+
+        let (a, b) = unsafe { (&*self.texture, &*other.texture) };
+        if a.vkFormat != b.vkFormat
+            || a.baseWidth != b.baseWidth
+            || a.baseHeight != b.baseHeight
+            || a.baseDepth != b.baseDepth
+            || a.numLevels != b.numLevels
+            || a.numLayers != b.numLayers
+            || a.numFaces != b.numFaces
+            || a.supercompressionScheme != b.supercompressionScheme
+        {
+            return false;
+        }
+
+        // Zstd/zlib payloads are only inflated by libktx when loading
+        let scheme = a.supercompressionScheme;
+        if scheme == ktxSupercmpScheme_KTX_SS_ZSTD || scheme == ktxSupercmpScheme_KTX_SS_ZLIB {
+            return match (self.inflated(), other.inflated()) {
+                (Ok(a), Ok(b)) => a.content_eq_impl(&b, compare_writer),
+                _ => false,
+            };
+        }
+
+        for (level, layer, slice) in self.image_indices() {
+            match (
+                self.get_image_data(level, layer, slice),
+                other.get_image_data(level, layer, slice),
+            ) {
+                (Ok(a), Ok(b)) if a == b => {}
+                _ => return false,
+            }
+        }
+
+        fn metadata(texture: &Ktx2Texture, compare_writer: bool) -> Vec<(String, &[u8])> {
+            let mut entries: Vec<_> = texture
+                .metadata_iter()
+                .filter(|(key, _)| compare_writer || key != WRITER_KEY)
+                .collect();
+            entries.sort();
+            entries
+        }
+        metadata(self, compare_writer) == metadata(other, compare_writer)
+    }
+
+    /// Returns the number of mip levels in a full chain down to 1x1x1
+    ///
+    /// Follows the KTX2 rule `floor(log2(max(width, height, depth))) + 1`.
+    pub fn full_mip_levels(width: u32, height: u32, depth: u32) -> u32 {
+        let largest = width.max(height).max(depth).max(1);
+        u32::BITS - largest.leading_zeros()
+    }
+
+    /// Creates a new builder for a texture
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ktx2_rw::{Ktx2Texture, VkFormat};
+    /// # fn main() -> ktx2_rw::Result<()> {
+    ///
+    /// let texture = Ktx2Texture::builder()
+    ///     .dimensions(512, 512)
+    ///     .format(VkFormat::R8G8B8A8Unorm)
+    ///     .levels(10)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> Ktx2TextureBuilder {
+        Ktx2TextureBuilder::default()
+    }
+
+    /// Creates an independent deep copy of the texture
+    ///
+    /// Image data, metadata, compression state and the supercompression scheme
+    /// are all copied. Textures opened without their image data are re-parsed,
+    /// from the retained bytes or by reopening the file, and keep their current
+    /// metadata. Reopening fails with [`Error::FileDataError`] if the file's
+    /// size or modification time changed since it was opened, and with an I/O
+    /// error if it is gone. Textures from [`from_raw`](Self::from_raw) without
+    /// image data return [`Error::InvalidOperation`].
+    ///
+    /// `Ktx2Texture` does not implement `Clone`, since copying can fail.
+    pub fn try_clone(&self) -> Result<Self> {
+        if self.has_image_data() {
+            return self.duplicate();
+        }
+
+        let mut copy = match &self.source {
+            #[cfg(not(windows))]
+            Some(Source::File { path, stamp }) => {
+                let copy = Self::from_file_with_flags(path, TextureCreateFlags::NONE)?;
+                // Checked after reopening, so the header read matches the stamp
+                if file_stamp(path).ok() != Some(*stamp) {
+                    return Err(Error::FileDataError);
+                }
+                copy
+            }
+            Some(source) => {
+                Self::from_memory_with_flags(source.as_bytes(), TextureCreateFlags::NONE)?
+            }
+            None => return Err(Error::InvalidOperation),
+        };
+
+        // The metadata may have been edited since the texture was opened
+        for key in copy.metadata_keys()? {
+            match copy.delete_metadata(&key) {
+                Ok(()) | Err(Error::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        for (key, value) in self.metadata_iter() {
+            copy.set_metadata(&key, value)?;
+        }
+        Ok(copy)
+    }
+
+    /// Returns the underlying `ktxTexture2` without giving up ownership
+    ///
+    /// Useful for calling libktx functions the wrapper does not cover. The
+    /// pointer stays valid as long as `self` and must not be destroyed. Only
+    /// mutate the texture through it while no references returned by `self`,
+    /// such as [`get_image_data`](Self::get_image_data) slices, are alive, and
+    /// do not replace or free its image data.
+    pub fn as_raw(&self) -> *mut ktxTexture2 {
+        self.texture
+    }
+
+    /// Releases ownership of the underlying `ktxTexture2`
+    ///
+    /// The wrapper is consumed without running its destructor, so the caller
+    /// becomes responsible for destroying the texture, either with
+    /// `ktxTexture2_Destroy` or by handing it back to [`from_raw`](Self::from_raw).
+    ///
+    /// Image data that still lives in a buffer owned by the wrapper, as with
+    /// [`from_vec`](Self::from_vec) or deferred loading, is copied or loaded into
+    /// libktx's own storage first. If that fails the buffer is leaked rather than
+    /// freed, so the returned texture never points at released memory.
+    pub fn into_raw(mut self) -> *mut ktxTexture2 {
+        let loaded = self
+            .detach_image_data()
+            .and_then(|()| self.load_image_data());
+        let source = self.source.take();
+        let texture = ManuallyDrop::new(self).texture;
+
+        match loaded {
+            Ok(()) => drop(source),
+            Err(_) => std::mem::forget(source),
+        }
+
+        texture
+    }
+
+    /// Takes ownership of a `ktxTexture2` created by libktx
+    ///
+    /// The texture is destroyed with `ktxTexture2_Destroy` when the wrapper is
+    /// dropped.
+    ///
+    /// # Safety
+    ///
+    /// `texture` must be a valid, non-null pointer returned by one of the libktx
+    /// `ktxTexture2_Create*` functions or by [`into_raw`](Self::into_raw). Nothing
+    /// else may destroy or use it afterwards, and any memory it reads from, such
+    /// as the source of a texture whose image data is not loaded yet, must
+    /// outlive the wrapper.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `texture` is null.
+    pub unsafe fn from_raw(texture: *mut ktxTexture2) -> Self {
+        assert!(!texture.is_null(), "Ktx2Texture::from_raw called with null");
+
+        Self {
+            texture,
+            source: None,
+        }
     }
 
     pub fn width(&self) -> u32 {
@@ -173,6 +1028,19 @@ impl Ktx2Texture {
         unsafe { (*self.texture).baseDepth }
     }
 
+    /// Returns the number of dimensions: 1, 2 or 3
+    pub fn num_dimensions(&self) -> u32 {
+        if self.texture.is_null() {
+            return 0;
+        }
+        unsafe { (*self.texture).numDimensions }
+    }
+
+    /// Returns `true` for 1D textures and 1D texture arrays
+    pub fn is_1d(&self) -> bool {
+        self.num_dimensions() == 1
+    }
+
     pub fn layers(&self) -> u32 {
         if self.texture.is_null() {
             return 0;
@@ -201,6 +1069,14 @@ impl Ktx2Texture {
         unsafe { VkFormat::from_raw((*self.texture).vkFormat).unwrap_or(VkFormat::Undefined) }
     }
 
+    /// Returns the supercompression scheme applied to the image data
+    pub fn supercompression_scheme(&self) -> SupercompressionScheme {
+        if self.texture.is_null() {
+            return SupercompressionScheme::None;
+        }
+        unsafe { SupercompressionScheme::from_raw((*self.texture).supercompressionScheme) }
+    }
+
     pub fn is_array(&self) -> bool {
         if self.texture.is_null() {
             return false;
@@ -242,68 +1118,274 @@ impl Ktx2Texture {
         }
     }
 
-    pub fn get_image_data(&self, level: u32, layer: u32, face: u32) -> Result<&[u8]> {
-        // Safety: Check texture validity first
+    /// Returns the number of components described by the data format descriptor
+    ///
+    /// Read from the DFD rather than derived from [`vk_format`](Self::vk_format),
+    /// so it also works for formats this crate has no table entry for and for
+    /// Basis Universal textures.
+    pub fn num_components(&self) -> Result<u32> {
         if self.texture.is_null() {
             return Err(Error::InvalidOperation);
         }
 
-        // Validate parameters against texture properties
-        let texture = unsafe { &*self.texture };
-        if level >= texture.numLevels || layer >= texture.numLayers || face >= texture.numFaces {
+        Ok(unsafe { ktxTexture2_GetNumComponents(self.texture) })
+    }
+
+    /// Returns the width, height and depth of `level`
+    ///
+    /// Each level halves the previous one, rounding down to at least 1. A level
+    /// past [`levels()`](Self::levels) returns [`Error::InvalidValue`].
+    pub fn level_dimensions(&self, level: u32) -> Result<(u32, u32, u32)> {
+        if level >= self.levels() {
             return Err(Error::InvalidValue);
         }
 
-        let mut offset = 0usize;
-
-        let result = unsafe {
-            let vtbl = texture.vtbl;
-            if vtbl.is_null() {
-                return Err(Error::InvalidOperation);
-            }
+        Ok((
+            (self.width() >> level).max(1),
+            (self.height() >> level).max(1),
+            (self.depth() >> level).max(1),
+        ))
+    }
 
-            let get_image_offset = (*vtbl).GetImageOffset;
-            match get_image_offset {
-                Some(func) => func(
-                    self.texture as *mut ktxTexture,
-                    level,
-                    layer,
-                    face,
-                    &mut offset,
-                ),
-                None => return Err(Error::UnsupportedFeature),
+    /// Returns the size in bytes of a single image of `level`
+    ///
+    /// This is the size of one layer, face or depth slice, as expected by
+    /// [`set_image_data`](Self::set_image_data). Only the header is needed, so
+    /// it also works before the image data is loaded.
+    pub fn image_size(&self, level: u32) -> Result<usize> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+        if level >= self.levels() {
+            return Err(Error::InvalidValue);
+        }
+
+        unsafe {
+            let vtbl = (*self.texture).vtbl;
+            if vtbl.is_null() {
+                return Err(Error::InvalidOperation);
             }
-        };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+            match (*vtbl).GetImageSize {
+                Some(func) => Ok(func(self.texture as *mut ktxTexture, level)),
+                None => Err(Error::UnsupportedFeature),
+            }
         }
+    }
 
-        let size = unsafe {
-            let vtbl = texture.vtbl;
-            let get_image_size = (*vtbl).GetImageSize;
-            match get_image_size {
-                Some(func) => func(self.texture as *mut ktxTexture, level),
-                None => return Err(Error::UnsupportedFeature),
+    /// Returns `true` if the image data in memory is supercompressed
+    ///
+    /// This includes BasisLZ. Zstd and zlib data is inflated when a file is
+    /// loaded with its image data, so only freshly deflated textures and
+    /// header-only loads report those.
+    pub fn is_supercompressed(&self) -> bool {
+        self.supercompression_scheme() != SupercompressionScheme::None
+    }
+
+    /// Returns the size in bytes of the image data currently held in memory
+    pub fn data_size(&self) -> usize {
+        if self.texture.is_null() {
+            return 0;
+        }
+        unsafe { (*self.texture).dataSize }
+    }
+
+    /// Estimates the heap memory held by the texture, in bytes
+    ///
+    /// Adds the loaded image data, any bytes retained for deferred loading, the
+    /// metadata, the data format descriptor and the libktx bookkeeping, which is
+    /// estimated rather than measured. Nothing is allocated, so this is cheap
+    /// enough to call on every cache update. A memory-mapped file counts in
+    /// full even though the OS pages it in lazily.
+    pub fn memory_footprint(&self) -> usize {
+        // Rough size of a libktx key/value list entry, excluding key and value
+        const METADATA_ENTRY_OVERHEAD: usize = 96;
+        // Size of a ktxLevelIndexEntry: byte offset, length and uncompressed length
+        const LEVEL_INDEX_ENTRY: usize = 24;
+
+        if self.texture.is_null() {
+            return std::mem::size_of::<Self>();
+        }
+        let texture = unsafe { &*self.texture };
+
+        // Image data aliasing a `from_vec` buffer is counted once, as the buffer
+        let (retained, image_data) = match &self.source {
+            Some(Source::Aliased(bytes)) => (bytes.capacity(), 0),
+            Some(source) => (source.as_bytes().len(), texture.dataSize),
+            None => (0, texture.dataSize),
+        };
+        let image_data = if texture.pData.is_null() {
+            0
+        } else {
+            image_data
+        };
+
+        let mut metadata = texture.kvDataLen as usize;
+        let mut entry = texture.kvDataHead;
+        while !entry.is_null() {
+            let mut key_len = 0u32;
+            let mut key: *mut std::os::raw::c_char = ptr::null_mut();
+            let mut value_len = 0u32;
+            let mut value: *mut libc::c_void = ptr::null_mut();
+            unsafe {
+                ktxHashListEntry_GetKey(entry, &mut key_len, &mut key);
+                ktxHashListEntry_GetValue(entry, &mut value_len, &mut value);
+                entry = ktxHashList_Next(entry);
             }
+            metadata += METADATA_ENTRY_OVERHEAD + key_len as usize + value_len as usize;
+        }
+
+        // The first word of the descriptor holds its size in bytes
+        let dfd = if texture.pDfd.is_null() {
+            0
+        } else {
+            unsafe { *texture.pDfd as usize }
         };
 
-        // Safety: Validate the calculated bounds
-        if texture.pData.is_null() {
+        std::mem::size_of::<Self>()
+            + std::mem::size_of::<ktxTexture2>()
+            + texture.numLevels as usize * LEVEL_INDEX_ENTRY
+            + retained
+            + image_data
+            + metadata
+            + dfd
+    }
+
+    /// Returns all image data held in memory as one slice, or `None` if it is
+    /// not loaded
+    ///
+    /// Levels are stored smallest first and the slice is
+    /// [`data_size`](Self::data_size) bytes long. Unlike
+    /// [`write_to_memory`](Self::write_to_memory), no header, index or metadata
+    /// is included, and supercompressed data is returned as is.
+    pub fn raw_data(&self) -> Option<&[u8]> {
+        if !self.has_image_data() {
+            return None;
+        }
+
+        unsafe {
+            let texture = &*self.texture;
+            Some(std::slice::from_raw_parts(texture.pData, texture.dataSize))
+        }
+    }
+
+    /// Returns the size in bytes the image data occupies once inflated
+    ///
+    /// For Zstandard or zlib supercompressed data this is the sum of the
+    /// uncompressed level sizes; otherwise it equals [`data_size`](Self::data_size).
+    /// Basis Universal payloads report their encoded size, so use
+    /// [`estimate_transcoded_size`](Self::estimate_transcoded_size) to budget for
+    /// a specific transcode target.
+    pub fn uncompressed_size(&self) -> Result<usize> {
+        if self.texture.is_null() {
             return Err(Error::InvalidOperation);
         }
 
-        let data_size = texture.dataSize;
-        if offset.saturating_add(size) > data_size {
+        unsafe {
+            let vtbl = (*self.texture).vtbl;
+            if vtbl.is_null() {
+                return Err(Error::InvalidOperation);
+            }
+
+            match (*vtbl).GetDataSizeUncompressed {
+                Some(func) => Ok(func(self.texture as *mut ktxTexture)),
+                None => Err(Error::UnsupportedFeature),
+            }
+        }
+    }
+
+    /// Returns `true` if the image data has been loaded
+    pub fn has_image_data(&self) -> bool {
+        !self.texture.is_null() && unsafe { !(*self.texture).pData.is_null() }
+    }
+
+    /// Loads the image data of a texture opened without
+    /// [`TextureCreateFlags::LOAD_IMAGE_DATA`]
+    ///
+    /// Works for both file and memory opens. Does nothing if the data is already
+    /// loaded.
+    pub fn load_image_data(&mut self) -> Result<()> {
+        if self.texture.is_null() {
             return Err(Error::InvalidOperation);
         }
+        if self.has_image_data() {
+            return Ok(());
+        }
+
+        let result = unsafe { ktxTexture2_LoadImageData(self.texture, ptr::null_mut(), 0) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        // Nothing reads from the memory stream once the data is loaded
+        drop(self.source.take());
+
+        Ok(())
+    }
+
+    /// Iterates over every image of the texture in libktx order
+    ///
+    /// Images are yielded level by level, then by layer, then by face (or depth
+    /// slice for 3D textures). Every image is located up front, so if any of
+    /// them cannot be borrowed, e.g. because the image data is not loaded, the
+    /// error is returned instead of the iterator.
+    pub fn iter_images(&self) -> Result<impl Iterator<Item = ImageRef<'_>> + '_> {
+        let images = self
+            .image_indices()
+            .into_iter()
+            .map(|(level, layer, face)| {
+                let data = self.get_image_data(level, layer, face)?;
+                Ok(ImageRef {
+                    level,
+                    layer,
+                    face,
+                    data,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(images.into_iter())
+    }
+
+    pub fn get_image_data(&self, level: u32, layer: u32, face: u32) -> Result<&[u8]> {
+        let (offset, size) = self.image_range(level, layer, face)?;
 
         unsafe {
-            let data_ptr = texture.pData.add(offset);
+            let data_ptr = (*self.texture).pData.add(offset);
             Ok(std::slice::from_raw_parts(data_ptr, size))
         }
     }
 
+    /// Returns a mutable view of an image for in-place editing
+    ///
+    /// Only uncompressed textures can be edited this way; block-compressed data
+    /// returns [`Error::InvalidOperation`].
+    pub fn get_image_data_mut(&mut self, level: u32, layer: u32, face: u32) -> Result<&mut [u8]> {
+        if self.is_compressed() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let (offset, size) = self.image_range(level, layer, face)?;
+
+        unsafe {
+            let data_ptr = (*self.texture).pData.add(offset);
+            Ok(std::slice::from_raw_parts_mut(data_ptr, size))
+        }
+    }
+
+    /// Fills one uncompressed image with `value`
+    ///
+    /// Compressed and Basis Universal textures return
+    /// [`Error::InvalidOperation`].
+    pub fn clear_image(&mut self, level: u32, layer: u32, face: u32, value: u8) -> Result<()> {
+        self.get_image_data_mut(level, layer, face)?.fill(value);
+        Ok(())
+    }
+
+    /// Copies `data` into one image of the texture
+    ///
+    /// `data` must be exactly the size of one image at `level`, otherwise
+    /// [`Error::ImageSizeMismatch`] is returned.
     pub fn set_image_data(&mut self, level: u32, layer: u32, face: u32, data: &[u8]) -> Result<()> {
         // Safety: Check texture validity first
         if self.texture.is_null() {
@@ -312,7 +1394,10 @@ impl Ktx2Texture {
 
         // Validate parameters against texture properties
         let texture = unsafe { &*self.texture };
-        if level >= texture.numLevels || layer >= texture.numLayers || face >= texture.numFaces {
+        if level >= texture.numLevels
+            || layer >= texture.numLayers
+            || face >= self.face_slices(level)
+        {
             return Err(Error::InvalidValue);
         }
 
@@ -321,6 +1406,15 @@ impl Ktx2Texture {
             return Err(Error::InvalidValue);
         }
 
+        // A longer buffer would spill into the next image
+        let expected = self.image_size(level)?;
+        if data.len() != expected {
+            return Err(Error::ImageSizeMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+
         let result = unsafe {
             let vtbl = texture.vtbl;
             if vtbl.is_null() {
@@ -341,157 +1435,1869 @@ impl Ktx2Texture {
             }
         };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
         }
 
         Ok(())
     }
 
-    pub fn transcode_basis(&mut self, format: TranscodeFormat) -> Result<()> {
-        let result = unsafe { ktxTexture2_TranscodeBasis(self.texture, format.into(), 0) };
+    /// Sets the image data of a cubemap face by name
+    ///
+    /// Returns [`Error::InvalidOperation`] if the texture is not a cubemap.
+    pub fn set_face_data(
+        &mut self,
+        level: u32,
+        layer: u32,
+        face: CubeFace,
+        data: &[u8],
+    ) -> Result<()> {
+        if !self.is_cubemap() {
+            return Err(Error::InvalidOperation);
+        }
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        self.set_image_data(level, layer, face.index(), data)
+    }
+
+    /// Same as [`set_face_data`](Self::set_face_data)
+    pub fn set_cube_face(
+        &mut self,
+        level: u32,
+        layer: u32,
+        face: CubeFace,
+        data: &[u8],
+    ) -> Result<()> {
+        self.set_face_data(level, layer, face, data)
+    }
+
+    /// Returns the image data of a cubemap face by name
+    ///
+    /// Returns [`Error::InvalidOperation`] if the texture is not a cubemap.
+    pub fn get_face_data(&self, level: u32, layer: u32, face: CubeFace) -> Result<&[u8]> {
+        if !self.is_cubemap() {
+            return Err(Error::InvalidOperation);
         }
 
-        Ok(())
+        self.get_image_data(level, layer, face.index())
     }
 
-    pub fn compress_basis(&mut self, params: &BasisCompressionParams) -> Result<()> {
-        let mut ktx_params: ktxBasisParams = params.into();
+    /// Sets the image data of every mip level of `layer`/`face` in one call
+    ///
+    /// `levels` must contain exactly [`levels()`](Self::levels) slices, ordered
+    /// from the base level down, each matching the size of its level. The whole
+    /// input is validated before anything is written. For 3D textures `face` is
+    /// the depth slice, which has to exist at every level.
+    pub fn set_all_level_data(&mut self, layer: u32, face: u32, levels: &[&[u8]]) -> Result<()> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        if levels.len() != self.levels() as usize {
+            return Err(Error::InvalidValue);
+        }
+        if layer >= self.layers() || (0..self.levels()).any(|level| face >= self.face_slices(level))
+        {
+            return Err(Error::InvalidValue);
+        }
 
-        let result = unsafe { ktxTexture2_CompressBasisEx(self.texture, &mut ktx_params) };
+        for (level, data) in levels.iter().enumerate() {
+            if data.len() != self.image_size(level as u32)? {
+                return Err(Error::InvalidValue);
+            }
+        }
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        for (level, data) in levels.iter().enumerate() {
+            self.set_image_data(level as u32, layer, face, data)?;
         }
 
         Ok(())
     }
 
-    pub fn compress_basis_simple(&mut self, quality: u32) -> Result<()> {
-        let result = unsafe { ktxTexture2_CompressBasis(self.texture, quality) };
-
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+    /// Reorders the channels of every texel of an uncompressed 4-channel texture
+    ///
+    /// Output channel `i` takes the value of input channel `mapping[i]`, so
+    /// `[2, 1, 0, 3]` turns BGRA data into RGBA. Only the pixel data changes; the
+    /// format is left as is.
+    pub fn swizzle_channels(&mut self, mapping: [u8; 4]) -> Result<()> {
+        let format = self.vk_format();
+        if format.channel_count() != 4 || format.block_size() != (1, 1) || format.is_packed() {
+            return Err(Error::UnsupportedFormat(format));
+        }
+        if mapping.iter().any(|&channel| channel >= 4) {
+            return Err(Error::InvalidValue);
+        }
+        let channel_size = format.bytes_per_block() as usize / 4;
+        let mut texel = [0u8; 16];
+        for (_, data) in self.images_mut()? {
+            for chunk in data.chunks_exact_mut(channel_size * 4) {
+                texel[..chunk.len()].copy_from_slice(chunk);
+                for (dst, &src) in chunk.chunks_exact_mut(channel_size).zip(&mapping) {
+                    let src = src as usize * channel_size;
+                    dst.copy_from_slice(&texel[src..src + channel_size]);
+                }
+            }
         }
 
         Ok(())
     }
 
-    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let path_str = path.as_ref().to_str().ok_or(Error::InvalidValue)?;
-        let c_path = CString::new(path_str).map_err(|_| Error::InvalidValue)?;
+    /// Returns the transfer function recorded in the data format descriptor
+    ///
+    /// Unlike [`VkFormat::is_srgb`] this also works for Basis Universal
+    /// textures, whose format is `Undefined`.
+    pub fn transfer_function(&self) -> TransferFunction {
+        if self.texture.is_null() {
+            return TransferFunction::Unknown;
+        }
+
+        TransferFunction::from_raw(unsafe { ktxTexture2_GetOETF(self.texture) })
+    }
 
-        let result = unsafe { ktxTexture2_WriteToNamedFile(self.texture, c_path.as_ptr()) };
+    /// Returns `true` if the data format descriptor marks the data as sRGB
+    pub fn is_srgb(&self) -> bool {
+        self.transfer_function() == TransferFunction::Srgb
+    }
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+    /// Rewrites the transfer function in the data format descriptor
+    ///
+    /// Formats with an sRGB/UNORM pair are relabeled as well, as with
+    /// [`reinterpret_format`](Self::reinterpret_format); the image data is left
+    /// untouched. Block-compressed formats can only change through such a
+    /// pair. Block-compressed formats without one and Basis Universal textures
+    /// return [`Error::InvalidOperation`], and [`TransferFunction::Unknown`]
+    /// returns [`Error::InvalidValue`].
+    pub fn set_transfer_function(&mut self, transfer: TransferFunction) -> Result<()> {
+        if self.texture.is_null() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
         }
 
-        Ok(())
-    }
+        let format = self.vk_format();
+        let (raw, relabeled) = match transfer {
+            TransferFunction::Linear => (
+                _khr_df_transfer_e_KHR_DF_TRANSFER_LINEAR,
+                format.unorm_variant(),
+            ),
+            TransferFunction::Srgb => (
+                _khr_df_transfer_e_KHR_DF_TRANSFER_SRGB,
+                format.srgb_variant(),
+            ),
+            TransferFunction::Unknown => return Err(Error::InvalidValue),
+        };
 
-    pub fn write_to_memory(&self) -> Result<Vec<u8>> {
-        let mut data: *mut ktx_uint8_t = ptr::null_mut();
-        let mut size: ktx_size_t = 0;
+        if relabeled.is_none() && self.is_compressed() {
+            return Err(Error::InvalidOperation);
+        }
+        if let Some(relabeled) = relabeled.filter(|&relabeled| relabeled != format) {
+            return self.reinterpret_format(relabeled);
+        }
 
-        let result = unsafe { ktxTexture2_WriteToMemory(self.texture, &mut data, &mut size) };
+        let result = unsafe { ktxTexture2_SetOETF(self.texture, raw) };
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
         }
 
-        // Safety: Check that we got valid data before proceeding
-        if data.is_null() || size == 0 {
+        Ok(())
+    }
+
+    /// Parses the texture's data format descriptor
+    pub fn dfd(&self) -> Result<DataFormatDescriptor> {
+        if self.texture.is_null() {
             return Err(Error::InvalidOperation);
         }
 
-        // Create Vec from the allocated memory - this is safe because:
-        // 1. data is non-null and size > 0 (checked above)
-        // 2. ktxTexture2_WriteToMemory allocates with malloc()
-        // 3. Vec::from_raw_parts will take ownership and free with the global allocator
-        let vec = unsafe { Vec::from_raw_parts(data, size, size) };
+        unsafe {
+            let dfd = (*self.texture).pDfd;
+            if dfd.is_null() {
+                return Err(Error::InvalidOperation);
+            }
+            // The first word holds the total size in bytes
+            let words = std::slice::from_raw_parts(dfd, (*dfd as usize / 4).max(1));
+            DataFormatDescriptor::from_words(words)
+        }
+    }
 
-        Ok(vec)
+    /// Returns `true` if the data format descriptor marks the color channels as
+    /// premultiplied by alpha
+    pub fn is_premultiplied(&self) -> bool {
+        !self.texture.is_null() && unsafe { ktxTexture2_GetPremultipliedAlpha(self.texture) }
     }
 
-    pub fn get_metadata(&self, key: &str) -> Result<Vec<u8>> {
-        let c_key = CString::new(key).map_err(|_| Error::InvalidValue)?;
+    /// Sets the premultiplied-alpha flag in the data format descriptor
+    ///
+    /// Only the flag changes; the image data is not converted. The flag is kept
+    /// through compression and transcoding. Compressed textures return
+    /// [`Error::InvalidOperation`], so set it before compressing.
+    pub fn set_premultiplied(&mut self, premultiplied: bool) -> Result<()> {
+        if self.texture.is_null() || self.is_compressed() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
+        if unsafe { (*self.texture).pDfd.is_null() } {
+            return Err(Error::InvalidOperation);
+        }
 
-        let mut value_len = 0u32;
-        let mut value: *mut libc::c_void = ptr::null_mut();
+        self.set_dfd_premultiplied(premultiplied);
+        Ok(())
+    }
 
-        let result = unsafe {
-            ktxHashList_FindValue(
-                &mut (*self.texture).kvDataHead,
-                c_key.as_ptr(),
-                &mut value_len,
-                &mut value,
-            )
-        };
+    /// Relabels the image data as `new_format` without touching the bytes
+    ///
+    /// Meant for fixing a wrong sRGB/UNORM tag, so `new_format` must be the
+    /// sRGB or UNORM counterpart of the current format (see
+    /// [`VkFormat::srgb_variant`]); any other format returns
+    /// [`Error::InvalidValue`], even one with the same block size. Only the
+    /// transfer function in the data format descriptor is updated to match,
+    /// which would leave e.g. `R8G8B8A8Uint` with a descriptor still
+    /// describing normalized channels. Basis Universal textures have no
+    /// format to relabel and return [`Error::InvalidOperation`]; use
+    /// [`set_transfer_function`](Self::set_transfer_function) before
+    /// compressing instead.
+    pub fn reinterpret_format(&mut self, new_format: VkFormat) -> Result<()> {
+        if self.texture.is_null() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        let current = self.vk_format();
+        if new_format == current {
+            return Ok(());
+        }
+        if current.unorm_variant().is_none()
+            || current.unorm_variant() != new_format.unorm_variant()
+        {
+            return Err(Error::InvalidValue);
         }
 
-        if value.is_null() {
-            return Err(Error::NotFound);
+        let transfer = if new_format.is_srgb() {
+            _khr_df_transfer_e_KHR_DF_TRANSFER_SRGB
+        } else {
+            _khr_df_transfer_e_KHR_DF_TRANSFER_LINEAR
+        };
+        let result = unsafe { ktxTexture2_SetOETF(self.texture, transfer) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
         }
 
-        let data =
-            unsafe { std::slice::from_raw_parts(value as *const u8, value_len as usize).to_vec() };
+        unsafe {
+            (*self.texture).vkFormat = new_format.as_raw();
+        }
 
-        Ok(data)
+        Ok(())
     }
 
-    pub fn set_metadata(&mut self, key: &str, value: &[u8]) -> Result<()> {
-        let c_key = CString::new(key).map_err(|_| Error::InvalidValue)?;
+    /// Reverses the row order of every image and toggles the Y direction in the
+    /// `KTXorientation` entry
+    ///
+    /// Converts between bottom-up (OpenGL-style) and top-down (Vulkan-style)
+    /// data. Without an orientation entry the KTX2 default of `rd` (`rdi` for
+    /// 3D textures) is assumed. Block-compressed and Basis Universal textures
+    /// return [`Error::InvalidOperation`].
+    pub fn flip_y(&mut self) -> Result<()> {
+        if self.texture.is_null() || self.is_compressed() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
 
-        let result = unsafe {
-            ktxHashList_AddKVPair(
-                &mut (*self.texture).kvDataHead,
-                c_key.as_ptr(),
-                value.len() as u32,
-                value.as_ptr() as *const libc::c_void,
-            )
+        let orientation = match self.orientation()? {
+            Some(orientation) => orientation,
+            None => Orientation {
+                x: XOrient::Right,
+                y: YOrient::Down,
+                z: (self.depth() > 1).then_some(ZOrient::In),
+            },
+        };
+
+        let height = self.height();
+        for (level, data) in self.images_mut()? {
+            let rows = (height >> level).max(1) as usize;
+            let stride = data.len() / rows;
+            for row in 0..rows / 2 {
+                let (top, bottom) = data.split_at_mut((rows - 1 - row) * stride);
+                top[row * stride..(row + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+            }
+        }
+
+        let y = match orientation.y {
+            YOrient::Down => YOrient::Up,
+            YOrient::Up => YOrient::Down,
         };
+        self.set_orientation(Orientation { y, ..orientation })
+    }
+
+    /// Returns the transcode targets that keep every channel of this texture
+    ///
+    /// ETC1S and UASTC sources can both reach every target; what rules targets
+    /// out is the component count from the data format descriptor (an RGBA
+    /// source skips the opaque formats, an RGB one skips BC4 and BC5) and
+    /// [`TranscodeFormat::is_compatible_with`]. The result is empty if the
+    /// texture needs no transcoding.
+    pub fn available_transcode_formats(&self) -> Vec<TranscodeFormat> {
+        if !self.needs_transcoding() {
+            return Vec::new();
+        }
+
+        let components = self.num_components().unwrap_or(4);
+        TranscodeFormat::ALL
+            .into_iter()
+            .filter(|format| format.num_components() >= components)
+            .filter(|format| format.is_compatible_with(self.width(), self.height()))
+            .collect()
+    }
+
+    /// Transcodes the Basis Universal data in place to `format`
+    ///
+    /// Fails with [`Error::InvalidOperation`] if the texture holds no Basis
+    /// Universal data, and with [`Error::UnsupportedTranscodeTarget`] if `format`
+    /// cannot represent this texture (PVRTC1 requires square, power-of-two
+    /// dimensions).
+    pub fn transcode_basis(&mut self, format: TranscodeFormat) -> Result<()> {
+        if self.texture.is_null() || !self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
 
-        if result != ktx_error_code_e_KTX_SUCCESS {
-            return Err(result.into());
+        if !format.is_compatible_with(self.width(), self.height()) {
+            return Err(Error::UnsupportedTranscodeTarget(format));
         }
 
+        self.with_preserved_dfd_flags(|texture| unsafe {
+            ktxTexture2_TranscodeBasis(texture, format.into(), 0)
+        })
+    }
+
+    /// Transcodes the Basis Universal data in place to the Vulkan format `target`
+    ///
+    /// Picks the matching [`TranscodeFormat`] via
+    /// [`TranscodeFormat::from_vk_format`] and returns
+    /// [`Error::UnsupportedFeature`] for formats Basis Universal cannot produce.
+    /// libktx picks the sRGB or UNORM variant from the source texture's transfer
+    /// function, so the result is relabeled with
+    /// [`reinterpret_format`](Self::reinterpret_format) when that differs from
+    /// `target`. Afterwards [`vk_format`](Self::vk_format) returns `target`.
+    pub fn transcode_to_vk_format(&mut self, target: VkFormat) -> Result<()> {
+        let format = TranscodeFormat::from_vk_format(target).ok_or(Error::UnsupportedFeature)?;
+        self.transcode_basis(format)?;
+
+        if self.vk_format() != target {
+            self.reinterpret_format(target)?;
+        }
         Ok(())
     }
-}
 
-impl Drop for Ktx2Texture {
-    fn drop(&mut self) {
-        if !self.texture.is_null() {
-            unsafe {
-                ktxTexture2_Destroy(self.texture);
-            }
+    /// Transcodes the texture to `format` only if it still needs transcoding
+    ///
+    /// Returns `true` if the texture was transcoded and `false` if it already
+    /// held GPU-ready data.
+    pub fn ensure_transcoded(&mut self, format: TranscodeFormat) -> Result<bool> {
+        if !self.needs_transcoding() {
+            return Ok(false);
         }
+
+        self.transcode_basis(format)?;
+        Ok(true)
     }
-}
 
-impl fmt::Debug for Ktx2Texture {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Ktx2Texture")
-            .field("width", &self.width())
-            .field("height", &self.height())
-            .field("depth", &self.depth())
-            .field("layers", &self.layers())
-            .field("faces", &self.faces())
-            .field("levels", &self.levels())
-            .field("vk_format", &self.vk_format())
-            .field("is_array", &self.is_array())
-            .field("is_cubemap", &self.is_cubemap())
-            .field("is_compressed", &self.is_compressed())
-            .finish()
+    /// Estimates the total image data size after transcoding to `format`
+    ///
+    /// Sums the block-compressed (or uncompressed) size of every image without
+    /// transcoding. Level alignment padding added by libktx is not included.
+    pub fn estimate_transcoded_size(&self, format: TranscodeFormat) -> Result<usize> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let (block_width, block_height) = format.block_size();
+        let bytes_per_block = format.bytes_per_block() as usize;
+        // PVRTC1 images are never smaller than 2x2 blocks
+        let min_blocks = match format {
+            TranscodeFormat::Pvrtc1_4_Rgb | TranscodeFormat::Pvrtc1_4_Rgba => 2,
+            _ => 1,
+        };
+
+        let mut level_total = 0usize;
+        for level in 0..self.levels() {
+            let width = (self.width() >> level).max(1);
+            let height = (self.height() >> level).max(1);
+            let depth = (self.depth() >> level).max(1);
+
+            let blocks_x = width.div_ceil(block_width).max(min_blocks) as usize;
+            let blocks_y = height.div_ceil(block_height).max(min_blocks) as usize;
+            level_total += blocks_x * blocks_y * depth as usize * bytes_per_block;
+        }
+
+        Ok(level_total * self.layers() as usize * self.faces() as usize)
     }
-}
 
-unsafe impl Send for Ktx2Texture {}
-unsafe impl Sync for Ktx2Texture {}
+    /// Transcodes a deep copy of this texture, leaving `self` untouched
+    ///
+    /// Useful for fanning one Basis Universal source out to several GPU formats
+    /// without reparsing the file for every target.
+    pub fn transcode_to(&self, format: TranscodeFormat) -> Result<Ktx2Texture> {
+        let mut copy = self.duplicate()?;
+        copy.transcode_basis(format)?;
+        Ok(copy)
+    }
+
+    /// Decodes one image to tightly-packed RGBA8 pixels without modifying the texture
+    ///
+    /// Basis Universal textures are transcoded on an internal copy and supercompressed
+    /// textures are inflated first. Uncompressed 8-bit formats are expanded to four
+    /// channels; any other format returns [`Error::UnsupportedFormat`].
+    pub fn decode_rgba8(&self, level: u32, layer: u32, face: u32) -> Result<Vec<u8>> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+        if level >= self.levels() || layer >= self.layers() || face >= self.face_slices(level) {
+            return Err(Error::InvalidValue);
+        }
+
+        // Zstd/zlib payloads are only inflated by libktx when loading, so reload a copy
+        let scheme = unsafe { (*self.texture).supercompressionScheme };
+        if scheme == ktxSupercmpScheme_KTX_SS_ZSTD || scheme == ktxSupercmpScheme_KTX_SS_ZLIB {
+            let inflated = Self::from_memory(&self.write_to_memory()?)?;
+            return inflated.decode_rgba8(level, layer, face);
+        }
+
+        if self.needs_transcoding() {
+            let transcoded = self.transcode_to(TranscodeFormat::Rgba32)?;
+            return transcoded
+                .get_image_data(level, layer, face)
+                .map(<[u8]>::to_vec);
+        }
+
+        let format = self.vk_format();
+        let data = self.get_image_data(level, layer, face)?;
+        let pixels = match format {
+            VkFormat::R8G8B8A8Unorm | VkFormat::R8G8B8A8Srgb => data.to_vec(),
+            VkFormat::B8G8R8A8Unorm | VkFormat::B8G8R8A8Srgb => data
+                .chunks_exact(4)
+                .flat_map(|p| [p[2], p[1], p[0], p[3]])
+                .collect(),
+            VkFormat::R8G8B8Unorm => data
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            VkFormat::B8G8R8Unorm => data
+                .chunks_exact(3)
+                .flat_map(|p| [p[2], p[1], p[0], 255])
+                .collect(),
+            VkFormat::R8G8Unorm => data
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[1], 0, 255])
+                .collect(),
+            VkFormat::R8Unorm => data.iter().flat_map(|&r| [r, 0, 0, 255]).collect(),
+            _ => return Err(Error::UnsupportedFormat(format)),
+        };
+
+        Ok(pixels)
+    }
+
+    /// Encodes the texture to Basis Universal (ETC1S or UASTC)
+    ///
+    /// Both modes are low dynamic range, so floating-point textures such as
+    /// those from [`from_rgba_f32`](Self::from_rgba_f32) are rejected with
+    /// [`Error::UnsupportedFormat`] instead of being clamped.
+    pub fn compress_basis(&mut self, params: &BasisCompressionParams) -> Result<()> {
+        self.check_basis_input()?;
+
+        let mut ktx_params: ktxBasisParams = params.into();
+
+        self.with_preserved_dfd_flags(|texture| unsafe {
+            ktxTexture2_CompressBasisEx(texture, &mut ktx_params)
+        })
+    }
+
+    /// Compresses the texture like [`compress_basis`](Self::compress_basis) and
+    /// reports the achieved sizes, mode and encoding time
+    pub fn compress_basis_reported(
+        &mut self,
+        params: &BasisCompressionParams,
+    ) -> Result<CompressionReport> {
+        let original_size = self.data_size();
+        let start = Instant::now();
+        self.compress_basis(params)?;
+        let duration = start.elapsed();
+        let compressed_size = self.data_size();
+
+        Ok(CompressionReport {
+            original_size,
+            compressed_size,
+            ratio: original_size as f64 / compressed_size.max(1) as f64,
+            mode: if params.uastc {
+                CompressionMode::Uastc
+            } else {
+                CompressionMode::Etc1s
+            },
+            duration,
+        })
+    }
+
+    /// Compresses the texture like [`compress_basis`](Self::compress_basis),
+    /// reporting progress to `progress`
+    ///
+    /// libktx exposes no progress hook from the Basis Universal encoder, so the
+    /// callback is coarse: it fires once before encoding starts and once after it
+    /// finishes successfully. It always runs on the calling thread, so no `Send`
+    /// bound is required even when `thread_count` is greater than one.
+    pub fn compress_basis_with_progress<F>(
+        &mut self,
+        params: &BasisCompressionParams,
+        mut progress: F,
+    ) -> Result<()>
+    where
+        F: FnMut(CompressionProgress),
+    {
+        let image_count = self.levels() * self.layers() * self.faces();
+
+        progress(CompressionProgress {
+            stage: CompressionStage::Started,
+            image_count,
+            fraction: 0.0,
+        });
+
+        self.compress_basis(params)?;
+
+        progress(CompressionProgress {
+            stage: CompressionStage::Finished,
+            image_count,
+            fraction: 1.0,
+        });
+
+        Ok(())
+    }
+
+    /// Compresses the texture directly to an ASTC block format
+    ///
+    /// Afterwards [`vk_format`](Self::vk_format) reports the ASTC format matching
+    /// the block dimension and the texture's transfer function. Returns
+    /// [`Error::UnsupportedFeature`] if libktx was built without the ASTC encoder.
+    pub fn compress_astc(&mut self, params: &AstcParams) -> Result<()> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let mut ktx_params: ktxAstcParams = params.into();
+
+        self.with_preserved_dfd_flags(|texture| unsafe {
+            ktxTexture2_CompressAstcEx(texture, &mut ktx_params)
+        })
+    }
+
+    /// Encodes the texture to ETC1S at `quality` with libktx's other defaults
+    ///
+    /// Rejects the same textures as [`compress_basis`](Self::compress_basis).
+    pub fn compress_basis_simple(&mut self, quality: u32) -> Result<()> {
+        self.check_basis_input()?;
+
+        self.with_preserved_dfd_flags(|texture| unsafe {
+            ktxTexture2_CompressBasis(texture, quality)
+        })
+    }
+
+    /// Supercompresses the image data with Zstandard at `level`
+    ///
+    /// `level` must be in `1..=22`, otherwise [`Error::InvalidValue`] is
+    /// returned. The level is not recorded in the file. Loading a zstd file
+    /// with image data inflates it again, so its images read back directly.
+    pub fn deflate_zstd(&mut self, level: u32) -> Result<()> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+        if !(1..=22).contains(&level) {
+            return Err(Error::InvalidValue);
+        }
+
+        self.detach_image_data()?;
+        let result = unsafe { ktxTexture2_DeflateZstd(self.texture, level) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Inflates zstd or zlib supercompressed image data in place
+    ///
+    /// Does nothing if the data is not supercompressed. BasisLZ data is part of
+    /// the ETC1S encoding and returns [`Error::InvalidOperation`]; transcode it
+    /// instead. Schemes unknown to libktx return [`Error::UnsupportedFeature`].
+    pub fn inflate(&mut self) -> Result<()> {
+        match self.supercompression_scheme() {
+            SupercompressionScheme::None => Ok(()),
+            SupercompressionScheme::Zstd | SupercompressionScheme::Zlib => {
+                *self = self.inflated()?;
+                Ok(())
+            }
+            SupercompressionScheme::BasisLz => Err(Error::InvalidOperation),
+            SupercompressionScheme::Other(_) => Err(Error::UnsupportedFeature),
+        }
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_to_named_file(path.as_ref(), true)
+    }
+
+    /// Writes the texture to a temporary file next to `path`, then renames it
+    /// over `path`
+    ///
+    /// Readers see either the previous file or the complete new one, never a
+    /// partial write. The temporary file is removed if writing fails.
+    pub fn write_to_file_atomic<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        write_atomically(path.as_ref(), |file| {
+            let mut writer = std::io::BufWriter::new(file);
+            self.write_to_stream(&mut writer, true)?;
+            writer.flush()?;
+            Ok(())
+        })
+    }
+
+    /// Writes the texture to a file, applying `options` on the way out
+    ///
+    /// Supercompression is applied to a copy, so `self` is left unchanged. See
+    /// [`WriteOptions`] for the accepted settings.
+    pub fn write_to_file_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &WriteOptions,
+    ) -> Result<()> {
+        let prepared = self.prepare_write(options)?;
+        let texture = prepared.as_ref().unwrap_or(self);
+        texture.write_to_named_file(path.as_ref(), options.add_writer_metadata)
+    }
+
+    /// Streams the serialized texture into `writer`, returning the bytes written
+    ///
+    /// The file is written front to back without being buffered in memory first.
+    /// Failures of `writer` are returned as [`Error::Io`]; a panic in `writer` is
+    /// resumed once libktx has returned.
+    pub fn write_to_writer<W: Write>(&self, writer: W) -> Result<u64> {
+        self.write_to_stream(writer, true)
+    }
+
+    /// Serializes the texture into a new `Vec`
+    pub fn write_to_memory(&self) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.write_into(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Serializes the texture into a new `Vec`, applying `options`
+    ///
+    /// See [`write_to_file_with`](Self::write_to_file_with).
+    pub fn write_to_memory_with(&self, options: &WriteOptions) -> Result<Vec<u8>> {
+        let prepared = self.prepare_write(options)?;
+        let texture = prepared.as_ref().unwrap_or(self);
+
+        let mut buf = Vec::new();
+        texture.serialize_into(&mut buf, options.add_writer_metadata)?;
+        Ok(buf)
+    }
+
+    /// Serializes the texture into `buf`, replacing its contents
+    ///
+    /// Reuses the capacity of `buf`, so repeated calls with the same buffer avoid
+    /// growing a fresh `Vec` each time. libktx still serializes into its own
+    /// allocation, which is copied and freed immediately.
+    pub fn write_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        self.serialize_into(buf, true)
+    }
+
+    pub fn get_metadata(&self, key: &str) -> Result<Vec<u8>> {
+        self.get_metadata_ref(key).map(<[u8]>::to_vec)
+    }
+
+    /// Returns the metadata value for `key` borrowed from the texture's storage
+    ///
+    /// Unlike [`get_metadata`](Self::get_metadata) this does not allocate. The
+    /// slice is invalidated by any metadata change, which the borrow checker
+    /// enforces since those methods take `&mut self`.
+    pub fn get_metadata_ref(&self, key: &str) -> Result<&[u8]> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let c_key = CString::new(key).map_err(|_| Error::InvalidValue)?;
+
+        let mut value_len = 0u32;
+        let mut value: *mut libc::c_void = ptr::null_mut();
+
+        let result = unsafe {
+            ktxHashList_FindValue(
+                &mut (*self.texture).kvDataHead,
+                c_key.as_ptr(),
+                &mut value_len,
+                &mut value,
+            )
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        if value.is_null() {
+            return Err(Error::NotFound);
+        }
+
+        let data = unsafe { std::slice::from_raw_parts(value as *const u8, value_len as usize) };
+
+        Ok(data)
+    }
+
+    /// Sets the metadata value for `key`, replacing any existing entry
+    pub fn set_metadata(&mut self, key: &str, value: &[u8]) -> Result<()> {
+        let c_key = CString::new(key).map_err(|_| Error::InvalidValue)?;
+
+        // ktxHashList_AddKVPair appends, so drop existing entries to keep keys unique
+        loop {
+            match self.delete_metadata(key) {
+                Ok(()) => continue,
+                Err(Error::NotFound) => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let result = unsafe {
+            ktxHashList_AddKVPair(
+                &mut (*self.texture).kvDataHead,
+                c_key.as_ptr(),
+                value.len() as u32,
+                value.as_ptr() as *const libc::c_void,
+            )
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Sets every entry of `entries`, replacing existing values
+    ///
+    /// All keys are checked before anything is written, so an empty key or one
+    /// containing a NUL byte fails with [`Error::InvalidMetadataKey`] naming the
+    /// first such key and leaves the metadata untouched.
+    pub fn set_metadata_map(&mut self, entries: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+        if let Some(key) = entries
+            .keys()
+            .find(|key| key.is_empty() || key.contains('\0'))
+        {
+            return Err(Error::InvalidMetadataKey(key.clone()));
+        }
+
+        for (key, value) in entries {
+            self.set_metadata(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Passes the metadata value for `key` to `f` without copying it
+    ///
+    /// Handy for hashing or parsing large embedded payloads in place.
+    pub fn with_metadata<R>(&self, key: &str, f: impl FnOnce(&[u8]) -> R) -> Result<R> {
+        self.get_metadata_ref(key).map(f)
+    }
+
+    /// Returns the metadata value for `key` as a string
+    ///
+    /// A single trailing NUL terminator is stripped. Values that are not valid
+    /// UTF-8 are reported as [`Error::InvalidValue`].
+    pub fn get_metadata_str(&self, key: &str) -> Result<String> {
+        let mut value = self.get_metadata(key)?;
+        if value.last() == Some(&0) {
+            value.pop();
+        }
+        String::from_utf8(value).map_err(|_| Error::InvalidValue)
+    }
+
+    /// Sets the metadata value for `key` to `value` followed by a NUL terminator
+    ///
+    /// The KTX2 specification recommends NUL-terminating string values.
+    pub fn set_metadata_str(&mut self, key: &str, value: &str) -> Result<()> {
+        let c_value = CString::new(value).map_err(|_| Error::InvalidValue)?;
+        self.set_metadata(key, c_value.as_bytes_with_nul())
+    }
+
+    /// Returns the orientation stored in the `KTXorientation` entry, if any
+    ///
+    /// Malformed values are reported as [`Error::InvalidValue`].
+    pub fn orientation(&self) -> Result<Option<Orientation>> {
+        let value = match self.get_metadata(Orientation::KEY) {
+            Ok(value) => value,
+            Err(Error::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let value = value.strip_suffix(&[0]).unwrap_or(&value);
+        let value = std::str::from_utf8(value).map_err(|_| Error::InvalidValue)?;
+        value.parse().map(Some)
+    }
+
+    /// Stores `orientation` in the `KTXorientation` entry
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<()> {
+        // libktx expects the value to include its NUL terminator
+        let mut value = orientation.to_string().into_bytes();
+        value.push(0);
+        self.set_metadata(Orientation::KEY, &value)
+    }
+
+    /// Returns the animation timing stored in the `KTXanimData` entry, if any
+    ///
+    /// Malformed values are reported as [`Error::FileDataError`].
+    pub fn anim_data(&self) -> Result<Option<AnimData>> {
+        match self.get_metadata_ref(AnimData::KEY) {
+            Ok(value) => AnimData::from_bytes(value).map(Some),
+            Err(Error::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stores animation timing in the `KTXanimData` entry
+    ///
+    /// The layers of the texture are the frames of the animation.
+    pub fn set_anim_data(&mut self, duration: u32, timescale: u32, loop_count: u32) -> Result<()> {
+        let anim = AnimData {
+            duration,
+            timescale,
+            loop_count,
+        };
+        self.set_metadata(AnimData::KEY, &anim.to_bytes())
+    }
+
+    /// Returns the `KTXwriter` entry identifying the tool that wrote the file
+    ///
+    /// libktx appends its own version to the value on write.
+    pub fn writer(&self) -> Result<Option<String>> {
+        let value = match self.get_metadata(WRITER_KEY) {
+            Ok(value) => value,
+            Err(Error::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let value = value.strip_suffix(&[0]).unwrap_or(&value);
+        Ok(Some(String::from_utf8_lossy(value).into_owned()))
+    }
+
+    /// Overrides the `KTXwriter` entry
+    ///
+    /// Without an explicit writer, `ktx2-rw <version>` is added on write.
+    pub fn set_writer(&mut self, writer: &str) -> Result<()> {
+        self.set_metadata_str(WRITER_KEY, writer)
+    }
+
+    /// Returns the keys of every metadata entry, in list order
+    ///
+    /// Includes the keys libktx maintains itself, such as `KTXwriter`.
+    pub fn metadata_keys(&self) -> Result<Vec<String>> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        Ok(self.metadata_iter().map(|(key, _)| key).collect())
+    }
+
+    /// Iterates over every metadata entry as `(key, value)` pairs, in list order
+    ///
+    /// Values are returned exactly as stored, including any NUL terminator.
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (String, &[u8])> + '_ {
+        let entry = if self.texture.is_null() {
+            ptr::null_mut()
+        } else {
+            unsafe { (*self.texture).kvDataHead }
+        };
+
+        MetadataIter {
+            entry,
+            _texture: PhantomData,
+        }
+    }
+
+    /// Returns a copy of every metadata entry whose key starts with `prefix`,
+    /// in list order
+    ///
+    /// The match is case-sensitive and byte-wise, so `"MyTool."` selects
+    /// `MyTool.author` but not `MyToolkit.author`.
+    pub fn metadata_with_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)> {
+        self.metadata_iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key, value.to_vec()))
+            .collect()
+    }
+
+    /// Checks the texture against the structural rules of the KTX2 specification
+    ///
+    /// Inconsistencies that make the file unusable return
+    /// [`Error::FileDataError`]: more levels than the full mip chain, a data
+    /// format descriptor that disagrees with the `VkFormat` on block size, bytes
+    /// per block or transfer function, a level whose size does not match its
+    /// dimensions, a duplicated metadata key, or a malformed `KTXorientation` or
+    /// `KTXanimData` entry. Anything else worth fixing is returned as a list of
+    /// [`ValidationWarning`]s. Key order is not checked, since libktx sorts the
+    /// metadata when writing.
+    pub fn validate(&self) -> Result<Vec<ValidationWarning>> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let levels = self.levels();
+        if levels == 0 || levels > Self::full_mip_levels(self.width(), self.height(), self.depth())
+        {
+            return Err(Error::FileDataError);
+        }
+        // 1D textures are a single row and 2D textures a single slice
+        if (self.num_dimensions() < 2 && self.height() > 1)
+            || (self.num_dimensions() < 3 && self.depth() > 1)
+        {
+            return Err(Error::FileDataError);
+        }
+
+        let format = self.vk_format();
+        if format != VkFormat::Undefined {
+            let dfd = self.dfd()?;
+            let (block_width, block_height) = format.block_size();
+            let bytes_per_block = format.bytes_per_block();
+
+            // bytesPlane0 is zero in supercompressed files
+            let bytes_match =
+                self.is_supercompressed() || u32::from(dfd.bytes_planes[0]) == bytes_per_block;
+            if u32::from(dfd.texel_block_dimensions[0]) != block_width
+                || u32::from(dfd.texel_block_dimensions[1]) != block_height
+                || !bytes_match
+                || self.is_srgb() != format.is_srgb()
+            {
+                return Err(Error::FileDataError);
+            }
+
+            if self.has_image_data() && !self.is_supercompressed() {
+                let images = self.layers() as usize * self.faces() as usize;
+                for level in 0..levels {
+                    let (width, height, depth) = self.level_dimensions(level)?;
+                    let expected = width.div_ceil(block_width) as usize
+                        * height.div_ceil(block_height) as usize
+                        * depth as usize
+                        * bytes_per_block as usize
+                        * images;
+                    if self.level_range(level)?.1 != expected {
+                        return Err(Error::FileDataError);
+                    }
+                }
+            }
+        }
+
+        let mut warnings = Vec::new();
+        let mut keys = BTreeSet::new();
+        for (key, value) in self.metadata_iter() {
+            if !keys.insert(key.clone()) {
+                return Err(Error::FileDataError);
+            }
+            if STRING_KEYS.contains(&key.as_str()) && value.last() != Some(&0) {
+                warnings.push(ValidationWarning::MissingNulTerminator(key.clone()));
+            }
+            if (key.starts_with("KTX") || key.starts_with("ktx"))
+                && !RESERVED_KEYS.contains(&key.as_str())
+            {
+                warnings.push(ValidationWarning::UnknownReservedKey(key));
+            }
+        }
+
+        match self.get_metadata_ref(Orientation::KEY) {
+            Ok(value) => {
+                // One of two letters per dimension: x, then y, then z
+                let value = value.strip_suffix(&[0]).unwrap_or(value);
+                let well_formed = (1..=3).contains(&value.len())
+                    && value
+                        .iter()
+                        .zip([b"rl", b"du", b"oi"])
+                        .all(|(c, allowed)| allowed.contains(c));
+                if !well_formed {
+                    return Err(Error::FileDataError);
+                }
+
+                let dimensions = self.num_dimensions();
+                if value.len() as u32 != dimensions {
+                    warnings.push(ValidationWarning::OrientationDimensionMismatch {
+                        expected: dimensions,
+                        actual: value.len() as u32,
+                    });
+                }
+            }
+            Err(Error::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        if self.anim_data()?.is_some() && !self.is_array() {
+            warnings.push(ValidationWarning::AnimDataWithoutArray);
+        }
+
+        Ok(warnings)
+    }
+
+    /// Removes the metadata entry for `key`
+    ///
+    /// Returns [`Error::NotFound`] if no entry exists for `key`.
+    pub fn delete_metadata(&mut self, key: &str) -> Result<()> {
+        let c_key = CString::new(key).map_err(|_| Error::InvalidValue)?;
+
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let mut entry: *mut ktxHashListEntry = ptr::null_mut();
+
+        let result = unsafe {
+            ktxHashList_FindEntry(&mut (*self.texture).kvDataHead, c_key.as_ptr(), &mut entry)
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        if entry.is_null() {
+            return Err(Error::NotFound);
+        }
+
+        let result = unsafe { ktxHashList_DeleteEntry(&mut (*self.texture).kvDataHead, entry) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a supercompressed copy of the texture if `options` asks for a
+    /// scheme the texture does not use yet
+    fn prepare_write(&self, options: &WriteOptions) -> Result<Option<Self>> {
+        let Some(target) = options.supercompression else {
+            return Ok(None);
+        };
+
+        let current = self.supercompression_scheme();
+        if target == current {
+            return Ok(None);
+        }
+
+        match target {
+            SupercompressionScheme::Zstd => {
+                // BasisLZ payloads are already supercompressed and cannot be deflated
+                if current == SupercompressionScheme::BasisLz {
+                    return Err(Error::InvalidOperation);
+                }
+                let mut copy = if current == SupercompressionScheme::None {
+                    self.duplicate()?
+                } else {
+                    self.inflated()?
+                };
+                copy.deflate_zstd(options.zstd_level)?;
+                Ok(Some(copy))
+            }
+            SupercompressionScheme::None => {
+                if current == SupercompressionScheme::BasisLz {
+                    return Err(Error::InvalidOperation);
+                }
+                self.inflated().map(Some)
+            }
+            _ => Err(Error::UnsupportedFeature),
+        }
+    }
+
+    /// Returns a copy with zstd/zlib image data inflated, which libktx only does
+    /// when loading
+    fn inflated(&self) -> Result<Self> {
+        let mut bytes = Vec::new();
+        self.serialize_into(&mut bytes, false)?;
+        Self::from_memory(&bytes)
+    }
+
+    /// Writes the file at `path`, adding the default `KTXwriter` entry if
+    /// `add_writer` is set and the texture has none
+    fn write_to_named_file(&self, path: &Path, add_writer: bool) -> Result<()> {
+        // See from_file_with_flags for why Windows goes through std::fs
+        #[cfg(windows)]
+        {
+            let file = std::fs::File::create(path).map_err(|_| Error::FileOpenFailed)?;
+            let mut writer = std::io::BufWriter::new(file);
+            self.write_to_stream(&mut writer, add_writer)?;
+            writer.flush()?;
+            Ok(())
+        }
+
+        #[cfg(not(windows))]
+        {
+            let c_path = c_path(path)?;
+            let mut view = self.write_view(add_writer)?;
+
+            let result = unsafe { ktxTexture2_WriteToNamedFile(view.as_ptr(), c_path.as_ptr()) };
+
+            if let Some(error) = Error::try_from_code(result) {
+                return Err(error);
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Streams the file into `writer`; see
+    /// [`write_to_named_file`](Self::write_to_named_file) for `add_writer`
+    fn write_to_stream<W: Write>(&self, writer: W, add_writer: bool) -> Result<u64> {
+        let mut view = self.write_view(add_writer)?;
+
+        let mut adapter = StreamAdapter::new(writer);
+        let mut stream = adapter.writer_stream();
+
+        let result = unsafe { ktxTexture2_WriteToStream(view.as_ptr(), &mut stream) };
+
+        let written = adapter.written();
+        if let Some(e) = adapter.finish() {
+            return Err(e.into());
+        }
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(written)
+    }
+
+    /// Serializes the file into `buf`; see
+    /// [`write_to_named_file`](Self::write_to_named_file) for `add_writer`
+    fn serialize_into(&self, buf: &mut Vec<u8>, add_writer: bool) -> Result<()> {
+        let mut view = self.write_view(add_writer)?;
+
+        // The libktx buffer comes from C malloc(), so it is copied into a
+        // Rust-owned Vec and freed with free() rather than adopted by the Vec
+        let mut data: *mut ktx_uint8_t = ptr::null_mut();
+        let mut size: ktx_size_t = 0;
+
+        let result = unsafe { ktxTexture2_WriteToMemory(view.as_ptr(), &mut data, &mut size) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        if data.is_null() || size == 0 {
+            return Err(Error::InvalidOperation);
+        }
+
+        buf.clear();
+        unsafe {
+            buf.extend_from_slice(std::slice::from_raw_parts(data, size));
+            // libktx allocates the output with malloc()
+            libc::free(data as *mut libc::c_void);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a shallow copy of the texture to hand to the libktx writers
+    ///
+    /// libktx sorts the key/value list and appends its version to the
+    /// `KTXwriter` entry while writing, so the copy gets a list of its own and
+    /// writing through `&self` never mutates `self`. With `add_writer` the
+    /// default `KTXwriter` entry is added to the copy unless one is present.
+    fn write_view(&self, add_writer: bool) -> Result<WriteView> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        // Safety: the copy only reads through the pointers it shares with
+        // `self`, and its key/value list is replaced before it is used
+        let mut view = WriteView {
+            texture: unsafe { ptr::read(self.texture) },
+        };
+        // Cleared first so dropping the view after a failed copy never frees
+        // the list owned by `self`
+        view.texture.kvDataHead = ptr::null_mut();
+        let result = unsafe {
+            ktxHashList_ConstructCopy(&mut view.texture.kvDataHead, (*self.texture).kvDataHead)
+        };
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        if !add_writer {
+            return Ok(view);
+        }
+        match self.get_metadata_ref(WRITER_KEY) {
+            Ok(_) => return Ok(view),
+            Err(Error::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        let c_key = CString::new(WRITER_KEY).map_err(|_| Error::InvalidValue)?;
+        let value = concat!("ktx2-rw ", env!("CARGO_PKG_VERSION"), "\0");
+
+        let result = unsafe {
+            ktxHashList_AddKVPair(
+                &mut view.texture.kvDataHead,
+                c_key.as_ptr(),
+                value.len() as u32,
+                value.as_ptr() as *const libc::c_void,
+            )
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(view)
+    }
+
+    /// Writes the premultiplied-alpha bit of the basic descriptor block
+    fn set_dfd_premultiplied(&mut self, premultiplied: bool) {
+        // pDfd[0] is the total size; the basic block's word 2 holds the flags
+        // in its top byte
+        const FLAGS_WORD: usize = 3;
+        const FLAGS_SHIFT: u32 = 24;
+
+        unsafe {
+            let dfd = (*self.texture).pDfd;
+            if dfd.is_null() {
+                return;
+            }
+            let flag = _khr_df_flags_e_KHR_DF_FLAG_ALPHA_PREMULTIPLIED << FLAGS_SHIFT;
+            let word = dfd.add(FLAGS_WORD);
+            if premultiplied {
+                *word |= flag;
+            } else {
+                *word &= !flag;
+            }
+        }
+    }
+
+    /// Fails unless the texture holds data Basis Universal can encode
+    fn check_basis_input(&self) -> Result<()> {
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+        if self.dfd()?.samples.iter().any(|sample| sample.float) {
+            return Err(Error::UnsupportedFormat(self.vk_format()));
+        }
+        Ok(())
+    }
+
+    /// Runs a libktx call that replaces the image data and the data format
+    /// descriptor, keeping the premultiplied-alpha flag
+    ///
+    /// The image data is detached first so libktx can free it, and the flag is
+    /// restored because libktx writes a fresh descriptor without it.
+    fn with_preserved_dfd_flags(
+        &mut self,
+        call: impl FnOnce(*mut ktxTexture2) -> u32,
+    ) -> Result<()> {
+        self.detach_image_data()?;
+        let premultiplied = self.is_premultiplied();
+        let result = call(self.texture);
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        self.set_dfd_premultiplied(premultiplied);
+        Ok(())
+    }
+
+    /// Moves image data that points into a [`from_vec`](Self::from_vec) buffer
+    /// into a libktx allocation, so libktx can free or replace it
+    fn detach_image_data(&mut self) -> Result<()> {
+        let Some(Source::Aliased(bytes)) = &self.source else {
+            return Ok(());
+        };
+
+        unsafe {
+            let texture = &mut *self.texture;
+            let copy = libc::malloc(texture.dataSize.max(1)) as *mut u8;
+            if copy.is_null() {
+                return Err(Error::OutOfMemory);
+            }
+            debug_assert!(bytes.as_ptr_range().contains(&(texture.pData as *const u8)));
+            ptr::copy_nonoverlapping(texture.pData, copy, texture.dataSize);
+            texture.pData = copy;
+        }
+
+        self.source = None;
+        Ok(())
+    }
+
+    /// Returns the `(level, layer, face_slice)` index of every image
+    ///
+    /// The last index walks the depth slices of a 3D texture and the faces of
+    /// anything else.
+    fn image_indices(&self) -> Vec<(u32, u32, u32)> {
+        let mut indices = Vec::new();
+        for level in 0..self.levels() {
+            let slices = self.face_slices(level);
+            for layer in 0..self.layers() {
+                for slice in 0..slices {
+                    indices.push((level, layer, slice));
+                }
+            }
+        }
+        indices
+    }
+
+    /// Returns a mutable view of every uncompressed image with its level
+    ///
+    /// All images are located before any view is handed out, so edits that
+    /// walk the whole texture fail before writing anything instead of leaving
+    /// it partly modified.
+    fn images_mut(&mut self) -> Result<Vec<(u32, &mut [u8])>> {
+        if self.is_compressed() || self.needs_transcoding() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let ranges = self
+            .image_indices()
+            .into_iter()
+            .map(|(level, layer, face)| Ok((level, self.image_range(level, layer, face)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Safety: every range was checked against dataSize, and images never
+        // overlap, so the slices are disjoint
+        let data = unsafe { (*self.texture).pData };
+        Ok(ranges
+            .into_iter()
+            .map(|(level, (offset, size))| unsafe {
+                (
+                    level,
+                    std::slice::from_raw_parts_mut(data.add(offset), size),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the number of depth slices of a 3D texture at `level`, or the
+    /// number of faces otherwise, matching the bound libktx's `GetImageOffset`
+    /// puts on its `faceSlice` argument
+    fn face_slices(&self, level: u32) -> u32 {
+        if self.depth() > 1 {
+            (self.depth() >> level).max(1)
+        } else {
+            self.faces()
+        }
+    }
+
+    /// Returns the validated `(offset, size)` of an image within `pData`
+    fn image_range(&self, level: u32, layer: u32, face: u32) -> Result<(usize, usize)> {
+        // Safety: Check texture validity first
+        if self.texture.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        // Validate parameters against texture properties
+        let texture = unsafe { &*self.texture };
+        if level >= texture.numLevels
+            || layer >= texture.numLayers
+            || face >= self.face_slices(level)
+        {
+            return Err(Error::InvalidValue);
+        }
+
+        let mut offset = 0usize;
+
+        let result = unsafe {
+            let vtbl = texture.vtbl;
+            if vtbl.is_null() {
+                return Err(Error::InvalidOperation);
+            }
+
+            let get_image_offset = (*vtbl).GetImageOffset;
+            match get_image_offset {
+                Some(func) => func(
+                    self.texture as *mut ktxTexture,
+                    level,
+                    layer,
+                    face,
+                    &mut offset,
+                ),
+                None => return Err(Error::UnsupportedFeature),
+            }
+        };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        let size = unsafe {
+            let vtbl = texture.vtbl;
+            let get_image_size = (*vtbl).GetImageSize;
+            match get_image_size {
+                Some(func) => func(self.texture as *mut ktxTexture, level),
+                None => return Err(Error::UnsupportedFeature),
+            }
+        };
+
+        // Safety: Validate the calculated bounds
+        if texture.pData.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let data_size = texture.dataSize;
+        if offset.saturating_add(size) > data_size {
+            return Err(Error::InvalidOperation);
+        }
+
+        Ok((offset, size))
+    }
+
+    /// Returns the offset and size in `pData` of every image of `level`
+    fn level_range(&self, level: u32) -> Result<(usize, usize)> {
+        let (offset, _) = self.image_range(level, 0, 0)?;
+
+        let size = unsafe {
+            match (*(*self.texture).vtbl).GetLevelSize {
+                Some(func) => func(self.texture as *mut ktxTexture, level),
+                None => return Err(Error::UnsupportedFeature),
+            }
+        };
+
+        if offset.saturating_add(size) > unsafe { (*self.texture).dataSize } {
+            return Err(Error::InvalidOperation);
+        }
+        Ok((offset, size))
+    }
+
+    /// Creates an independent deep copy of the underlying `ktxTexture2`
+    fn duplicate(&self) -> Result<Self> {
+        // ktxTexture2_CreateCopy copies pData unconditionally
+        if !self.has_image_data() {
+            return Err(Error::InvalidOperation);
+        }
+
+        let mut texture: *mut ktxTexture2 = ptr::null_mut();
+
+        let result = unsafe { ktxTexture2_CreateCopy(self.texture, &mut texture) };
+
+        if let Some(error) = Error::try_from_code(result) {
+            return Err(error);
+        }
+
+        Ok(Self {
+            texture,
+            source: None,
+        })
+    }
+}
+
+/// Converts `path` to the NUL-terminated string libktx hands to `fopen()`
+///
+/// Unix paths are passed through as raw bytes, so non-UTF-8 names work.
+#[cfg(not(windows))]
+fn c_path(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path.to_str().ok_or(Error::InvalidValue)?.as_bytes();
+
+    CString::new(bytes).map_err(|_| Error::InvalidValue)
+}
+
+/// Returns the size and modification time of the file at `path`
+#[cfg(not(windows))]
+fn file_stamp(path: &Path) -> std::io::Result<FileStamp> {
+    let metadata = std::fs::metadata(path)?;
+    Ok((metadata.len(), metadata.modified().ok()))
+}
+
+/// Runs `write` on a temporary file in the directory of `path` and renames the
+/// result over `path` once it succeeds and is synced to disk
+pub(crate) fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut std::fs::File) -> Result<()>,
+) -> Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().ok_or(Error::InvalidValue)?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = std::fs::File::create(&temp_path)
+        .map_err(|_| Error::FileOpenFailed)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()?;
+            Ok(())
+        })
+        .and_then(|()| Ok(std::fs::rename(&temp_path, path)?));
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Returns the file offset of the first level's data in a KTX2 file
+///
+/// Levels are stored smallest first, and libktx keeps them contiguous in
+/// `pData` starting at this offset.
+fn first_level_offset(data: &[u8], levels: u32) -> Option<usize> {
+    const LEVEL_INDEX_OFFSET: usize = 80;
+    const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+    (0..levels as usize)
+        .map(|level| {
+            let start = LEVEL_INDEX_OFFSET + level * LEVEL_INDEX_ENTRY_SIZE;
+            let bytes = data.get(start..start + 8)?;
+            usize::try_from(u64::from_le_bytes(bytes.try_into().ok()?)).ok()
+        })
+        .try_fold(usize::MAX, |first, offset| Some(first.min(offset?)))
+}
+
+/// Header copy made by [`Ktx2Texture::write_view`]
+///
+/// Shares the image data and descriptor with the texture it was copied from
+/// but owns its key/value list, which is freed on drop.
+struct WriteView {
+    texture: ktxTexture2,
+}
+
+impl WriteView {
+    fn as_ptr(&mut self) -> *mut ktxTexture2 {
+        &mut self.texture
+    }
+}
+
+impl Drop for WriteView {
+    fn drop(&mut self) {
+        unsafe { ktxHashList_Destruct(&mut self.texture.kvDataHead) };
+    }
+}
+
+/// Walks the key/value list of a texture without copying values
+struct MetadataIter<'a> {
+    entry: *mut ktxHashListEntry,
+    _texture: PhantomData<&'a Ktx2Texture>,
+}
+
+impl<'a> Iterator for MetadataIter<'a> {
+    type Item = (String, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.entry.is_null() {
+            let entry = self.entry;
+            self.entry = unsafe { ktxHashList_Next(entry) };
+
+            let mut key_len = 0u32;
+            let mut key: *mut std::os::raw::c_char = ptr::null_mut();
+            let mut value_len = 0u32;
+            let mut value: *mut libc::c_void = ptr::null_mut();
+
+            let found = unsafe {
+                ktxHashListEntry_GetKey(entry, &mut key_len, &mut key)
+                    == ktx_error_code_e_KTX_SUCCESS
+                    && ktxHashListEntry_GetValue(entry, &mut value_len, &mut value)
+                        == ktx_error_code_e_KTX_SUCCESS
+            };
+            if !found || key.is_null() {
+                continue;
+            }
+
+            // The stored key length includes the NUL terminator
+            let key_bytes =
+                unsafe { std::slice::from_raw_parts(key as *const u8, key_len as usize) };
+            let key_bytes = key_bytes.strip_suffix(&[0]).unwrap_or(key_bytes);
+            let key = String::from_utf8_lossy(key_bytes).into_owned();
+
+            let value = if value.is_null() {
+                &[][..]
+            } else {
+                unsafe { std::slice::from_raw_parts(value as *const u8, value_len as usize) }
+            };
+
+            return Some((key, value));
+        }
+
+        None
+    }
+}
+
+impl Drop for Ktx2Texture {
+    fn drop(&mut self) {
+        if !self.texture.is_null() {
+            unsafe {
+                // The aliased buffer is freed by Rust, not by libktx
+                if matches!(self.source, Some(Source::Aliased(_))) {
+                    (*self.texture).pData = ptr::null_mut();
+                }
+                ktxTexture2_Destroy(self.texture);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Ktx2Texture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ktx2Texture")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("depth", &self.depth())
+            .field("layers", &self.layers())
+            .field("faces", &self.faces())
+            .field("levels", &self.levels())
+            .field("vk_format", &self.vk_format())
+            .field("is_array", &self.is_array())
+            .field("is_cubemap", &self.is_cubemap())
+            .field("is_compressed", &self.is_compressed())
+            .finish()
+    }
+}
+
+unsafe impl Send for Ktx2Texture {}
+unsafe impl Sync for Ktx2Texture {}
+
+/// One image of a texture, yielded by [`Ktx2Texture::iter_images`]
+#[derive(Debug, Clone, Copy)]
+pub struct ImageRef<'a> {
+    /// Mip level, 0 being the base level
+    pub level: u32,
+    /// Array layer
+    pub layer: u32,
+    /// Cubemap face, or depth slice for 3D textures
+    pub face: u32,
+    /// Image data as stored in the texture
+    pub data: &'a [u8],
+}
+
+/// Builder for [`Ktx2Texture`]
+///
+/// Provides named setters in place of the positional arguments of
+/// [`Ktx2Texture::create`], which is implemented on top of it.
+#[derive(Debug, Clone, Copy)]
+pub struct Ktx2TextureBuilder {
+    width: u32,
+    height: u32,
+    depth: u32,
+    layers: u32,
+    faces: u32,
+    levels: u32,
+    vk_format: u32,
+    num_dimensions: Option<u32>,
+}
+
+impl Default for Ktx2TextureBuilder {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            depth: 1,
+            layers: 1,
+            faces: 1,
+            levels: 1,
+            vk_format: VkFormat::Undefined.as_raw(),
+            num_dimensions: None,
+        }
+    }
+}
+
+impl Ktx2TextureBuilder {
+    /// Sets the width and height of the base level
+    ///
+    /// Required; there is no default.
+    pub fn dimensions(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the width of the base level
+    ///
+    /// Required; there is no default.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the height of the base level
+    ///
+    /// Required; there is no default.
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the depth of the base level
+    ///
+    /// Default: `1`
+    pub fn depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the number of array layers
+    ///
+    /// Default: `1`
+    pub fn layers(mut self, layers: u32) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Sets the number of faces (6 for a cubemap)
+    ///
+    /// Default: `1`
+    pub fn faces(mut self, faces: u32) -> Self {
+        self.faces = faces;
+        self
+    }
+
+    /// Makes the texture a cubemap (6 faces) or not (1 face)
+    pub fn cubemap(mut self, cubemap: bool) -> Self {
+        self.faces = if cubemap { 6 } else { 1 };
+        self
+    }
+
+    /// Same as [`layers`](Self::layers)
+    pub fn array_layers(self, layers: u32) -> Self {
+        self.layers(layers)
+    }
+
+    /// Overrides the number of dimensions, 1 to 3
+    ///
+    /// By default a depth above 1 makes a 3D texture, a height above 1 a 2D
+    /// texture and anything else a 1D texture. The override may raise that
+    /// (e.g. a 2D texture of height 1) but not lower it; building with a
+    /// lower or out-of-range value fails with [`Error::InvalidValue`].
+    pub fn num_dimensions(mut self, num_dimensions: u32) -> Self {
+        self.num_dimensions = Some(num_dimensions);
+        self
+    }
+
+    /// Sets the number of mip levels
+    ///
+    /// Default: `1`
+    pub fn levels(mut self, levels: u32) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Sets the pixel format
+    ///
+    /// Required; building without a format fails with [`Error::InvalidValue`].
+    pub fn format(mut self, format: impl Into<u32>) -> Self {
+        self.vk_format = format.into();
+        self
+    }
+
+    /// Creates the texture
+    pub fn build(self) -> Result<Ktx2Texture> {
+        if self.vk_format == VkFormat::Undefined.as_raw() {
+            return Err(Error::InvalidValue);
+        }
+
+        Ktx2Texture::create_impl(
+            &self,
+            None,
+            ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_ALLOC_STORAGE,
+        )
+    }
+}
+
+/// Builder for 2D array textures, one pushed layer at a time
+///
+/// Every layer holds the base level of one array element; with more than one
+/// mip level, the smaller levels start zeroed.
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{Ktx2ArrayBuilder, VkFormat};
+/// # fn main() -> ktx2_rw::Result<()> {
+///
+/// let texture = Ktx2ArrayBuilder::new(2, 2, VkFormat::R8Unorm)
+///     .push_layer(&[0; 4])?
+///     .push_layer(&[255; 4])?
+///     .build()?;
+/// assert_eq!(texture.layers(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ktx2ArrayBuilder {
+    width: u32,
+    height: u32,
+    levels: u32,
+    vk_format: u32,
+    layers: Vec<Vec<u8>>,
+}
+
+impl Ktx2ArrayBuilder {
+    /// Most layers a builder accepts, the common `maxImageArrayLayers` limit
+    pub const MAX_LAYERS: usize = 2048;
+
+    /// Starts an array of `width` x `height` layers in `vk_format`
+    pub fn new(width: u32, height: u32, vk_format: impl Into<u32>) -> Self {
+        Self {
+            width,
+            height,
+            levels: 1,
+            vk_format: vk_format.into(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Sets the number of mip levels
+    ///
+    /// Default: `1`
+    pub fn levels(mut self, levels: u32) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Appends a layer
+    ///
+    /// Every layer must be as long as the first one, otherwise
+    /// [`Error::ImageSizeMismatch`] is returned. Pushing more than
+    /// [`MAX_LAYERS`](Self::MAX_LAYERS) layers returns [`Error::InvalidValue`].
+    /// Like the other setters this consumes the builder, so it is gone on error.
+    pub fn push_layer(mut self, data: &[u8]) -> Result<Self> {
+        if self.layers.len() >= Self::MAX_LAYERS {
+            return Err(Error::InvalidValue);
+        }
+        if let Some(first) = self.layers.first() {
+            if data.len() != first.len() {
+                return Err(Error::ImageSizeMismatch {
+                    expected: first.len(),
+                    actual: data.len(),
+                });
+            }
+        }
+
+        self.layers.push(data.to_vec());
+        Ok(self)
+    }
+
+    /// Creates the array texture
+    ///
+    /// Fails with [`Error::InvalidValue`] if no layer was pushed, and with
+    /// [`Error::ImageSizeMismatch`] if the layers do not match the base level
+    /// size of the format.
+    pub fn build(self) -> Result<Ktx2Texture> {
+        if self.layers.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+
+        let mut texture = Ktx2Texture::create(
+            self.width,
+            self.height,
+            1,
+            self.layers.len() as u32,
+            1,
+            self.levels,
+            self.vk_format,
+        )?;
+        // `create` only marks textures with several layers as arrays
+        unsafe { (*texture.texture).isArray = true };
+
+        for (layer, data) in self.layers.iter().enumerate() {
+            texture.set_image_data(0, layer as u32, 0, data)?;
+        }
+        Ok(texture)
+    }
+}
@@ -0,0 +1,64 @@
+//! Non-fatal findings reported by [`Ktx2Texture::validate`].
+//!
+//! [`Ktx2Texture::validate`]: crate::Ktx2Texture::validate
+
+use std::fmt;
+
+/// Metadata keys defined by the KTX2 specification
+pub(crate) const RESERVED_KEYS: [&str; 10] = [
+    "KTXanimData",
+    "KTXastcDecodeMode",
+    "KTXcubemapIncomplete",
+    "KTXdxgiFormat__",
+    "KTXglFormat",
+    "KTXmetalPixelFormat",
+    "KTXorientation",
+    "KTXswizzle",
+    "KTXwriter",
+    "KTXwriterScParams",
+];
+
+/// Reserved metadata keys whose values are NUL-terminated strings
+pub(crate) const STRING_KEYS: [&str; 4] = [
+    "KTXorientation",
+    "KTXswizzle",
+    "KTXwriter",
+    "KTXwriterScParams",
+];
+
+/// A problem found by [`Ktx2Texture::validate`] that does not make the file
+/// unusable
+///
+/// [`Ktx2Texture::validate`]: crate::Ktx2Texture::validate
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidationWarning {
+    /// A key uses the reserved `KTX`/`ktx` prefix but is not defined by the
+    /// specification
+    UnknownReservedKey(String),
+    /// A string-valued entry is not NUL-terminated
+    MissingNulTerminator(String),
+    /// The `KTXorientation` entry does not have one character per dimension
+    OrientationDimensionMismatch { expected: u32, actual: u32 },
+    /// A `KTXanimData` entry is present on a texture that is not an array
+    AnimDataWithoutArray,
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationWarning::UnknownReservedKey(key) => {
+                write!(f, "Metadata key {key:?} uses the reserved KTX prefix")
+            }
+            ValidationWarning::MissingNulTerminator(key) => {
+                write!(f, "Metadata value for {key:?} is not NUL-terminated")
+            }
+            ValidationWarning::OrientationDimensionMismatch { expected, actual } => write!(
+                f,
+                "KTXorientation has {actual} characters, expected {expected}"
+            ),
+            ValidationWarning::AnimDataWithoutArray => {
+                write!(f, "KTXanimData is set on a texture that is not an array")
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Borrowed, header-only access to KTX2 data in memory.
+
+use std::fmt;
+
+use crate::error::Result;
+use crate::format::SupercompressionScheme;
+use crate::options::TextureCreateFlags;
+use crate::texture::Ktx2Texture;
+use crate::vk_format::VkFormat;
+
+/// Read-only view of a KTX2 file that borrows its bytes instead of copying them
+///
+/// Only the header, level index and metadata are parsed; the image data stays
+/// in the source slice. libktx keeps pointing into that slice, and the `'a`
+/// lifetime makes sure it outlives the view:
+///
+/// ```rust,compile_fail
+/// use ktx2_rw::Ktx2TextureView;
+///
+/// let bytes = std::fs::read("texture.ktx2").unwrap();
+/// let view = Ktx2TextureView::from_memory(&bytes).unwrap();
+/// drop(bytes); // error: `bytes` is still borrowed by `view`
+/// println!("{}", view.width());
+/// ```
+///
+/// Use [`to_owned`](Self::to_owned) to get a full [`Ktx2Texture`] with its
+/// image data loaded.
+pub struct Ktx2TextureView<'a> {
+    texture: Ktx2Texture,
+    data: &'a [u8],
+}
+
+impl<'a> Ktx2TextureView<'a> {
+    /// Parses the header and metadata of the KTX2 file in `data`
+    pub fn from_memory(data: &'a [u8]) -> Result<Self> {
+        // Safety: the view owns the texture and cannot outlive `data`
+        let texture =
+            unsafe { Ktx2Texture::from_memory_unretained(data, TextureCreateFlags::NONE)? };
+        Ok(Self { texture, data })
+    }
+
+    /// Loads a standalone texture, including its image data, from the same bytes
+    pub fn to_owned(&self) -> Result<Ktx2Texture> {
+        Ktx2Texture::from_memory(self.data)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.texture.depth()
+    }
+
+    pub fn layers(&self) -> u32 {
+        self.texture.layers()
+    }
+
+    pub fn faces(&self) -> u32 {
+        self.texture.faces()
+    }
+
+    pub fn levels(&self) -> u32 {
+        self.texture.levels()
+    }
+
+    pub fn vk_format(&self) -> VkFormat {
+        self.texture.vk_format()
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.texture.is_array()
+    }
+
+    pub fn is_cubemap(&self) -> bool {
+        self.texture.is_cubemap()
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.texture.is_compressed()
+    }
+
+    pub fn needs_transcoding(&self) -> bool {
+        self.texture.needs_transcoding()
+    }
+
+    pub fn supercompression_scheme(&self) -> SupercompressionScheme {
+        self.texture.supercompression_scheme()
+    }
+
+    /// See [`Ktx2Texture::get_metadata`]
+    pub fn get_metadata(&self, key: &str) -> Result<Vec<u8>> {
+        self.texture.get_metadata(key)
+    }
+
+    /// See [`Ktx2Texture::get_metadata_ref`]
+    pub fn get_metadata_ref(&self, key: &str) -> Result<&[u8]> {
+        self.texture.get_metadata_ref(key)
+    }
+
+    /// See [`Ktx2Texture::get_metadata_str`]
+    pub fn get_metadata_str(&self, key: &str) -> Result<String> {
+        self.texture.get_metadata_str(key)
+    }
+
+    /// See [`Ktx2Texture::metadata_keys`]
+    pub fn metadata_keys(&self) -> Result<Vec<String>> {
+        self.texture.metadata_keys()
+    }
+
+    /// See [`Ktx2Texture::metadata_iter`]
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (String, &[u8])> + '_ {
+        self.texture.metadata_iter()
+    }
+}
+
+impl fmt::Debug for Ktx2TextureView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ktx2TextureView")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("depth", &self.depth())
+            .field("layers", &self.layers())
+            .field("faces", &self.faces())
+            .field("levels", &self.levels())
+            .field("vk_format", &self.vk_format())
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
@@ -1,116 +1,346 @@
 //! Vulkan format definitions for KTX2 textures.
 //!
 //! This module defines the Vulkan format enum that corresponds to the
-//! VkFormat values used in the KTX2 library.
-
-/// Vulkan format enum
-///
-/// This represents the VkFormat values from the Vulkan specification.
-/// Only the most common formats are included here for brevity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[repr(u32)]
-pub enum VkFormat {
-    /// Undefined format
-    Undefined = 0,
-
-    /// 8-bit R component, unsigned normalized
-    R8Unorm = 9,
-
-    /// 8-bit R and G components, unsigned normalized
-    R8G8Unorm = 16,
-
-    /// 8-bit R, G and B components, unsigned normalized
-    R8G8B8Unorm = 23,
-
-    /// 8-bit R, G, B and A components, unsigned normalized
-    R8G8B8A8Unorm = 37,
-
-    /// 8-bit R, G, B and A components, sRGB
-    R8G8B8A8Srgb = 43,
-
-    /// 8-bit B, G and R components, unsigned normalized
-    B8G8R8Unorm = 30,
-
-    /// 8-bit B, G, R and A components, unsigned normalized
-    B8G8R8A8Unorm = 44,
-
-    /// 8-bit B, G, R and A components, sRGB
-    B8G8R8A8Srgb = 50,
-
-    /// 32-bit R component, signed float
-    R32Sfloat = 100,
-
-    /// 32-bit R and G components, signed float
-    R32G32Sfloat = 103,
-
-    /// 32-bit R, G, B and A components, signed float
-    R32G32B32A32Sfloat = 109,
-
-    /// 16-bit R component, signed float
-    R16Sfloat = 70,
-
-    /// 16-bit R and G components, signed float
-    R16G16Sfloat = 73,
-
-    /// 16-bit R, G, B and A components, signed float
-    R16G16B16A16Sfloat = 97,
-
-    /// BC1 compressed format (DXT1)
-    Bc1RgbUnormBlock = 131,
-
-    /// BC1 compressed format with alpha (DXT1)
-    Bc1RgbaUnormBlock = 132,
-
-    /// BC1 compressed format with alpha (DXT1), sRGB
-    Bc1RgbaSrgbBlock = 134,
-
-    /// BC3 compressed format (DXT5)
-    Bc3UnormBlock = 136,
-
-    /// BC3 compressed format (DXT5), sRGB
-    Bc3SrgbBlock = 138,
-
-    /// BC4 compressed format (unsigned)
-    Bc4UnormBlock = 137,
-
-    /// BC5 compressed format (unsigned)
-    Bc5UnormBlock = 140,
-
-    /// BC7 compressed format
-    Bc7UnormBlock = 145,
-
-    /// BC7 compressed format (sRGB)
-    Bc7SrgbBlock = 146,
-
-    /// ETC2 compressed format (RGB)
-    Etc2R8G8B8UnormBlock = 147,
-
-    /// ETC2 compressed format (RGB, sRGB)
-    Etc2R8G8B8SrgbBlock = 148,
-
-    /// ETC2 compressed format with alpha
-    Etc2R8G8B8A1UnormBlock = 149,
-
-    /// ETC2 compressed format with alpha (sRGB)
-    Etc2R8G8B8A1SrgbBlock = 150,
-
-    /// ETC2 compressed format with EAC alpha
-    Etc2R8G8B8A8UnormBlock = 151,
-
-    /// ETC2 compressed format with EAC alpha (sRGB)
-    Etc2R8G8B8A8SrgbBlock = 152,
-
-    /// ASTC 4x4 compressed format
-    Astc4x4UnormBlock = 157,
-
-    /// ASTC 4x4 compressed format (sRGB)
-    Astc4x4SrgbBlock = 158,
+//! VkFormat values used in the KTX2 library. The variants and their layout
+//! properties are generated from one table, so the enum, [`VkFormat::from_raw`]
+//! and the accessors cannot drift apart.
+
+use std::fmt;
+
+/// Defines [`VkFormat`] from rows of
+/// `Variant = raw, "VK_FORMAT_NAME", (block width, block height), bytes per block, channels;`
+macro_rules! vk_formats {
+    ($(
+        $variant:ident = $raw:literal,
+        $name:literal,
+        ($block_width:literal, $block_height:literal),
+        $bytes:literal,
+        $channels:literal;
+    )*) => {
+        /// Vulkan format enum
+        ///
+        /// This represents the VkFormat values from the Vulkan specification,
+        /// with the discriminants equal to the spec values: the core formats,
+        /// the 4:2:2 and multi-planar YCbCr formats, ASTC HDR, PVRTC and the
+        /// other extension formats up to Vulkan 1.4. New formats may be added
+        /// in minor releases, so matches need a wildcard arm.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u32)]
+        #[non_exhaustive]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum VkFormat {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant = $raw,
+            )*
+        }
 
-    /// ASTC 8x8 compressed format
-    Astc8x8UnormBlock = 165,
+        impl VkFormat {
+            /// Every format, in ascending order of raw value
+            pub const ALL: &'static [VkFormat] = &[$(VkFormat::$variant),*];
+
+            /// Create a VkFormat from a raw value
+            ///
+            /// Returns None if the value doesn't correspond to a known format
+            pub fn from_raw(value: u32) -> Option<Self> {
+                match value {
+                    $($raw => Some(VkFormat::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the canonical Vulkan name of the format, e.g. `VK_FORMAT_R8G8B8A8_UNORM`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(VkFormat::$variant => $name,)*
+                }
+            }
+
+            /// Returns the number of logical channels in the format
+            ///
+            /// Block-compressed formats report the channels they decode to,
+            /// depth and stencil each count as one and YCbCr formats report
+            /// 3. Padding bits and shared exponents are not counted.
+            /// `Undefined` reports 0.
+            pub fn channel_count(&self) -> u8 {
+                match self {
+                    $(VkFormat::$variant => $channels,)*
+                }
+            }
+
+            /// Returns the block width and height in texels
+            ///
+            /// Uncompressed formats report `(1, 1)` and 4:2:2 formats `(2, 1)`.
+            pub fn block_size(&self) -> (u32, u32) {
+                match self {
+                    $(VkFormat::$variant => ($block_width, $block_height),)*
+                }
+            }
+
+            /// Returns the size in bytes of one block (or texel, if uncompressed)
+            ///
+            /// `Undefined` and the multi-planar formats, whose planes have
+            /// different texel sizes, report 0.
+            pub fn bytes_per_block(&self) -> u32 {
+                match self {
+                    $(VkFormat::$variant => $bytes,)*
+                }
+            }
+        }
+    };
+}
 
-    /// ASTC 8x8 compressed format (sRGB)
-    Astc8x8SrgbBlock = 172,
+vk_formats! {
+    Undefined = 0, "VK_FORMAT_UNDEFINED", (1, 1), 0, 0;
+    R4G4UnormPack8 = 1, "VK_FORMAT_R4G4_UNORM_PACK8", (1, 1), 1, 2;
+    R4G4B4A4UnormPack16 = 2, "VK_FORMAT_R4G4B4A4_UNORM_PACK16", (1, 1), 2, 4;
+    B4G4R4A4UnormPack16 = 3, "VK_FORMAT_B4G4R4A4_UNORM_PACK16", (1, 1), 2, 4;
+    R5G6B5UnormPack16 = 4, "VK_FORMAT_R5G6B5_UNORM_PACK16", (1, 1), 2, 3;
+    B5G6R5UnormPack16 = 5, "VK_FORMAT_B5G6R5_UNORM_PACK16", (1, 1), 2, 3;
+    R5G5B5A1UnormPack16 = 6, "VK_FORMAT_R5G5B5A1_UNORM_PACK16", (1, 1), 2, 4;
+    B5G5R5A1UnormPack16 = 7, "VK_FORMAT_B5G5R5A1_UNORM_PACK16", (1, 1), 2, 4;
+    A1R5G5B5UnormPack16 = 8, "VK_FORMAT_A1R5G5B5_UNORM_PACK16", (1, 1), 2, 4;
+    R8Unorm = 9, "VK_FORMAT_R8_UNORM", (1, 1), 1, 1;
+    R8Snorm = 10, "VK_FORMAT_R8_SNORM", (1, 1), 1, 1;
+    R8Uscaled = 11, "VK_FORMAT_R8_USCALED", (1, 1), 1, 1;
+    R8Sscaled = 12, "VK_FORMAT_R8_SSCALED", (1, 1), 1, 1;
+    R8Uint = 13, "VK_FORMAT_R8_UINT", (1, 1), 1, 1;
+    R8Sint = 14, "VK_FORMAT_R8_SINT", (1, 1), 1, 1;
+    R8Srgb = 15, "VK_FORMAT_R8_SRGB", (1, 1), 1, 1;
+    R8G8Unorm = 16, "VK_FORMAT_R8G8_UNORM", (1, 1), 2, 2;
+    R8G8Snorm = 17, "VK_FORMAT_R8G8_SNORM", (1, 1), 2, 2;
+    R8G8Uscaled = 18, "VK_FORMAT_R8G8_USCALED", (1, 1), 2, 2;
+    R8G8Sscaled = 19, "VK_FORMAT_R8G8_SSCALED", (1, 1), 2, 2;
+    R8G8Uint = 20, "VK_FORMAT_R8G8_UINT", (1, 1), 2, 2;
+    R8G8Sint = 21, "VK_FORMAT_R8G8_SINT", (1, 1), 2, 2;
+    R8G8Srgb = 22, "VK_FORMAT_R8G8_SRGB", (1, 1), 2, 2;
+    R8G8B8Unorm = 23, "VK_FORMAT_R8G8B8_UNORM", (1, 1), 3, 3;
+    R8G8B8Snorm = 24, "VK_FORMAT_R8G8B8_SNORM", (1, 1), 3, 3;
+    R8G8B8Uscaled = 25, "VK_FORMAT_R8G8B8_USCALED", (1, 1), 3, 3;
+    R8G8B8Sscaled = 26, "VK_FORMAT_R8G8B8_SSCALED", (1, 1), 3, 3;
+    R8G8B8Uint = 27, "VK_FORMAT_R8G8B8_UINT", (1, 1), 3, 3;
+    R8G8B8Sint = 28, "VK_FORMAT_R8G8B8_SINT", (1, 1), 3, 3;
+    R8G8B8Srgb = 29, "VK_FORMAT_R8G8B8_SRGB", (1, 1), 3, 3;
+    B8G8R8Unorm = 30, "VK_FORMAT_B8G8R8_UNORM", (1, 1), 3, 3;
+    B8G8R8Snorm = 31, "VK_FORMAT_B8G8R8_SNORM", (1, 1), 3, 3;
+    B8G8R8Uscaled = 32, "VK_FORMAT_B8G8R8_USCALED", (1, 1), 3, 3;
+    B8G8R8Sscaled = 33, "VK_FORMAT_B8G8R8_SSCALED", (1, 1), 3, 3;
+    B8G8R8Uint = 34, "VK_FORMAT_B8G8R8_UINT", (1, 1), 3, 3;
+    B8G8R8Sint = 35, "VK_FORMAT_B8G8R8_SINT", (1, 1), 3, 3;
+    B8G8R8Srgb = 36, "VK_FORMAT_B8G8R8_SRGB", (1, 1), 3, 3;
+    R8G8B8A8Unorm = 37, "VK_FORMAT_R8G8B8A8_UNORM", (1, 1), 4, 4;
+    R8G8B8A8Snorm = 38, "VK_FORMAT_R8G8B8A8_SNORM", (1, 1), 4, 4;
+    R8G8B8A8Uscaled = 39, "VK_FORMAT_R8G8B8A8_USCALED", (1, 1), 4, 4;
+    R8G8B8A8Sscaled = 40, "VK_FORMAT_R8G8B8A8_SSCALED", (1, 1), 4, 4;
+    R8G8B8A8Uint = 41, "VK_FORMAT_R8G8B8A8_UINT", (1, 1), 4, 4;
+    R8G8B8A8Sint = 42, "VK_FORMAT_R8G8B8A8_SINT", (1, 1), 4, 4;
+    R8G8B8A8Srgb = 43, "VK_FORMAT_R8G8B8A8_SRGB", (1, 1), 4, 4;
+    B8G8R8A8Unorm = 44, "VK_FORMAT_B8G8R8A8_UNORM", (1, 1), 4, 4;
+    B8G8R8A8Snorm = 45, "VK_FORMAT_B8G8R8A8_SNORM", (1, 1), 4, 4;
+    B8G8R8A8Uscaled = 46, "VK_FORMAT_B8G8R8A8_USCALED", (1, 1), 4, 4;
+    B8G8R8A8Sscaled = 47, "VK_FORMAT_B8G8R8A8_SSCALED", (1, 1), 4, 4;
+    B8G8R8A8Uint = 48, "VK_FORMAT_B8G8R8A8_UINT", (1, 1), 4, 4;
+    B8G8R8A8Sint = 49, "VK_FORMAT_B8G8R8A8_SINT", (1, 1), 4, 4;
+    B8G8R8A8Srgb = 50, "VK_FORMAT_B8G8R8A8_SRGB", (1, 1), 4, 4;
+    A8B8G8R8UnormPack32 = 51, "VK_FORMAT_A8B8G8R8_UNORM_PACK32", (1, 1), 4, 4;
+    A8B8G8R8SnormPack32 = 52, "VK_FORMAT_A8B8G8R8_SNORM_PACK32", (1, 1), 4, 4;
+    A8B8G8R8UscaledPack32 = 53, "VK_FORMAT_A8B8G8R8_USCALED_PACK32", (1, 1), 4, 4;
+    A8B8G8R8SscaledPack32 = 54, "VK_FORMAT_A8B8G8R8_SSCALED_PACK32", (1, 1), 4, 4;
+    A8B8G8R8UintPack32 = 55, "VK_FORMAT_A8B8G8R8_UINT_PACK32", (1, 1), 4, 4;
+    A8B8G8R8SintPack32 = 56, "VK_FORMAT_A8B8G8R8_SINT_PACK32", (1, 1), 4, 4;
+    A8B8G8R8SrgbPack32 = 57, "VK_FORMAT_A8B8G8R8_SRGB_PACK32", (1, 1), 4, 4;
+    A2R10G10B10UnormPack32 = 58, "VK_FORMAT_A2R10G10B10_UNORM_PACK32", (1, 1), 4, 4;
+    A2R10G10B10SnormPack32 = 59, "VK_FORMAT_A2R10G10B10_SNORM_PACK32", (1, 1), 4, 4;
+    A2R10G10B10UscaledPack32 = 60, "VK_FORMAT_A2R10G10B10_USCALED_PACK32", (1, 1), 4, 4;
+    A2R10G10B10SscaledPack32 = 61, "VK_FORMAT_A2R10G10B10_SSCALED_PACK32", (1, 1), 4, 4;
+    A2R10G10B10UintPack32 = 62, "VK_FORMAT_A2R10G10B10_UINT_PACK32", (1, 1), 4, 4;
+    A2R10G10B10SintPack32 = 63, "VK_FORMAT_A2R10G10B10_SINT_PACK32", (1, 1), 4, 4;
+    A2B10G10R10UnormPack32 = 64, "VK_FORMAT_A2B10G10R10_UNORM_PACK32", (1, 1), 4, 4;
+    A2B10G10R10SnormPack32 = 65, "VK_FORMAT_A2B10G10R10_SNORM_PACK32", (1, 1), 4, 4;
+    A2B10G10R10UscaledPack32 = 66, "VK_FORMAT_A2B10G10R10_USCALED_PACK32", (1, 1), 4, 4;
+    A2B10G10R10SscaledPack32 = 67, "VK_FORMAT_A2B10G10R10_SSCALED_PACK32", (1, 1), 4, 4;
+    A2B10G10R10UintPack32 = 68, "VK_FORMAT_A2B10G10R10_UINT_PACK32", (1, 1), 4, 4;
+    A2B10G10R10SintPack32 = 69, "VK_FORMAT_A2B10G10R10_SINT_PACK32", (1, 1), 4, 4;
+    R16Unorm = 70, "VK_FORMAT_R16_UNORM", (1, 1), 2, 1;
+    R16Snorm = 71, "VK_FORMAT_R16_SNORM", (1, 1), 2, 1;
+    R16Uscaled = 72, "VK_FORMAT_R16_USCALED", (1, 1), 2, 1;
+    R16Sscaled = 73, "VK_FORMAT_R16_SSCALED", (1, 1), 2, 1;
+    R16Uint = 74, "VK_FORMAT_R16_UINT", (1, 1), 2, 1;
+    R16Sint = 75, "VK_FORMAT_R16_SINT", (1, 1), 2, 1;
+    R16Sfloat = 76, "VK_FORMAT_R16_SFLOAT", (1, 1), 2, 1;
+    R16G16Unorm = 77, "VK_FORMAT_R16G16_UNORM", (1, 1), 4, 2;
+    R16G16Snorm = 78, "VK_FORMAT_R16G16_SNORM", (1, 1), 4, 2;
+    R16G16Uscaled = 79, "VK_FORMAT_R16G16_USCALED", (1, 1), 4, 2;
+    R16G16Sscaled = 80, "VK_FORMAT_R16G16_SSCALED", (1, 1), 4, 2;
+    R16G16Uint = 81, "VK_FORMAT_R16G16_UINT", (1, 1), 4, 2;
+    R16G16Sint = 82, "VK_FORMAT_R16G16_SINT", (1, 1), 4, 2;
+    R16G16Sfloat = 83, "VK_FORMAT_R16G16_SFLOAT", (1, 1), 4, 2;
+    R16G16B16Unorm = 84, "VK_FORMAT_R16G16B16_UNORM", (1, 1), 6, 3;
+    R16G16B16Snorm = 85, "VK_FORMAT_R16G16B16_SNORM", (1, 1), 6, 3;
+    R16G16B16Uscaled = 86, "VK_FORMAT_R16G16B16_USCALED", (1, 1), 6, 3;
+    R16G16B16Sscaled = 87, "VK_FORMAT_R16G16B16_SSCALED", (1, 1), 6, 3;
+    R16G16B16Uint = 88, "VK_FORMAT_R16G16B16_UINT", (1, 1), 6, 3;
+    R16G16B16Sint = 89, "VK_FORMAT_R16G16B16_SINT", (1, 1), 6, 3;
+    R16G16B16Sfloat = 90, "VK_FORMAT_R16G16B16_SFLOAT", (1, 1), 6, 3;
+    R16G16B16A16Unorm = 91, "VK_FORMAT_R16G16B16A16_UNORM", (1, 1), 8, 4;
+    R16G16B16A16Snorm = 92, "VK_FORMAT_R16G16B16A16_SNORM", (1, 1), 8, 4;
+    R16G16B16A16Uscaled = 93, "VK_FORMAT_R16G16B16A16_USCALED", (1, 1), 8, 4;
+    R16G16B16A16Sscaled = 94, "VK_FORMAT_R16G16B16A16_SSCALED", (1, 1), 8, 4;
+    R16G16B16A16Uint = 95, "VK_FORMAT_R16G16B16A16_UINT", (1, 1), 8, 4;
+    R16G16B16A16Sint = 96, "VK_FORMAT_R16G16B16A16_SINT", (1, 1), 8, 4;
+    R16G16B16A16Sfloat = 97, "VK_FORMAT_R16G16B16A16_SFLOAT", (1, 1), 8, 4;
+    R32Uint = 98, "VK_FORMAT_R32_UINT", (1, 1), 4, 1;
+    R32Sint = 99, "VK_FORMAT_R32_SINT", (1, 1), 4, 1;
+    R32Sfloat = 100, "VK_FORMAT_R32_SFLOAT", (1, 1), 4, 1;
+    R32G32Uint = 101, "VK_FORMAT_R32G32_UINT", (1, 1), 8, 2;
+    R32G32Sint = 102, "VK_FORMAT_R32G32_SINT", (1, 1), 8, 2;
+    R32G32Sfloat = 103, "VK_FORMAT_R32G32_SFLOAT", (1, 1), 8, 2;
+    R32G32B32Uint = 104, "VK_FORMAT_R32G32B32_UINT", (1, 1), 12, 3;
+    R32G32B32Sint = 105, "VK_FORMAT_R32G32B32_SINT", (1, 1), 12, 3;
+    R32G32B32Sfloat = 106, "VK_FORMAT_R32G32B32_SFLOAT", (1, 1), 12, 3;
+    R32G32B32A32Uint = 107, "VK_FORMAT_R32G32B32A32_UINT", (1, 1), 16, 4;
+    R32G32B32A32Sint = 108, "VK_FORMAT_R32G32B32A32_SINT", (1, 1), 16, 4;
+    R32G32B32A32Sfloat = 109, "VK_FORMAT_R32G32B32A32_SFLOAT", (1, 1), 16, 4;
+    R64Uint = 110, "VK_FORMAT_R64_UINT", (1, 1), 8, 1;
+    R64Sint = 111, "VK_FORMAT_R64_SINT", (1, 1), 8, 1;
+    R64Sfloat = 112, "VK_FORMAT_R64_SFLOAT", (1, 1), 8, 1;
+    R64G64Uint = 113, "VK_FORMAT_R64G64_UINT", (1, 1), 16, 2;
+    R64G64Sint = 114, "VK_FORMAT_R64G64_SINT", (1, 1), 16, 2;
+    R64G64Sfloat = 115, "VK_FORMAT_R64G64_SFLOAT", (1, 1), 16, 2;
+    R64G64B64Uint = 116, "VK_FORMAT_R64G64B64_UINT", (1, 1), 24, 3;
+    R64G64B64Sint = 117, "VK_FORMAT_R64G64B64_SINT", (1, 1), 24, 3;
+    R64G64B64Sfloat = 118, "VK_FORMAT_R64G64B64_SFLOAT", (1, 1), 24, 3;
+    R64G64B64A64Uint = 119, "VK_FORMAT_R64G64B64A64_UINT", (1, 1), 32, 4;
+    R64G64B64A64Sint = 120, "VK_FORMAT_R64G64B64A64_SINT", (1, 1), 32, 4;
+    R64G64B64A64Sfloat = 121, "VK_FORMAT_R64G64B64A64_SFLOAT", (1, 1), 32, 4;
+    B10G11R11UfloatPack32 = 122, "VK_FORMAT_B10G11R11_UFLOAT_PACK32", (1, 1), 4, 3;
+    E5B9G9R9UfloatPack32 = 123, "VK_FORMAT_E5B9G9R9_UFLOAT_PACK32", (1, 1), 4, 3;
+    D16Unorm = 124, "VK_FORMAT_D16_UNORM", (1, 1), 2, 1;
+    X8D24UnormPack32 = 125, "VK_FORMAT_X8_D24_UNORM_PACK32", (1, 1), 4, 1;
+    D32Sfloat = 126, "VK_FORMAT_D32_SFLOAT", (1, 1), 4, 1;
+    S8Uint = 127, "VK_FORMAT_S8_UINT", (1, 1), 1, 1;
+    D16UnormS8Uint = 128, "VK_FORMAT_D16_UNORM_S8_UINT", (1, 1), 3, 2;
+    D24UnormS8Uint = 129, "VK_FORMAT_D24_UNORM_S8_UINT", (1, 1), 4, 2;
+    D32SfloatS8Uint = 130, "VK_FORMAT_D32_SFLOAT_S8_UINT", (1, 1), 5, 2;
+    Bc1RgbUnormBlock = 131, "VK_FORMAT_BC1_RGB_UNORM_BLOCK", (4, 4), 8, 3;
+    Bc1RgbSrgbBlock = 132, "VK_FORMAT_BC1_RGB_SRGB_BLOCK", (4, 4), 8, 3;
+    Bc1RgbaUnormBlock = 133, "VK_FORMAT_BC1_RGBA_UNORM_BLOCK", (4, 4), 8, 4;
+    Bc1RgbaSrgbBlock = 134, "VK_FORMAT_BC1_RGBA_SRGB_BLOCK", (4, 4), 8, 4;
+    Bc2UnormBlock = 135, "VK_FORMAT_BC2_UNORM_BLOCK", (4, 4), 16, 4;
+    Bc2SrgbBlock = 136, "VK_FORMAT_BC2_SRGB_BLOCK", (4, 4), 16, 4;
+    Bc3UnormBlock = 137, "VK_FORMAT_BC3_UNORM_BLOCK", (4, 4), 16, 4;
+    Bc3SrgbBlock = 138, "VK_FORMAT_BC3_SRGB_BLOCK", (4, 4), 16, 4;
+    Bc4UnormBlock = 139, "VK_FORMAT_BC4_UNORM_BLOCK", (4, 4), 8, 1;
+    Bc4SnormBlock = 140, "VK_FORMAT_BC4_SNORM_BLOCK", (4, 4), 8, 1;
+    Bc5UnormBlock = 141, "VK_FORMAT_BC5_UNORM_BLOCK", (4, 4), 16, 2;
+    Bc5SnormBlock = 142, "VK_FORMAT_BC5_SNORM_BLOCK", (4, 4), 16, 2;
+    Bc6hUfloatBlock = 143, "VK_FORMAT_BC6H_UFLOAT_BLOCK", (4, 4), 16, 3;
+    Bc6hSfloatBlock = 144, "VK_FORMAT_BC6H_SFLOAT_BLOCK", (4, 4), 16, 3;
+    Bc7UnormBlock = 145, "VK_FORMAT_BC7_UNORM_BLOCK", (4, 4), 16, 4;
+    Bc7SrgbBlock = 146, "VK_FORMAT_BC7_SRGB_BLOCK", (4, 4), 16, 4;
+    Etc2R8G8B8UnormBlock = 147, "VK_FORMAT_ETC2_R8G8B8_UNORM_BLOCK", (4, 4), 8, 3;
+    Etc2R8G8B8SrgbBlock = 148, "VK_FORMAT_ETC2_R8G8B8_SRGB_BLOCK", (4, 4), 8, 3;
+    Etc2R8G8B8A1UnormBlock = 149, "VK_FORMAT_ETC2_R8G8B8A1_UNORM_BLOCK", (4, 4), 8, 4;
+    Etc2R8G8B8A1SrgbBlock = 150, "VK_FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK", (4, 4), 8, 4;
+    Etc2R8G8B8A8UnormBlock = 151, "VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK", (4, 4), 16, 4;
+    Etc2R8G8B8A8SrgbBlock = 152, "VK_FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK", (4, 4), 16, 4;
+    EacR11UnormBlock = 153, "VK_FORMAT_EAC_R11_UNORM_BLOCK", (4, 4), 8, 1;
+    EacR11SnormBlock = 154, "VK_FORMAT_EAC_R11_SNORM_BLOCK", (4, 4), 8, 1;
+    EacR11G11UnormBlock = 155, "VK_FORMAT_EAC_R11G11_UNORM_BLOCK", (4, 4), 16, 2;
+    EacR11G11SnormBlock = 156, "VK_FORMAT_EAC_R11G11_SNORM_BLOCK", (4, 4), 16, 2;
+    Astc4x4UnormBlock = 157, "VK_FORMAT_ASTC_4x4_UNORM_BLOCK", (4, 4), 16, 4;
+    Astc4x4SrgbBlock = 158, "VK_FORMAT_ASTC_4x4_SRGB_BLOCK", (4, 4), 16, 4;
+    Astc5x4UnormBlock = 159, "VK_FORMAT_ASTC_5x4_UNORM_BLOCK", (5, 4), 16, 4;
+    Astc5x4SrgbBlock = 160, "VK_FORMAT_ASTC_5x4_SRGB_BLOCK", (5, 4), 16, 4;
+    Astc5x5UnormBlock = 161, "VK_FORMAT_ASTC_5x5_UNORM_BLOCK", (5, 5), 16, 4;
+    Astc5x5SrgbBlock = 162, "VK_FORMAT_ASTC_5x5_SRGB_BLOCK", (5, 5), 16, 4;
+    Astc6x5UnormBlock = 163, "VK_FORMAT_ASTC_6x5_UNORM_BLOCK", (6, 5), 16, 4;
+    Astc6x5SrgbBlock = 164, "VK_FORMAT_ASTC_6x5_SRGB_BLOCK", (6, 5), 16, 4;
+    Astc6x6UnormBlock = 165, "VK_FORMAT_ASTC_6x6_UNORM_BLOCK", (6, 6), 16, 4;
+    Astc6x6SrgbBlock = 166, "VK_FORMAT_ASTC_6x6_SRGB_BLOCK", (6, 6), 16, 4;
+    Astc8x5UnormBlock = 167, "VK_FORMAT_ASTC_8x5_UNORM_BLOCK", (8, 5), 16, 4;
+    Astc8x5SrgbBlock = 168, "VK_FORMAT_ASTC_8x5_SRGB_BLOCK", (8, 5), 16, 4;
+    Astc8x6UnormBlock = 169, "VK_FORMAT_ASTC_8x6_UNORM_BLOCK", (8, 6), 16, 4;
+    Astc8x6SrgbBlock = 170, "VK_FORMAT_ASTC_8x6_SRGB_BLOCK", (8, 6), 16, 4;
+    Astc8x8UnormBlock = 171, "VK_FORMAT_ASTC_8x8_UNORM_BLOCK", (8, 8), 16, 4;
+    Astc8x8SrgbBlock = 172, "VK_FORMAT_ASTC_8x8_SRGB_BLOCK", (8, 8), 16, 4;
+    Astc10x5UnormBlock = 173, "VK_FORMAT_ASTC_10x5_UNORM_BLOCK", (10, 5), 16, 4;
+    Astc10x5SrgbBlock = 174, "VK_FORMAT_ASTC_10x5_SRGB_BLOCK", (10, 5), 16, 4;
+    Astc10x6UnormBlock = 175, "VK_FORMAT_ASTC_10x6_UNORM_BLOCK", (10, 6), 16, 4;
+    Astc10x6SrgbBlock = 176, "VK_FORMAT_ASTC_10x6_SRGB_BLOCK", (10, 6), 16, 4;
+    Astc10x8UnormBlock = 177, "VK_FORMAT_ASTC_10x8_UNORM_BLOCK", (10, 8), 16, 4;
+    Astc10x8SrgbBlock = 178, "VK_FORMAT_ASTC_10x8_SRGB_BLOCK", (10, 8), 16, 4;
+    Astc10x10UnormBlock = 179, "VK_FORMAT_ASTC_10x10_UNORM_BLOCK", (10, 10), 16, 4;
+    Astc10x10SrgbBlock = 180, "VK_FORMAT_ASTC_10x10_SRGB_BLOCK", (10, 10), 16, 4;
+    Astc12x10UnormBlock = 181, "VK_FORMAT_ASTC_12x10_UNORM_BLOCK", (12, 10), 16, 4;
+    Astc12x10SrgbBlock = 182, "VK_FORMAT_ASTC_12x10_SRGB_BLOCK", (12, 10), 16, 4;
+    Astc12x12UnormBlock = 183, "VK_FORMAT_ASTC_12x12_UNORM_BLOCK", (12, 12), 16, 4;
+    Astc12x12SrgbBlock = 184, "VK_FORMAT_ASTC_12x12_SRGB_BLOCK", (12, 12), 16, 4;
+    Pvrtc1_2bppUnormBlockImg = 1000054000, "VK_FORMAT_PVRTC1_2BPP_UNORM_BLOCK_IMG", (8, 4), 8, 4;
+    Pvrtc1_4bppUnormBlockImg = 1000054001, "VK_FORMAT_PVRTC1_4BPP_UNORM_BLOCK_IMG", (4, 4), 8, 4;
+    Pvrtc2_2bppUnormBlockImg = 1000054002, "VK_FORMAT_PVRTC2_2BPP_UNORM_BLOCK_IMG", (8, 4), 8, 4;
+    Pvrtc2_4bppUnormBlockImg = 1000054003, "VK_FORMAT_PVRTC2_4BPP_UNORM_BLOCK_IMG", (4, 4), 8, 4;
+    Pvrtc1_2bppSrgbBlockImg = 1000054004, "VK_FORMAT_PVRTC1_2BPP_SRGB_BLOCK_IMG", (8, 4), 8, 4;
+    Pvrtc1_4bppSrgbBlockImg = 1000054005, "VK_FORMAT_PVRTC1_4BPP_SRGB_BLOCK_IMG", (4, 4), 8, 4;
+    Pvrtc2_2bppSrgbBlockImg = 1000054006, "VK_FORMAT_PVRTC2_2BPP_SRGB_BLOCK_IMG", (8, 4), 8, 4;
+    Pvrtc2_4bppSrgbBlockImg = 1000054007, "VK_FORMAT_PVRTC2_4BPP_SRGB_BLOCK_IMG", (4, 4), 8, 4;
+    Astc4x4SfloatBlock = 1000066000, "VK_FORMAT_ASTC_4x4_SFLOAT_BLOCK", (4, 4), 16, 4;
+    Astc5x4SfloatBlock = 1000066001, "VK_FORMAT_ASTC_5x4_SFLOAT_BLOCK", (5, 4), 16, 4;
+    Astc5x5SfloatBlock = 1000066002, "VK_FORMAT_ASTC_5x5_SFLOAT_BLOCK", (5, 5), 16, 4;
+    Astc6x5SfloatBlock = 1000066003, "VK_FORMAT_ASTC_6x5_SFLOAT_BLOCK", (6, 5), 16, 4;
+    Astc6x6SfloatBlock = 1000066004, "VK_FORMAT_ASTC_6x6_SFLOAT_BLOCK", (6, 6), 16, 4;
+    Astc8x5SfloatBlock = 1000066005, "VK_FORMAT_ASTC_8x5_SFLOAT_BLOCK", (8, 5), 16, 4;
+    Astc8x6SfloatBlock = 1000066006, "VK_FORMAT_ASTC_8x6_SFLOAT_BLOCK", (8, 6), 16, 4;
+    Astc8x8SfloatBlock = 1000066007, "VK_FORMAT_ASTC_8x8_SFLOAT_BLOCK", (8, 8), 16, 4;
+    Astc10x5SfloatBlock = 1000066008, "VK_FORMAT_ASTC_10x5_SFLOAT_BLOCK", (10, 5), 16, 4;
+    Astc10x6SfloatBlock = 1000066009, "VK_FORMAT_ASTC_10x6_SFLOAT_BLOCK", (10, 6), 16, 4;
+    Astc10x8SfloatBlock = 1000066010, "VK_FORMAT_ASTC_10x8_SFLOAT_BLOCK", (10, 8), 16, 4;
+    Astc10x10SfloatBlock = 1000066011, "VK_FORMAT_ASTC_10x10_SFLOAT_BLOCK", (10, 10), 16, 4;
+    Astc12x10SfloatBlock = 1000066012, "VK_FORMAT_ASTC_12x10_SFLOAT_BLOCK", (12, 10), 16, 4;
+    Astc12x12SfloatBlock = 1000066013, "VK_FORMAT_ASTC_12x12_SFLOAT_BLOCK", (12, 12), 16, 4;
+    G8B8G8R8_422Unorm = 1000156000, "VK_FORMAT_G8B8G8R8_422_UNORM", (2, 1), 4, 3;
+    B8G8R8G8_422Unorm = 1000156001, "VK_FORMAT_B8G8R8G8_422_UNORM", (2, 1), 4, 3;
+    G8B8R8_3plane420Unorm = 1000156002, "VK_FORMAT_G8_B8_R8_3PLANE_420_UNORM", (1, 1), 0, 3;
+    G8B8R8_2plane420Unorm = 1000156003, "VK_FORMAT_G8_B8R8_2PLANE_420_UNORM", (1, 1), 0, 3;
+    G8B8R8_3plane422Unorm = 1000156004, "VK_FORMAT_G8_B8_R8_3PLANE_422_UNORM", (1, 1), 0, 3;
+    G8B8R8_2plane422Unorm = 1000156005, "VK_FORMAT_G8_B8R8_2PLANE_422_UNORM", (1, 1), 0, 3;
+    G8B8R8_3plane444Unorm = 1000156006, "VK_FORMAT_G8_B8_R8_3PLANE_444_UNORM", (1, 1), 0, 3;
+    R10X6UnormPack16 = 1000156007, "VK_FORMAT_R10X6_UNORM_PACK16", (1, 1), 2, 1;
+    R10X6G10X6Unorm2pack16 = 1000156008, "VK_FORMAT_R10X6G10X6_UNORM_2PACK16", (1, 1), 4, 2;
+    R10X6G10X6B10X6A10X6Unorm4pack16 = 1000156009, "VK_FORMAT_R10X6G10X6B10X6A10X6_UNORM_4PACK16", (1, 1), 8, 4;
+    G10X6B10X6G10X6R10X6_422Unorm4pack16 = 1000156010, "VK_FORMAT_G10X6B10X6G10X6R10X6_422_UNORM_4PACK16", (2, 1), 8, 3;
+    B10X6G10X6R10X6G10X6_422Unorm4pack16 = 1000156011, "VK_FORMAT_B10X6G10X6R10X6G10X6_422_UNORM_4PACK16", (2, 1), 8, 3;
+    G10X6B10X6R10X6_3plane420Unorm3pack16 = 1000156012, "VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_420_UNORM_3PACK16", (1, 1), 0, 3;
+    G10X6B10X6R10X6_2plane420Unorm3pack16 = 1000156013, "VK_FORMAT_G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16", (1, 1), 0, 3;
+    G10X6B10X6R10X6_3plane422Unorm3pack16 = 1000156014, "VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_422_UNORM_3PACK16", (1, 1), 0, 3;
+    G10X6B10X6R10X6_2plane422Unorm3pack16 = 1000156015, "VK_FORMAT_G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16", (1, 1), 0, 3;
+    G10X6B10X6R10X6_3plane444Unorm3pack16 = 1000156016, "VK_FORMAT_G10X6_B10X6_R10X6_3PLANE_444_UNORM_3PACK16", (1, 1), 0, 3;
+    R12X4UnormPack16 = 1000156017, "VK_FORMAT_R12X4_UNORM_PACK16", (1, 1), 2, 1;
+    R12X4G12X4Unorm2pack16 = 1000156018, "VK_FORMAT_R12X4G12X4_UNORM_2PACK16", (1, 1), 4, 2;
+    R12X4G12X4B12X4A12X4Unorm4pack16 = 1000156019, "VK_FORMAT_R12X4G12X4B12X4A12X4_UNORM_4PACK16", (1, 1), 8, 4;
+    G12X4B12X4G12X4R12X4_422Unorm4pack16 = 1000156020, "VK_FORMAT_G12X4B12X4G12X4R12X4_422_UNORM_4PACK16", (2, 1), 8, 3;
+    B12X4G12X4R12X4G12X4_422Unorm4pack16 = 1000156021, "VK_FORMAT_B12X4G12X4R12X4G12X4_422_UNORM_4PACK16", (2, 1), 8, 3;
+    G12X4B12X4R12X4_3plane420Unorm3pack16 = 1000156022, "VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_420_UNORM_3PACK16", (1, 1), 0, 3;
+    G12X4B12X4R12X4_2plane420Unorm3pack16 = 1000156023, "VK_FORMAT_G12X4_B12X4R12X4_2PLANE_420_UNORM_3PACK16", (1, 1), 0, 3;
+    G12X4B12X4R12X4_3plane422Unorm3pack16 = 1000156024, "VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_422_UNORM_3PACK16", (1, 1), 0, 3;
+    G12X4B12X4R12X4_2plane422Unorm3pack16 = 1000156025, "VK_FORMAT_G12X4_B12X4R12X4_2PLANE_422_UNORM_3PACK16", (1, 1), 0, 3;
+    G12X4B12X4R12X4_3plane444Unorm3pack16 = 1000156026, "VK_FORMAT_G12X4_B12X4_R12X4_3PLANE_444_UNORM_3PACK16", (1, 1), 0, 3;
+    G16B16G16R16_422Unorm = 1000156027, "VK_FORMAT_G16B16G16R16_422_UNORM", (2, 1), 8, 3;
+    B16G16R16G16_422Unorm = 1000156028, "VK_FORMAT_B16G16R16G16_422_UNORM", (2, 1), 8, 3;
+    G16B16R16_3plane420Unorm = 1000156029, "VK_FORMAT_G16_B16_R16_3PLANE_420_UNORM", (1, 1), 0, 3;
+    G16B16R16_2plane420Unorm = 1000156030, "VK_FORMAT_G16_B16R16_2PLANE_420_UNORM", (1, 1), 0, 3;
+    G16B16R16_3plane422Unorm = 1000156031, "VK_FORMAT_G16_B16_R16_3PLANE_422_UNORM", (1, 1), 0, 3;
+    G16B16R16_2plane422Unorm = 1000156032, "VK_FORMAT_G16_B16R16_2PLANE_422_UNORM", (1, 1), 0, 3;
+    G16B16R16_3plane444Unorm = 1000156033, "VK_FORMAT_G16_B16_R16_3PLANE_444_UNORM", (1, 1), 0, 3;
+    G8B8R8_2plane444Unorm = 1000330000, "VK_FORMAT_G8_B8R8_2PLANE_444_UNORM", (1, 1), 0, 3;
+    G10X6B10X6R10X6_2plane444Unorm3pack16 = 1000330001, "VK_FORMAT_G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16", (1, 1), 0, 3;
+    G12X4B12X4R12X4_2plane444Unorm3pack16 = 1000330002, "VK_FORMAT_G12X4_B12X4R12X4_2PLANE_444_UNORM_3PACK16", (1, 1), 0, 3;
+    G16B16R16_2plane444Unorm = 1000330003, "VK_FORMAT_G16_B16R16_2PLANE_444_UNORM", (1, 1), 0, 3;
+    A4R4G4B4UnormPack16 = 1000340000, "VK_FORMAT_A4R4G4B4_UNORM_PACK16", (1, 1), 2, 4;
+    A4B4G4R4UnormPack16 = 1000340001, "VK_FORMAT_A4B4G4R4_UNORM_PACK16", (1, 1), 2, 4;
+    R16G16Sfixed5Nv = 1000464000, "VK_FORMAT_R16G16_SFIXED5_NV", (1, 1), 4, 2;
+    A1B5G5R5UnormPack16 = 1000470000, "VK_FORMAT_A1B5G5R5_UNORM_PACK16", (1, 1), 2, 4;
+    A8Unorm = 1000470001, "VK_FORMAT_A8_UNORM", (1, 1), 1, 1;
 }
 
 impl VkFormat {
@@ -119,47 +349,109 @@ impl VkFormat {
         *self as u32
     }
 
-    /// Create a VkFormat from a raw value
+    /// Returns `true` if the format has an alpha channel
+    pub fn has_alpha(&self) -> bool {
+        self.channel_count() == 4
+    }
+
+    /// Returns `true` if the texels are packed into a single 8, 16 or 32-bit
+    /// word rather than stored as one byte-aligned value per channel
+    pub fn is_packed(&self) -> bool {
+        self.name().contains("PACK")
+    }
+
+    /// Returns `true` if the format stores color in the sRGB color space
+    pub fn is_srgb(&self) -> bool {
+        self.name().contains("_SRGB")
+    }
+
+    /// Returns the sRGB counterpart of the format
     ///
-    /// Returns None if the value doesn't correspond to a known format
-    pub fn from_raw(value: u32) -> Option<Self> {
-        match value {
-            0 => Some(VkFormat::Undefined),
-            9 => Some(VkFormat::R8Unorm),
-            16 => Some(VkFormat::R8G8Unorm),
-            23 => Some(VkFormat::R8G8B8Unorm),
-            37 => Some(VkFormat::R8G8B8A8Unorm),
-            43 => Some(VkFormat::R8G8B8A8Srgb),
-            30 => Some(VkFormat::B8G8R8Unorm),
-            44 => Some(VkFormat::B8G8R8A8Unorm),
-            50 => Some(VkFormat::B8G8R8A8Srgb),
-            100 => Some(VkFormat::R32Sfloat),
-            103 => Some(VkFormat::R32G32Sfloat),
-            109 => Some(VkFormat::R32G32B32A32Sfloat),
-            70 => Some(VkFormat::R16Sfloat),
-            73 => Some(VkFormat::R16G16Sfloat),
-            97 => Some(VkFormat::R16G16B16A16Sfloat),
-            131 => Some(VkFormat::Bc1RgbUnormBlock),
-            132 => Some(VkFormat::Bc1RgbaUnormBlock),
-            134 => Some(VkFormat::Bc1RgbaSrgbBlock),
-            136 => Some(VkFormat::Bc3UnormBlock),
-            138 => Some(VkFormat::Bc3SrgbBlock),
-            137 => Some(VkFormat::Bc4UnormBlock),
-            140 => Some(VkFormat::Bc5UnormBlock),
-            145 => Some(VkFormat::Bc7UnormBlock),
-            146 => Some(VkFormat::Bc7SrgbBlock),
-            147 => Some(VkFormat::Etc2R8G8B8UnormBlock),
-            148 => Some(VkFormat::Etc2R8G8B8SrgbBlock),
-            149 => Some(VkFormat::Etc2R8G8B8A1UnormBlock),
-            150 => Some(VkFormat::Etc2R8G8B8A1SrgbBlock),
-            151 => Some(VkFormat::Etc2R8G8B8A8UnormBlock),
-            152 => Some(VkFormat::Etc2R8G8B8A8SrgbBlock),
-            157 => Some(VkFormat::Astc4x4UnormBlock),
-            158 => Some(VkFormat::Astc4x4SrgbBlock),
-            165 => Some(VkFormat::Astc8x8UnormBlock),
-            172 => Some(VkFormat::Astc8x8SrgbBlock),
-            _ => None,
+    /// sRGB formats return themselves. Returns None if the format has no sRGB pair.
+    pub fn srgb_variant(&self) -> Option<Self> {
+        if self.is_srgb() {
+            return Some(*self);
         }
+
+        Self::SRGB_PAIRS
+            .iter()
+            .find(|(unorm, _)| unorm == self)
+            .map(|&(_, srgb)| srgb)
+    }
+
+    /// Returns the UNORM counterpart of the format
+    ///
+    /// Formats that have an sRGB pair and are already UNORM return themselves.
+    /// Returns None if the format has no sRGB/UNORM pair.
+    pub fn unorm_variant(&self) -> Option<Self> {
+        Self::SRGB_PAIRS
+            .iter()
+            .find(|(unorm, srgb)| unorm == self || srgb == self)
+            .map(|&(unorm, _)| unorm)
+    }
+
+    /// UNORM/sRGB format pairs
+    const SRGB_PAIRS: [(VkFormat, VkFormat); 33] = [
+        (VkFormat::R8Unorm, VkFormat::R8Srgb),
+        (VkFormat::R8G8Unorm, VkFormat::R8G8Srgb),
+        (VkFormat::R8G8B8Unorm, VkFormat::R8G8B8Srgb),
+        (VkFormat::B8G8R8Unorm, VkFormat::B8G8R8Srgb),
+        (VkFormat::R8G8B8A8Unorm, VkFormat::R8G8B8A8Srgb),
+        (VkFormat::B8G8R8A8Unorm, VkFormat::B8G8R8A8Srgb),
+        (VkFormat::A8B8G8R8UnormPack32, VkFormat::A8B8G8R8SrgbPack32),
+        (VkFormat::Bc1RgbUnormBlock, VkFormat::Bc1RgbSrgbBlock),
+        (VkFormat::Bc1RgbaUnormBlock, VkFormat::Bc1RgbaSrgbBlock),
+        (VkFormat::Bc2UnormBlock, VkFormat::Bc2SrgbBlock),
+        (VkFormat::Bc3UnormBlock, VkFormat::Bc3SrgbBlock),
+        (VkFormat::Bc7UnormBlock, VkFormat::Bc7SrgbBlock),
+        (
+            VkFormat::Etc2R8G8B8UnormBlock,
+            VkFormat::Etc2R8G8B8SrgbBlock,
+        ),
+        (
+            VkFormat::Etc2R8G8B8A1UnormBlock,
+            VkFormat::Etc2R8G8B8A1SrgbBlock,
+        ),
+        (
+            VkFormat::Etc2R8G8B8A8UnormBlock,
+            VkFormat::Etc2R8G8B8A8SrgbBlock,
+        ),
+        (VkFormat::Astc4x4UnormBlock, VkFormat::Astc4x4SrgbBlock),
+        (VkFormat::Astc5x4UnormBlock, VkFormat::Astc5x4SrgbBlock),
+        (VkFormat::Astc5x5UnormBlock, VkFormat::Astc5x5SrgbBlock),
+        (VkFormat::Astc6x5UnormBlock, VkFormat::Astc6x5SrgbBlock),
+        (VkFormat::Astc6x6UnormBlock, VkFormat::Astc6x6SrgbBlock),
+        (VkFormat::Astc8x5UnormBlock, VkFormat::Astc8x5SrgbBlock),
+        (VkFormat::Astc8x6UnormBlock, VkFormat::Astc8x6SrgbBlock),
+        (VkFormat::Astc8x8UnormBlock, VkFormat::Astc8x8SrgbBlock),
+        (VkFormat::Astc10x5UnormBlock, VkFormat::Astc10x5SrgbBlock),
+        (VkFormat::Astc10x6UnormBlock, VkFormat::Astc10x6SrgbBlock),
+        (VkFormat::Astc10x8UnormBlock, VkFormat::Astc10x8SrgbBlock),
+        (VkFormat::Astc10x10UnormBlock, VkFormat::Astc10x10SrgbBlock),
+        (VkFormat::Astc12x10UnormBlock, VkFormat::Astc12x10SrgbBlock),
+        (VkFormat::Astc12x12UnormBlock, VkFormat::Astc12x12SrgbBlock),
+        (
+            VkFormat::Pvrtc1_2bppUnormBlockImg,
+            VkFormat::Pvrtc1_2bppSrgbBlockImg,
+        ),
+        (
+            VkFormat::Pvrtc1_4bppUnormBlockImg,
+            VkFormat::Pvrtc1_4bppSrgbBlockImg,
+        ),
+        (
+            VkFormat::Pvrtc2_2bppUnormBlockImg,
+            VkFormat::Pvrtc2_2bppSrgbBlockImg,
+        ),
+        (
+            VkFormat::Pvrtc2_4bppUnormBlockImg,
+            VkFormat::Pvrtc2_4bppSrgbBlockImg,
+        ),
+    ];
+}
+
+impl fmt::Display for VkFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
     }
 }
 
@@ -0,0 +1,223 @@
+//! Mappings between this crate's formats and `wgpu` texture formats.
+//!
+//! The types come from `wgpu-types`, which `wgpu` re-exports, so they are the
+//! same `wgpu::TextureFormat` and `wgpu::Features` as long as both crates are
+//! on the same major version.
+
+use wgpu_types::{AstcBlock, AstcChannel, Features, TextureFormat};
+
+use crate::format::TranscodeFormat;
+use crate::vk_format::VkFormat;
+
+const fn astc(block: AstcBlock, channel: AstcChannel) -> TextureFormat {
+    TextureFormat::Astc { block, channel }
+}
+
+const fn astc_4x4(srgb: bool) -> TextureFormat {
+    let channel = if srgb {
+        AstcChannel::UnormSrgb
+    } else {
+        AstcChannel::Unorm
+    };
+    astc(AstcBlock::B4x4, channel)
+}
+
+impl TranscodeFormat {
+    /// Get the `wgpu` format a texture transcoded to this target is uploaded as
+    ///
+    /// `srgb` selects the sRGB variant where one exists; BC4 and BC5 have none
+    /// and ignore it. Returns `None` for targets `wgpu` cannot sample: PVRTC and
+    /// the packed 16-bit formats. [`TranscodeFormat::Bc1Rgb`] maps to
+    /// `Bc1RgbaUnorm`, as `wgpu` has no opaque-only BC1 format.
+    pub fn to_wgpu(&self, srgb: bool) -> Option<TextureFormat> {
+        let format = match (self, srgb) {
+            (TranscodeFormat::Etc1Rgb, false) => TextureFormat::Etc2Rgb8Unorm,
+            (TranscodeFormat::Etc1Rgb, true) => TextureFormat::Etc2Rgb8UnormSrgb,
+            (TranscodeFormat::Etc2Rgba, false) => TextureFormat::Etc2Rgba8Unorm,
+            (TranscodeFormat::Etc2Rgba, true) => TextureFormat::Etc2Rgba8UnormSrgb,
+            (TranscodeFormat::Bc1Rgb, false) => TextureFormat::Bc1RgbaUnorm,
+            (TranscodeFormat::Bc1Rgb, true) => TextureFormat::Bc1RgbaUnormSrgb,
+            (TranscodeFormat::Bc3Rgba, false) => TextureFormat::Bc3RgbaUnorm,
+            (TranscodeFormat::Bc3Rgba, true) => TextureFormat::Bc3RgbaUnormSrgb,
+            (TranscodeFormat::Bc4R, _) => TextureFormat::Bc4RUnorm,
+            (TranscodeFormat::Bc5Rg, _) => TextureFormat::Bc5RgUnorm,
+            (TranscodeFormat::Bc7Rgba, false) => TextureFormat::Bc7RgbaUnorm,
+            (TranscodeFormat::Bc7Rgba, true) => TextureFormat::Bc7RgbaUnormSrgb,
+            (TranscodeFormat::Astc_4x4_Rgba, srgb) => astc_4x4(srgb),
+            (TranscodeFormat::Rgba32, false) => TextureFormat::Rgba8Unorm,
+            (TranscodeFormat::Rgba32, true) => TextureFormat::Rgba8UnormSrgb,
+            (
+                TranscodeFormat::Pvrtc1_4_Rgb
+                | TranscodeFormat::Pvrtc1_4_Rgba
+                | TranscodeFormat::Rgb565
+                | TranscodeFormat::Bgr565
+                | TranscodeFormat::Rgba4444,
+                _,
+            ) => return None,
+        };
+        Some(format)
+    }
+}
+
+impl VkFormat {
+    /// Get the equivalent `wgpu` texture format
+    ///
+    /// Returns `None` for formats `wgpu` has no counterpart for, such as the
+    /// 24-bit, scaled, 64-bit, packed 16-bit, PVRTC and YCbCr formats, and for
+    /// depth formats whose `wgpu` equivalent does not guarantee the same
+    /// layout (`X8D24UnormPack32`, `D24UnormS8Uint`). `Bc1RgbUnormBlock` maps
+    /// to `Bc1RgbaUnorm`.
+    pub fn to_wgpu(&self) -> Option<TextureFormat> {
+        let format = match self {
+            VkFormat::R8Unorm => TextureFormat::R8Unorm,
+            VkFormat::R8Snorm => TextureFormat::R8Snorm,
+            VkFormat::R8Uint => TextureFormat::R8Uint,
+            VkFormat::R8Sint => TextureFormat::R8Sint,
+            VkFormat::R8G8Unorm => TextureFormat::Rg8Unorm,
+            VkFormat::R8G8Snorm => TextureFormat::Rg8Snorm,
+            VkFormat::R8G8Uint => TextureFormat::Rg8Uint,
+            VkFormat::R8G8Sint => TextureFormat::Rg8Sint,
+            VkFormat::R8G8B8A8Unorm => TextureFormat::Rgba8Unorm,
+            VkFormat::R8G8B8A8Snorm => TextureFormat::Rgba8Snorm,
+            VkFormat::R8G8B8A8Uint => TextureFormat::Rgba8Uint,
+            VkFormat::R8G8B8A8Sint => TextureFormat::Rgba8Sint,
+            VkFormat::R8G8B8A8Srgb => TextureFormat::Rgba8UnormSrgb,
+            VkFormat::B8G8R8A8Unorm => TextureFormat::Bgra8Unorm,
+            VkFormat::B8G8R8A8Srgb => TextureFormat::Bgra8UnormSrgb,
+            VkFormat::A2B10G10R10UnormPack32 => TextureFormat::Rgb10a2Unorm,
+            VkFormat::A2B10G10R10UintPack32 => TextureFormat::Rgb10a2Uint,
+            VkFormat::R16Unorm => TextureFormat::R16Unorm,
+            VkFormat::R16Snorm => TextureFormat::R16Snorm,
+            VkFormat::R16Uint => TextureFormat::R16Uint,
+            VkFormat::R16Sint => TextureFormat::R16Sint,
+            VkFormat::R16Sfloat => TextureFormat::R16Float,
+            VkFormat::R16G16Unorm => TextureFormat::Rg16Unorm,
+            VkFormat::R16G16Snorm => TextureFormat::Rg16Snorm,
+            VkFormat::R16G16Uint => TextureFormat::Rg16Uint,
+            VkFormat::R16G16Sint => TextureFormat::Rg16Sint,
+            VkFormat::R16G16Sfloat => TextureFormat::Rg16Float,
+            VkFormat::R16G16B16A16Unorm => TextureFormat::Rgba16Unorm,
+            VkFormat::R16G16B16A16Snorm => TextureFormat::Rgba16Snorm,
+            VkFormat::R16G16B16A16Uint => TextureFormat::Rgba16Uint,
+            VkFormat::R16G16B16A16Sint => TextureFormat::Rgba16Sint,
+            VkFormat::R16G16B16A16Sfloat => TextureFormat::Rgba16Float,
+            VkFormat::R32Uint => TextureFormat::R32Uint,
+            VkFormat::R32Sint => TextureFormat::R32Sint,
+            VkFormat::R32Sfloat => TextureFormat::R32Float,
+            VkFormat::R32G32Uint => TextureFormat::Rg32Uint,
+            VkFormat::R32G32Sint => TextureFormat::Rg32Sint,
+            VkFormat::R32G32Sfloat => TextureFormat::Rg32Float,
+            VkFormat::R32G32B32A32Uint => TextureFormat::Rgba32Uint,
+            VkFormat::R32G32B32A32Sint => TextureFormat::Rgba32Sint,
+            VkFormat::R32G32B32A32Sfloat => TextureFormat::Rgba32Float,
+            VkFormat::E5B9G9R9UfloatPack32 => TextureFormat::Rgb9e5Ufloat,
+            VkFormat::D16Unorm => TextureFormat::Depth16Unorm,
+            VkFormat::D32Sfloat => TextureFormat::Depth32Float,
+            VkFormat::S8Uint => TextureFormat::Stencil8,
+            VkFormat::D32SfloatS8Uint => TextureFormat::Depth32FloatStencil8,
+            VkFormat::Bc1RgbUnormBlock | VkFormat::Bc1RgbaUnormBlock => TextureFormat::Bc1RgbaUnorm,
+            VkFormat::Bc1RgbSrgbBlock | VkFormat::Bc1RgbaSrgbBlock => {
+                TextureFormat::Bc1RgbaUnormSrgb
+            }
+            VkFormat::Bc2UnormBlock => TextureFormat::Bc2RgbaUnorm,
+            VkFormat::Bc2SrgbBlock => TextureFormat::Bc2RgbaUnormSrgb,
+            VkFormat::Bc3UnormBlock => TextureFormat::Bc3RgbaUnorm,
+            VkFormat::Bc3SrgbBlock => TextureFormat::Bc3RgbaUnormSrgb,
+            VkFormat::Bc4UnormBlock => TextureFormat::Bc4RUnorm,
+            VkFormat::Bc4SnormBlock => TextureFormat::Bc4RSnorm,
+            VkFormat::Bc5UnormBlock => TextureFormat::Bc5RgUnorm,
+            VkFormat::Bc5SnormBlock => TextureFormat::Bc5RgSnorm,
+            VkFormat::Bc6hUfloatBlock => TextureFormat::Bc6hRgbUfloat,
+            VkFormat::Bc6hSfloatBlock => TextureFormat::Bc6hRgbFloat,
+            VkFormat::Bc7UnormBlock => TextureFormat::Bc7RgbaUnorm,
+            VkFormat::Bc7SrgbBlock => TextureFormat::Bc7RgbaUnormSrgb,
+            VkFormat::Etc2R8G8B8UnormBlock => TextureFormat::Etc2Rgb8Unorm,
+            VkFormat::Etc2R8G8B8SrgbBlock => TextureFormat::Etc2Rgb8UnormSrgb,
+            VkFormat::Etc2R8G8B8A1UnormBlock => TextureFormat::Etc2Rgb8A1Unorm,
+            VkFormat::Etc2R8G8B8A1SrgbBlock => TextureFormat::Etc2Rgb8A1UnormSrgb,
+            VkFormat::Etc2R8G8B8A8UnormBlock => TextureFormat::Etc2Rgba8Unorm,
+            VkFormat::Etc2R8G8B8A8SrgbBlock => TextureFormat::Etc2Rgba8UnormSrgb,
+            VkFormat::EacR11UnormBlock => TextureFormat::EacR11Unorm,
+            VkFormat::EacR11SnormBlock => TextureFormat::EacR11Snorm,
+            VkFormat::EacR11G11UnormBlock => TextureFormat::EacRg11Unorm,
+            VkFormat::EacR11G11SnormBlock => TextureFormat::EacRg11Snorm,
+            VkFormat::Astc4x4UnormBlock => astc(AstcBlock::B4x4, AstcChannel::Unorm),
+            VkFormat::Astc4x4SrgbBlock => astc(AstcBlock::B4x4, AstcChannel::UnormSrgb),
+            VkFormat::Astc4x4SfloatBlock => astc(AstcBlock::B4x4, AstcChannel::Hdr),
+            VkFormat::Astc5x4UnormBlock => astc(AstcBlock::B5x4, AstcChannel::Unorm),
+            VkFormat::Astc5x4SrgbBlock => astc(AstcBlock::B5x4, AstcChannel::UnormSrgb),
+            VkFormat::Astc5x4SfloatBlock => astc(AstcBlock::B5x4, AstcChannel::Hdr),
+            VkFormat::Astc5x5UnormBlock => astc(AstcBlock::B5x5, AstcChannel::Unorm),
+            VkFormat::Astc5x5SrgbBlock => astc(AstcBlock::B5x5, AstcChannel::UnormSrgb),
+            VkFormat::Astc5x5SfloatBlock => astc(AstcBlock::B5x5, AstcChannel::Hdr),
+            VkFormat::Astc6x5UnormBlock => astc(AstcBlock::B6x5, AstcChannel::Unorm),
+            VkFormat::Astc6x5SrgbBlock => astc(AstcBlock::B6x5, AstcChannel::UnormSrgb),
+            VkFormat::Astc6x5SfloatBlock => astc(AstcBlock::B6x5, AstcChannel::Hdr),
+            VkFormat::Astc6x6UnormBlock => astc(AstcBlock::B6x6, AstcChannel::Unorm),
+            VkFormat::Astc6x6SrgbBlock => astc(AstcBlock::B6x6, AstcChannel::UnormSrgb),
+            VkFormat::Astc6x6SfloatBlock => astc(AstcBlock::B6x6, AstcChannel::Hdr),
+            VkFormat::Astc8x5UnormBlock => astc(AstcBlock::B8x5, AstcChannel::Unorm),
+            VkFormat::Astc8x5SrgbBlock => astc(AstcBlock::B8x5, AstcChannel::UnormSrgb),
+            VkFormat::Astc8x5SfloatBlock => astc(AstcBlock::B8x5, AstcChannel::Hdr),
+            VkFormat::Astc8x6UnormBlock => astc(AstcBlock::B8x6, AstcChannel::Unorm),
+            VkFormat::Astc8x6SrgbBlock => astc(AstcBlock::B8x6, AstcChannel::UnormSrgb),
+            VkFormat::Astc8x6SfloatBlock => astc(AstcBlock::B8x6, AstcChannel::Hdr),
+            VkFormat::Astc8x8UnormBlock => astc(AstcBlock::B8x8, AstcChannel::Unorm),
+            VkFormat::Astc8x8SrgbBlock => astc(AstcBlock::B8x8, AstcChannel::UnormSrgb),
+            VkFormat::Astc8x8SfloatBlock => astc(AstcBlock::B8x8, AstcChannel::Hdr),
+            VkFormat::Astc10x5UnormBlock => astc(AstcBlock::B10x5, AstcChannel::Unorm),
+            VkFormat::Astc10x5SrgbBlock => astc(AstcBlock::B10x5, AstcChannel::UnormSrgb),
+            VkFormat::Astc10x5SfloatBlock => astc(AstcBlock::B10x5, AstcChannel::Hdr),
+            VkFormat::Astc10x6UnormBlock => astc(AstcBlock::B10x6, AstcChannel::Unorm),
+            VkFormat::Astc10x6SrgbBlock => astc(AstcBlock::B10x6, AstcChannel::UnormSrgb),
+            VkFormat::Astc10x6SfloatBlock => astc(AstcBlock::B10x6, AstcChannel::Hdr),
+            VkFormat::Astc10x8UnormBlock => astc(AstcBlock::B10x8, AstcChannel::Unorm),
+            VkFormat::Astc10x8SrgbBlock => astc(AstcBlock::B10x8, AstcChannel::UnormSrgb),
+            VkFormat::Astc10x8SfloatBlock => astc(AstcBlock::B10x8, AstcChannel::Hdr),
+            VkFormat::Astc10x10UnormBlock => astc(AstcBlock::B10x10, AstcChannel::Unorm),
+            VkFormat::Astc10x10SrgbBlock => astc(AstcBlock::B10x10, AstcChannel::UnormSrgb),
+            VkFormat::Astc10x10SfloatBlock => astc(AstcBlock::B10x10, AstcChannel::Hdr),
+            VkFormat::Astc12x10UnormBlock => astc(AstcBlock::B12x10, AstcChannel::Unorm),
+            VkFormat::Astc12x10SrgbBlock => astc(AstcBlock::B12x10, AstcChannel::UnormSrgb),
+            VkFormat::Astc12x10SfloatBlock => astc(AstcBlock::B12x10, AstcChannel::Hdr),
+            VkFormat::Astc12x12UnormBlock => astc(AstcBlock::B12x12, AstcChannel::Unorm),
+            VkFormat::Astc12x12SrgbBlock => astc(AstcBlock::B12x12, AstcChannel::UnormSrgb),
+            VkFormat::Astc12x12SfloatBlock => astc(AstcBlock::B12x12, AstcChannel::Hdr),
+            _ => return None,
+        };
+        Some(format)
+    }
+}
+
+/// Pick the transcode target for a device with the given `wgpu` features
+///
+/// Follows the usual preference order: BC7 when BC compression is available,
+/// then ASTC 4x4, then ETC2 (ETC1 for opaque textures, since it is half the
+/// size), falling back to uncompressed RGBA32 which every device can sample.
+///
+/// # Examples
+///
+/// ```rust
+/// use ktx2_rw::{pick_transcode_format_for_wgpu, TranscodeFormat};
+///
+/// let features = wgpu_types::Features::TEXTURE_COMPRESSION_ETC2;
+/// assert_eq!(
+///     pick_transcode_format_for_wgpu(features, false),
+///     TranscodeFormat::Etc1Rgb
+/// );
+/// ```
+pub fn pick_transcode_format_for_wgpu(features: Features, needs_alpha: bool) -> TranscodeFormat {
+    if features.contains(Features::TEXTURE_COMPRESSION_BC) {
+        TranscodeFormat::Bc7Rgba
+    } else if features.contains(Features::TEXTURE_COMPRESSION_ASTC) {
+        TranscodeFormat::Astc_4x4_Rgba
+    } else if features.contains(Features::TEXTURE_COMPRESSION_ETC2) {
+        if needs_alpha {
+            TranscodeFormat::Etc2Rgba
+        } else {
+            TranscodeFormat::Etc1Rgb
+        }
+    } else {
+        TranscodeFormat::Rgba32
+    }
+}